@@ -17,6 +17,7 @@ use crate::{
 };
 use alloc::{boxed::Box, format, string::String, sync::Arc};
 use dimas_core::builder_states::{Callback, NoCallback, NoStorage, Storage};
+use dimas_core::handle::CapabilityHandle;
 use dimas_core::{Result, enums::OperationState, traits::Context, utils::selector_from};
 use futures::future::Future;
 #[cfg(feature = "std")]
@@ -255,6 +256,7 @@ where
 			.ok_or_else(|| Error::NoZenohSession)?;
 		Ok(LivelinessSubscriber::new(
 			session,
+			session_id,
 			token,
 			context,
 			activation_state,
@@ -274,17 +276,30 @@ where
 	P: Send + Sync + 'static,
 {
 	/// Build and add the liveliness subscriber to the agent
+	/// Returns a [`CapabilityHandle`] to remove, pause or resume it later.
 	/// # Errors
-	///
-	pub fn add(self) -> Result<Option<Box<dyn LivelinessSubscriberTrait>>> {
+	/// if a capability with the same selector is already registered
+	pub fn add(self) -> Result<CapabilityHandle<P, Box<dyn LivelinessSubscriberTrait>>> {
 		let c = self.storage.storage.clone();
+		let context = self.context.clone();
 		let s = self.build()?;
+		let key = s.token().clone();
+		let capability: Box<dyn LivelinessSubscriberTrait> = Box::new(s);
+		CapabilityHandle::register(key, capability, context, c)
+	}
 
-		let r = c
-			.write()
-			.map_err(|_| Error::MutexPoison(String::from("LivelinessSubscriberBuilder")))?
-			.insert(s.token().into(), Box::new(s));
-		Ok(r)
+	/// Build and add the liveliness subscriber to the agent
+	/// An already registered one with the same selector is stopped and replaced.
+	/// Returns a [`CapabilityHandle`] to remove, pause or resume it later.
+	/// # Errors
+	/// if the storage is not accessible
+	pub fn replace(self) -> Result<CapabilityHandle<P, Box<dyn LivelinessSubscriberTrait>>> {
+		let c = self.storage.storage.clone();
+		let context = self.context.clone();
+		let s = self.build()?;
+		let key = s.token().clone();
+		let capability: Box<dyn LivelinessSubscriberTrait> = Box::new(s);
+		CapabilityHandle::replace(key, capability, context, c)
 	}
 }
 // endregion:	--- LivelinessSubscriberBuilder
@@ -20,7 +20,7 @@ pub use observer_builder::ObserverBuilder;
 pub use publisher_builder::PublisherBuilder;
 pub use querier_builder::QuerierBuilder;
 pub use queryable_builder::QueryableBuilder;
-pub use subscriber_builder::SubscriberBuilder;
+pub use subscriber_builder::{SubscriberBuilder, TypedSubscriberBuilder};
 // endregion: 	--- modules
 
 #[cfg(test)]
@@ -24,10 +24,12 @@ use alloc::{
 };
 use core::time::Duration;
 use dimas_core::builder_states::{Callback, NoCallback, NoSelector, NoStorage, Selector, Storage};
+use dimas_core::handle::CapabilityHandle;
 use dimas_core::{
 	Result,
 	enums::OperationState,
 	message_types::{ControlResponse, Message},
+	policies::{ArcErrorCallback, ErrorCallback, ErrorHandler, ErrorPolicy},
 	traits::Context,
 	utils::selector_from,
 };
@@ -48,6 +50,8 @@ where
 	/// Context for the `ObservableBuilder`
 	context: Context<P>,
 	activation_state: OperationState,
+	error_policy: ErrorPolicy,
+	error_callback: Option<ArcErrorCallback<P>>,
 	feedback_interval: Duration,
 	selector: K,
 	control_callback: CC,
@@ -67,6 +71,8 @@ where
 			session_id: session_id.into(),
 			context,
 			activation_state: OperationState::Active,
+			error_policy: ErrorPolicy::Log,
+			error_callback: None,
 			feedback_interval: Duration::from_millis(100),
 			selector: NoSelector,
 			control_callback: NoCallback,
@@ -88,6 +94,25 @@ where
 		self
 	}
 
+	/// Set the [`ErrorPolicy`] for errors returned by the callbacks, default is [`ErrorPolicy::Log`].
+	#[must_use]
+	pub const fn error_policy(mut self, policy: ErrorPolicy) -> Self {
+		self.error_policy = policy;
+		self
+	}
+
+	/// Set a callback, which is informed with the selector about every error returned by the callbacks.
+	#[must_use]
+	pub fn on_error<F>(mut self, callback: F) -> Self
+	where
+		F: FnMut(Context<P>, &str, &(dyn core::error::Error + Send + Sync)) + Send + Sync + 'static,
+	{
+		let callback: ErrorCallback<P> = Box::new(callback);
+		self.error_callback
+			.replace(Arc::new(std::sync::Mutex::new(callback)));
+		self
+	}
+
 	/// Set the feedback interval.
 	#[must_use]
 	pub const fn feedback_interval(mut self, interval: Duration) -> Self {
@@ -114,6 +139,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			feedback_interval,
 			storage,
 			control_callback,
@@ -125,6 +152,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			feedback_interval,
 			selector: Selector {
 				selector: selector.into(),
@@ -163,6 +192,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			feedback_interval,
 			selector,
 			storage,
@@ -176,6 +207,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			feedback_interval,
 			selector,
 			control_callback: Callback { callback },
@@ -204,6 +237,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			feedback_interval,
 			selector,
 			storage,
@@ -217,6 +252,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			feedback_interval,
 			selector,
 			control_callback,
@@ -245,6 +282,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			feedback_interval,
 			selector,
 			storage,
@@ -258,6 +297,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			feedback_interval,
 			selector,
 			control_callback,
@@ -282,6 +323,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			feedback_interval,
 			selector,
 			control_callback,
@@ -293,6 +336,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			feedback_interval,
 			selector,
 			control_callback,
@@ -329,6 +374,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			feedback_interval,
 			selector,
 			control_callback,
@@ -341,9 +388,11 @@ where
 			.ok_or_else(|| Error::NoZenohSession)?;
 		Ok(Observable::new(
 			session,
+			session_id,
 			selector.selector,
 			context,
 			activation_state,
+			ErrorHandler::new(error_policy, error_callback),
 			feedback_interval,
 			control_callback.callback,
 			feedback_callback.callback,
@@ -365,17 +414,30 @@ where
 	P: Send + Sync + 'static,
 {
 	/// Build and add the observable to the agents context
+	/// Returns a [`CapabilityHandle`] to remove, pause or resume it later.
 	/// # Errors
-	///
-	pub fn add(self) -> Result<Option<Box<dyn Responder>>> {
+	/// if a capability with the same selector is already registered
+	pub fn add(self) -> Result<CapabilityHandle<P, Box<dyn Responder>>> {
 		let collection = self.storage.storage.clone();
+		let context = self.context.clone();
 		let q = self.build()?;
+		let key = q.selector().to_string();
+		let capability: Box<dyn Responder> = Box::new(q);
+		CapabilityHandle::register(key, capability, context, collection)
+	}
 
-		let r = collection
-			.write()
-			.map_err(|_| Error::MutexPoison(String::from("ObservableBuilder")))?
-			.insert(q.selector().to_string(), Box::new(q));
-		Ok(r)
+	/// Build and add the observable to the agents context
+	/// An already registered one with the same selector is stopped and replaced.
+	/// Returns a [`CapabilityHandle`] to remove, pause or resume it later.
+	/// # Errors
+	/// if the storage is not accessible
+	pub fn replace(self) -> Result<CapabilityHandle<P, Box<dyn Responder>>> {
+		let collection = self.storage.storage.clone();
+		let context = self.context.clone();
+		let q = self.build()?;
+		let key = q.selector().to_string();
+		let capability: Box<dyn Responder> = Box::new(q);
+		CapabilityHandle::replace(key, capability, context, collection)
 	}
 }
 // endregion:	--- ObservableBuilder
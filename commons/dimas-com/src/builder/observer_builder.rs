@@ -21,6 +21,7 @@ use alloc::{
 };
 use core::time::Duration;
 use dimas_core::builder_states::{Callback, NoCallback, NoSelector, NoStorage, Selector, Storage};
+use dimas_core::handle::CapabilityHandle;
 use dimas_core::{
 	Result,
 	enums::OperationState,
@@ -278,6 +279,7 @@ where
 			.ok_or_else(|| Error::NoZenohSession)?;
 		Ok(Observer::new(
 			session,
+			session_id,
 			selector,
 			context,
 			activation_state,
@@ -300,18 +302,31 @@ where
 	P: Send + Sync + 'static,
 {
 	/// Build and add the [`Observer`] to the `Agent`.
+	/// Returns a [`CapabilityHandle`] to remove, pause or resume it later.
 	///
 	/// # Errors
-	/// Currently none
-	pub fn add(self) -> Result<Option<Box<dyn ObserverTrait>>> {
+	/// if a capability with the same selector is already registered
+	pub fn add(self) -> Result<CapabilityHandle<P, Box<dyn ObserverTrait>>> {
 		let c = self.storage.storage.clone();
+		let context = self.context.clone();
 		let s = self.build()?;
+		let key = s.selector().to_string();
+		let capability: Box<dyn ObserverTrait> = Box::new(s);
+		CapabilityHandle::register(key, capability, context, c)
+	}
 
-		let r = c
-			.write()
-			.map_err(|_| Error::MutexPoison(String::from("ObserverBuilder")))?
-			.insert(s.selector().to_string(), Box::new(s));
-		Ok(r)
+	/// Build and add the [`Observer`] to the `Agent`
+	/// An already registered one with the same selector is stopped and replaced.
+	/// Returns a [`CapabilityHandle`] to remove, pause or resume it later.
+	/// # Errors
+	/// if the storage is not accessible
+	pub fn replace(self) -> Result<CapabilityHandle<P, Box<dyn ObserverTrait>>> {
+		let c = self.storage.storage.clone();
+		let context = self.context.clone();
+		let s = self.build()?;
+		let key = s.selector().to_string();
+		let capability: Box<dyn ObserverTrait> = Box::new(s);
+		CapabilityHandle::replace(key, capability, context, c)
 	}
 }
 // endregion:	--- ObserverBuilder
@@ -11,13 +11,16 @@ extern crate std;
 // region:		--- modules
 use crate::error::Error;
 use crate::traits::Publisher as PublisherTrait;
-use crate::zenoh::publisher::Publisher;
+use crate::zenoh::publisher::{Publisher, TypedPublisher};
 use alloc::{
 	boxed::Box,
 	string::{String, ToString},
 	sync::Arc,
 };
+use bitcode::Encode;
 use dimas_core::builder_states::{NoSelector, NoStorage, Selector, Storage};
+use dimas_core::codec::{Bitcode, Codec, Encoder};
+use dimas_core::handle::CapabilityHandle;
 use dimas_core::{Result, enums::OperationState, traits::Context, utils::selector_from};
 #[cfg(feature = "std")]
 use std::{collections::HashMap, sync::RwLock};
@@ -114,6 +117,16 @@ where
 		self
 	}
 
+	/// Set the publishers encoding to the one of the [`Codec`] `C`
+	#[must_use]
+	pub fn codec<C>(mut self) -> Self
+	where
+		C: Codec,
+	{
+		self.encoding = C::ENCODING.to_string();
+		self
+	}
+
 	/// Set the publishers enexpress policy
 	#[must_use]
 	pub const fn set_express(mut self, express: bool) -> Self {
@@ -245,6 +258,7 @@ where
 			.ok_or_else(|| Error::NoZenohSession)?;
 		Ok(Publisher::new(
 			session,
+			self.session_id,
 			self.selector.selector,
 			self.activation_state,
 			#[cfg(feature = "unstable")]
@@ -264,17 +278,81 @@ where
 	P: Send + Sync + 'static,
 {
 	/// Build and add the [Publisher] to the `Agent`s context
+	/// Returns a [`CapabilityHandle`] to remove, pause or resume it later.
 	///
 	/// # Errors
-	/// Currently none
-	pub fn add(self) -> Result<Option<Box<dyn PublisherTrait>>> {
+	/// if a capability with the same selector is already registered
+	pub fn add(self) -> Result<CapabilityHandle<P, Box<dyn PublisherTrait>>> {
 		let collection = self.storage.storage.clone();
+		let context = self.context.clone();
 		let p = self.build()?;
-		let r = collection
-			.write()
-			.map_err(|_| Error::MutexPoison(String::from("PublisherBuilder")))?
-			.insert(p.selector().to_string(), Box::new(p));
-		Ok(r)
+		let key = p.selector().to_string();
+		let capability: Box<dyn PublisherTrait> = Box::new(p);
+		CapabilityHandle::register(key, capability, context, collection)
+	}
+
+	/// Build and add the [Publisher] to the `Agent`s context
+	/// An already registered one with the same selector is stopped and replaced.
+	/// Returns a [`CapabilityHandle`] to remove, pause or resume it later.
+	/// # Errors
+	/// if the storage is not accessible
+	pub fn replace(self) -> Result<CapabilityHandle<P, Box<dyn PublisherTrait>>> {
+		let collection = self.storage.storage.clone();
+		let context = self.context.clone();
+		let p = self.build()?;
+		let key = p.selector().to_string();
+		let capability: Box<dyn PublisherTrait> = Box::new(p);
+		CapabilityHandle::replace(key, capability, context, collection)
+	}
+
+	/// Build and add the [Publisher] to the `Agent`s context
+	/// Returns a [`TypedPublisher`] publishing values of type `T` encoded with [`Bitcode`].
+	///
+	/// # Errors
+	/// if a capability with the same selector is already registered
+	pub fn add_typed<T>(self) -> Result<TypedPublisher<P, T>>
+	where
+		T: Encode,
+	{
+		self.add_typed_with::<T, Bitcode>()
+	}
+
+	/// Build and add the [Publisher] to the `Agent`s context
+	/// Returns a [`TypedPublisher`] publishing values of type `T` encoded with the [`Codec`] `C`.
+	///
+	/// # Errors
+	/// if a capability with the same selector is already registered
+	pub fn add_typed_with<T, C>(self) -> Result<TypedPublisher<P, T, C>>
+	where
+		C: Encoder<T>,
+	{
+		let context = self.context.clone();
+		Ok(TypedPublisher::new(self.codec::<C>().add()?, context))
+	}
+
+	/// Build and add the [Publisher] to the `Agent`s context
+	/// An already registered one with the same selector is stopped and replaced.
+	/// Returns a [`TypedPublisher`] publishing values of type `T` encoded with [`Bitcode`].
+	/// # Errors
+	/// if the storage is not accessible
+	pub fn replace_typed<T>(self) -> Result<TypedPublisher<P, T>>
+	where
+		T: Encode,
+	{
+		self.replace_typed_with::<T, Bitcode>()
+	}
+
+	/// Build and add the [Publisher] to the `Agent`s context
+	/// An already registered one with the same selector is stopped and replaced.
+	/// Returns a [`TypedPublisher`] publishing values of type `T` encoded with the [`Codec`] `C`.
+	/// # Errors
+	/// if the storage is not accessible
+	pub fn replace_typed_with<T, C>(self) -> Result<TypedPublisher<P, T, C>>
+	where
+		C: Encoder<T>,
+	{
+		let context = self.context.clone();
+		Ok(TypedPublisher::new(self.codec::<C>().replace()?, context))
 	}
 }
 // endregion:	--- PublisherBuilder
@@ -19,9 +19,11 @@ use alloc::{
 };
 use core::time::Duration;
 use dimas_core::builder_states::{Callback, NoCallback, NoSelector, NoStorage, Selector, Storage};
+use dimas_core::codec::Codec;
+use dimas_core::handle::CapabilityHandle;
 use dimas_core::{
-	Result, enums::OperationState, message_types::QueryableMsg, traits::Context,
-	utils::selector_from,
+	Result, enums::OperationState, message_types::QueryableMsg, policies::RetryPolicy,
+	traits::Context, utils::selector_from,
 };
 use futures::Future;
 #[cfg(feature = "std")]
@@ -52,6 +54,7 @@ where
 	allowed_destination: Locality,
 	encoding: String,
 	timeout: Duration,
+	retry: RetryPolicy,
 	selector: K,
 	callback: C,
 	storage: S,
@@ -74,6 +77,7 @@ where
 			allowed_destination: Locality::Any,
 			encoding: Encoding::default().to_string(),
 			timeout: Duration::from_millis(100),
+			retry: RetryPolicy::new(),
 			selector: NoSelector,
 			callback: NoCallback,
 			storage: NoStorage,
@@ -130,6 +134,16 @@ where
 		self
 	}
 
+	/// Set the [`Querier`]s encoding to the one of the [`Codec`] `D`
+	#[must_use]
+	pub fn codec<D>(mut self) -> Self
+	where
+		D: Codec,
+	{
+		self.encoding = D::ENCODING.to_string();
+		self
+	}
+
 	/// Set a timeout for the [`Querier`].
 	/// Default is 100ms
 	#[must_use]
@@ -137,6 +151,14 @@ where
 		self.timeout = timeout;
 		self
 	}
+
+	/// Set the [`RetryPolicy`] for unanswered queries of the [`Querier`].
+	/// Default is [`RetryPolicy::new`]
+	#[must_use]
+	pub const fn retry(mut self, policy: RetryPolicy) -> Self {
+		self.retry = policy;
+		self
+	}
 }
 
 impl<P, C, S> QuerierBuilder<P, NoSelector, C, S>
@@ -154,6 +176,7 @@ where
 			allowed_destination,
 			encoding,
 			timeout,
+			retry,
 			storage,
 			callback,
 			mode,
@@ -168,6 +191,7 @@ where
 			allowed_destination,
 			encoding,
 			timeout,
+			retry,
 			selector: Selector {
 				selector: selector.into(),
 			},
@@ -209,6 +233,7 @@ where
 			allowed_destination,
 			encoding,
 			timeout,
+			retry,
 			selector,
 			storage,
 			mode,
@@ -225,6 +250,7 @@ where
 			allowed_destination,
 			encoding,
 			timeout,
+			retry,
 			selector,
 			callback: Callback { callback },
 			storage,
@@ -252,6 +278,7 @@ where
 			allowed_destination,
 			encoding,
 			timeout,
+			retry,
 			selector,
 			callback,
 			mode,
@@ -266,6 +293,7 @@ where
 			allowed_destination,
 			encoding,
 			timeout,
+			retry,
 			selector,
 			callback,
 			storage: Storage { storage },
@@ -291,6 +319,7 @@ where
 			allowed_destination,
 			encoding,
 			timeout,
+			retry,
 			selector,
 			callback: response,
 			mode,
@@ -303,6 +332,7 @@ where
 			.ok_or_else(|| Error::NoZenohSession)?;
 		Ok(Querier::new(
 			session,
+			session_id,
 			selector,
 			context,
 			activation_state,
@@ -313,6 +343,7 @@ where
 			encoding,
 			target,
 			timeout,
+			retry,
 		))
 	}
 }
@@ -322,16 +353,30 @@ where
 	P: Send + Sync + 'static,
 {
 	/// Build and add the query to the agents context
+	/// Returns a [`CapabilityHandle`] to remove, pause or resume it later.
 	/// # Errors
-	pub fn add(self) -> Result<Option<Box<dyn QuerierTrait>>> {
+	/// if a capability with the same selector is already registered
+	pub fn add(self) -> Result<CapabilityHandle<P, Box<dyn QuerierTrait>>> {
 		let collection = self.storage.storage.clone();
+		let context = self.context.clone();
 		let q = self.build()?;
+		let key = q.selector().to_string();
+		let capability: Box<dyn QuerierTrait> = Box::new(q);
+		CapabilityHandle::register(key, capability, context, collection)
+	}
 
-		let r = collection
-			.write()
-			.map_err(|_| Error::MutexPoison(String::from("QuerierBuilder")))?
-			.insert(q.selector().to_string(), Box::new(q));
-		Ok(r)
+	/// Build and add the query to the agents context
+	/// An already registered one with the same selector is stopped and replaced.
+	/// Returns a [`CapabilityHandle`] to remove, pause or resume it later.
+	/// # Errors
+	/// if the storage is not accessible
+	pub fn replace(self) -> Result<CapabilityHandle<P, Box<dyn QuerierTrait>>> {
+		let collection = self.storage.storage.clone();
+		let context = self.context.clone();
+		let q = self.build()?;
+		let key = q.selector().to_string();
+		let capability: Box<dyn QuerierTrait> = Box::new(q);
+		CapabilityHandle::replace(key, capability, context, collection)
 	}
 }
 // endregion:	--- QuerierBuilder
@@ -14,8 +14,14 @@ use alloc::{
 	string::{String, ToString},
 	sync::Arc,
 };
+use dimas_core::handle::CapabilityHandle;
 use dimas_core::{
-	Result, enums::OperationState, message_types::QueryMsg, traits::Context, utils::selector_from,
+	Result,
+	enums::OperationState,
+	message_types::QueryMsg,
+	policies::{ArcErrorCallback, ErrorCallback, ErrorHandler, ErrorPolicy},
+	traits::Context,
+	utils::selector_from,
 };
 use futures::future::Future;
 #[cfg(feature = "std")]
@@ -43,6 +49,8 @@ where
 	session_id: String,
 	context: Context<P>,
 	activation_state: OperationState,
+	error_policy: ErrorPolicy,
+	error_callback: Option<ArcErrorCallback<P>>,
 	completeness: bool,
 	#[cfg(feature = "unstable")]
 	allowed_origin: Locality,
@@ -62,6 +70,8 @@ where
 			session_id: session_id.into(),
 			context,
 			activation_state: OperationState::Active,
+			error_policy: ErrorPolicy::Log,
+			error_callback: None,
 			completeness: true,
 			#[cfg(feature = "unstable")]
 			allowed_origin: Locality::Any,
@@ -83,6 +93,25 @@ where
 		self
 	}
 
+	/// Set the [`ErrorPolicy`] for errors returned by the callbacks, default is [`ErrorPolicy::Log`].
+	#[must_use]
+	pub const fn error_policy(mut self, policy: ErrorPolicy) -> Self {
+		self.error_policy = policy;
+		self
+	}
+
+	/// Set a callback, which is informed with the selector about every error returned by the callbacks.
+	#[must_use]
+	pub fn on_error<F>(mut self, callback: F) -> Self
+	where
+		F: FnMut(Context<P>, &str, &(dyn core::error::Error + Send + Sync)) + Send + Sync + 'static,
+	{
+		let callback: ErrorCallback<P> = Box::new(callback);
+		self.error_callback
+			.replace(Arc::new(std::sync::Mutex::new(callback)));
+		self
+	}
+
 	/// Set the completeness of the [`Queryable`].
 	#[must_use]
 	pub const fn completeness(mut self, completeness: bool) -> Self {
@@ -117,6 +146,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			completeness,
 			#[cfg(feature = "unstable")]
 			allowed_origin,
@@ -128,6 +159,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			completeness,
 			#[cfg(feature = "unstable")]
 			allowed_origin,
@@ -166,6 +199,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			completeness,
 			#[cfg(feature = "unstable")]
 			allowed_origin,
@@ -179,6 +214,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			completeness,
 			#[cfg(feature = "unstable")]
 			allowed_origin,
@@ -203,6 +240,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			completeness,
 			#[cfg(feature = "unstable")]
 			allowed_origin,
@@ -214,6 +253,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			completeness,
 			#[cfg(feature = "unstable")]
 			allowed_origin,
@@ -236,6 +277,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			completeness,
 			#[cfg(feature = "unstable")]
 			allowed_origin,
@@ -249,9 +292,11 @@ where
 			.ok_or_else(|| Error::NoZenohSession)?;
 		Ok(Queryable::new(
 			session,
+			session_id,
 			selector,
 			context,
 			activation_state,
+			ErrorHandler::new(error_policy, error_callback),
 			callback.callback,
 			completeness,
 			#[cfg(feature = "unstable")]
@@ -265,17 +310,30 @@ where
 	P: Send + Sync + 'static,
 {
 	/// Build and add the queryable to the agents context
+	/// Returns a [`CapabilityHandle`] to remove, pause or resume it later.
 	/// # Errors
-	///
-	pub fn add(self) -> Result<Option<Box<dyn Responder>>> {
+	/// if a capability with the same selector is already registered
+	pub fn add(self) -> Result<CapabilityHandle<P, Box<dyn Responder>>> {
 		let collection = self.storage.storage.clone();
+		let context = self.context.clone();
 		let q = self.build()?;
+		let key = q.selector().to_string();
+		let capability: Box<dyn Responder> = Box::new(q);
+		CapabilityHandle::register(key, capability, context, collection)
+	}
 
-		let r = collection
-			.write()
-			.map_err(|_| Error::MutexPoison(String::from("QueryableBuilder")))?
-			.insert(q.selector().to_string(), Box::new(q));
-		Ok(r)
+	/// Build and add the queryable to the agents context
+	/// An already registered one with the same selector is stopped and replaced.
+	/// Returns a [`CapabilityHandle`] to remove, pause or resume it later.
+	/// # Errors
+	/// if the storage is not accessible
+	pub fn replace(self) -> Result<CapabilityHandle<P, Box<dyn Responder>>> {
+		let collection = self.storage.storage.clone();
+		let context = self.context.clone();
+		let q = self.build()?;
+		let key = q.selector().to_string();
+		let capability: Box<dyn Responder> = Box::new(q);
+		CapabilityHandle::replace(key, capability, context, collection)
 	}
 }
 // endregion:	--- QueryableBuilder
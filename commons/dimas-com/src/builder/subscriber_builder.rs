@@ -13,16 +13,25 @@ extern crate std;
 use crate::error::Error;
 use crate::traits::Responder as SubscriberTrait;
 use crate::zenoh::subscriber::{
-	ArcDeleteCallback, ArcPutCallback, DeleteCallback, PutCallback, Subscriber,
+	ArcDeleteCallback, ArcPutCallback, DecodeErrorCallback, DeleteCallback, PutCallback, Subscriber,
 };
 use alloc::{
 	boxed::Box,
 	string::{String, ToString},
 	sync::Arc,
 };
+use bitcode::Decode;
+use core::{fmt::Debug, marker::PhantomData};
 use dimas_core::builder_states::{Callback, NoCallback, NoSelector, NoStorage, Selector, Storage};
+use dimas_core::handle::CapabilityHandle;
 use dimas_core::{
-	Result, enums::OperationState, message_types::Message, traits::Context, utils::selector_from,
+	Result,
+	codec::{Bitcode, Decoder},
+	enums::OperationState,
+	message_types::{Message, SampleInfo},
+	policies::{ArcErrorCallback, ErrorCallback, ErrorHandler, ErrorPolicy},
+	traits::Context,
+	utils::selector_from,
 };
 use futures::future::Future;
 #[cfg(feature = "std")]
@@ -43,6 +52,8 @@ where
 	session_id: String,
 	context: Context<P>,
 	activation_state: OperationState,
+	error_policy: ErrorPolicy,
+	error_callback: Option<ArcErrorCallback<P>>,
 	#[cfg(feature = "unstable")]
 	allowed_origin: Locality,
 	selector: K,
@@ -62,6 +73,8 @@ where
 			session_id: session_id.into(),
 			context,
 			activation_state: OperationState::Active,
+			error_policy: ErrorPolicy::Log,
+			error_callback: None,
 			#[cfg(feature = "unstable")]
 			allowed_origin: Locality::Any,
 			selector: NoSelector,
@@ -83,6 +96,25 @@ where
 		self
 	}
 
+	/// Set the [`ErrorPolicy`] for errors returned by the callbacks, default is [`ErrorPolicy::Log`].
+	#[must_use]
+	pub const fn error_policy(mut self, policy: ErrorPolicy) -> Self {
+		self.error_policy = policy;
+		self
+	}
+
+	/// Set a callback, which is informed with the selector about every error returned by the callbacks.
+	#[must_use]
+	pub fn on_error<F>(mut self, callback: F) -> Self
+	where
+		F: FnMut(Context<P>, &str, &(dyn core::error::Error + Send + Sync)) + Send + Sync + 'static,
+	{
+		let callback: ErrorCallback<P> = Box::new(callback);
+		self.error_callback
+			.replace(Arc::new(std::sync::Mutex::new(callback)));
+		self
+	}
+
 	/// Set the allowed origin.
 	#[cfg(feature = "unstable")]
 	#[must_use]
@@ -123,6 +155,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			#[cfg(feature = "unstable")]
 			allowed_origin,
 			storage,
@@ -134,6 +168,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			#[cfg(feature = "unstable")]
 			allowed_origin,
 			selector: Selector {
@@ -168,10 +204,47 @@ where
 		CB: FnMut(Context<P>, Message) -> F + Send + Sync + 'static,
 		F: Future<Output = Result<()>> + Send + Sync + 'static,
 	{
+		let callback: PutCallback<P> = Box::new(move |ctx, msg| Box::pin(callback(ctx, msg)));
+		self.callback(callback)
+	}
+
+	/// Switch to a subscriber for values of type `T` encoded with [`Bitcode`].
+	/// The put callback of the returned [`TypedSubscriberBuilder`] receives the decoded values.
+	#[must_use]
+	pub fn typed<T>(self) -> TypedSubscriberBuilder<P, T, K, S>
+	where
+		T: for<'a> Decode<'a> + Send + 'static,
+	{
+		self.typed_with::<T, Bitcode>()
+	}
+
+	/// Switch to a subscriber for values of type `T` encoded with the
+	/// [`Codec`](dimas_core::codec::Codec) `D`.
+	/// The put callback of the returned [`TypedSubscriberBuilder`] receives the decoded values.
+	#[must_use]
+	pub fn typed_with<T, D>(self) -> TypedSubscriberBuilder<P, T, K, S, D>
+	where
+		T: Send + 'static,
+		D: Decoder<T> + 'static,
+	{
+		TypedSubscriberBuilder {
+			builder: self,
+			decode_error_callback: None,
+			value: PhantomData,
+			codec: PhantomData,
+		}
+	}
+
+	fn callback(
+		self,
+		callback: PutCallback<P>,
+	) -> SubscriberBuilder<P, K, Callback<ArcPutCallback<P>>, S> {
 		let Self {
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			#[cfg(feature = "unstable")]
 			allowed_origin,
 			selector,
@@ -179,12 +252,13 @@ where
 			delete_callback,
 			..
 		} = self;
-		let callback: PutCallback<P> = Box::new(move |ctx, msg| Box::pin(callback(ctx, msg)));
 		let callback: ArcPutCallback<P> = Arc::new(Mutex::new(callback));
 		SubscriberBuilder {
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			#[cfg(feature = "unstable")]
 			allowed_origin,
 			selector,
@@ -209,6 +283,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			#[cfg(feature = "unstable")]
 			allowed_origin,
 			selector,
@@ -220,6 +296,8 @@ where
 			session_id,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			#[cfg(feature = "unstable")]
 			allowed_origin,
 			selector,
@@ -244,6 +322,8 @@ where
 			selector,
 			context,
 			activation_state,
+			error_policy,
+			error_callback,
 			#[cfg(feature = "unstable")]
 			allowed_origin,
 			put_callback,
@@ -255,9 +335,11 @@ where
 			.ok_or_else(|| Error::NoZenohSession)?;
 		Ok(Subscriber::new(
 			session,
+			session_id,
 			selector.selector,
 			context,
 			activation_state,
+			ErrorHandler::new(error_policy, error_callback),
 			#[cfg(feature = "unstable")]
 			allowed_origin,
 			put_callback.callback,
@@ -272,25 +354,146 @@ where
 	P: Send + Sync + 'static,
 {
 	/// Build and add the [`Subscriber`] to the `Agent`.
+	/// Returns a [`CapabilityHandle`] to remove, pause or resume it later.
 	///
 	/// # Errors
-	/// Currently none
-	pub fn add(self) -> Result<Option<Box<dyn SubscriberTrait>>> {
+	/// if a capability with the same selector is already registered
+	pub fn add(self) -> Result<CapabilityHandle<P, Box<dyn SubscriberTrait>>> {
 		let c = self.storage.storage.clone();
+		let context = self.context.clone();
 		let s = self.build()?;
+		let key = s.selector().to_string();
+		let capability: Box<dyn SubscriberTrait> = Box::new(s);
+		CapabilityHandle::register(key, capability, context, c)
+	}
 
-		let r = c
-			.write()
-			.map_err(|_| Error::MutexPoison(String::from("SubscriberBuilder")))?
-			.insert(s.selector().to_string(), Box::new(s));
-		Ok(r)
+	/// Build and add the [`Subscriber`] to the `Agent`
+	/// An already registered one with the same selector is stopped and replaced.
+	/// Returns a [`CapabilityHandle`] to remove, pause or resume it later.
+	/// # Errors
+	/// if the storage is not accessible
+	pub fn replace(self) -> Result<CapabilityHandle<P, Box<dyn SubscriberTrait>>> {
+		let c = self.storage.storage.clone();
+		let context = self.context.clone();
+		let s = self.build()?;
+		let key = s.selector().to_string();
+		let capability: Box<dyn SubscriberTrait> = Box::new(s);
+		CapabilityHandle::replace(key, capability, context, c)
 	}
 }
 // endregion:	--- SubscriberBuilder
 
+// region:		--- TypedSubscriberBuilder
+/// A [`SubscriberBuilder`] for a subscriber receiving values of type `T`.
+///
+/// It is created with [`SubscriberBuilder::typed`] or [`SubscriberBuilder::typed_with`]
+/// and decodes the messages with the [`Codec`](dimas_core::codec::Codec) `D`.
+/// Messages, which can not be decoded into `T`, including messages with an encoding
+/// of another codec, are passed to the decode error callback.
+/// Without such a callback the decoding error is handled like an error returned by the
+/// put callback, according to the [`ErrorPolicy`] of the subscriber.
+pub struct TypedSubscriberBuilder<P, T, K, S, D = Bitcode>
+where
+	P: Send + Sync + 'static,
+{
+	builder: SubscriberBuilder<P, K, NoCallback, S>,
+	decode_error_callback: Option<DecodeErrorCallback<P>>,
+	value: PhantomData<fn() -> T>,
+	codec: PhantomData<fn() -> D>,
+}
+
+impl<P, T, K, S, D> Debug for TypedSubscriberBuilder<P, T, K, S, D>
+where
+	P: Send + Sync + 'static,
+{
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("TypedSubscriberBuilder")
+			.field("type", &core::any::type_name::<T>())
+			.field("codec", &core::any::type_name::<D>())
+			.field(
+				"decode_error_callback",
+				&self.decode_error_callback.is_some(),
+			)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<P, T, K, S, D> TypedSubscriberBuilder<P, T, K, S, D>
+where
+	P: Send + Sync + 'static,
+	T: Send + 'static,
+	D: Decoder<T> + 'static,
+{
+	/// Set a callback, which is informed about every message that could not be decoded.
+	/// These messages are then neither passed to the put callback nor counted as errors.
+	#[must_use]
+	pub fn on_decode_error<F>(mut self, callback: F) -> Self
+	where
+		F: FnMut(Context<P>, &Message, &(dyn core::error::Error + Send + Sync))
+			+ Send
+			+ Sync
+			+ 'static,
+	{
+		self.decode_error_callback
+			.replace(Box::new(callback));
+		self
+	}
+
+	/// Set callback for put messages, which receives the decoded value
+	#[must_use]
+	pub fn put_callback<CB, F>(
+		self,
+		mut callback: CB,
+	) -> SubscriberBuilder<P, K, Callback<ArcPutCallback<P>>, S>
+	where
+		CB: FnMut(Context<P>, T) -> F + Send + Sync + 'static,
+		F: Future<Output = Result<()>> + Send + Sync + 'static,
+	{
+		self.put_callback_with_info(move |ctx, value, _| callback(ctx, value))
+	}
+
+	/// Set callback for put messages, which receives the decoded value
+	/// together with the [`SampleInfo`] of the received message
+	#[must_use]
+	pub fn put_callback_with_info<CB, F>(
+		self,
+		mut callback: CB,
+	) -> SubscriberBuilder<P, K, Callback<ArcPutCallback<P>>, S>
+	where
+		CB: FnMut(Context<P>, T, Option<SampleInfo>) -> F + Send + Sync + 'static,
+		F: Future<Output = Result<()>> + Send + Sync + 'static,
+	{
+		let Self {
+			builder,
+			mut decode_error_callback,
+			..
+		} = self;
+		let callback: PutCallback<P> = Box::new(move |ctx, msg| {
+			let info = msg.info().cloned();
+			let value = match decode_error_callback.as_mut() {
+				Some(on_error) => match msg.clone().decode_with::<D, T>() {
+					Ok(value) => value,
+					Err(error) => {
+						on_error(ctx, &msg, &*error);
+						return Box::pin(core::future::ready(Ok(())));
+					}
+				},
+				None => match msg.decode_with::<D, T>() {
+					Ok(value) => value,
+					Err(error) => return Box::pin(core::future::ready(Err(error))),
+				},
+			};
+			Box::pin(callback(ctx, value, info))
+		});
+		builder.callback(callback)
+	}
+}
+// endregion:	--- TypedSubscriberBuilder
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use dimas_core::codec::Json;
 
 	#[derive(Debug)]
 	struct Props {}
@@ -301,5 +504,7 @@ mod tests {
 	#[test]
 	const fn normal_types() {
 		is_normal::<SubscriberBuilder<Props, NoSelector, NoCallback, NoStorage>>();
+		is_normal::<TypedSubscriberBuilder<Props, u32, NoSelector, NoStorage>>();
+		is_normal::<TypedSubscriberBuilder<Props, u32, NoSelector, NoStorage, Json>>();
 	}
 }
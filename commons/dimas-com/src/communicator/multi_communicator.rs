@@ -25,8 +25,9 @@ use alloc::{
 	sync::Arc,
 	vec::Vec,
 };
+use core::time::Duration;
 use dimas_config::Config;
-use dimas_core::message_types::{Message, QueryableMsg};
+use dimas_core::message_types::{Message, QueryableMsg, Replies};
 use dimas_core::{Result, enums::OperationState, traits::Capability};
 use std::{collections::HashMap, sync::RwLock};
 use zenoh::{Session, config::ZenohId};
@@ -165,6 +166,41 @@ impl CommunicatorMethods for MultiCommunicator {
 		}
 	}
 
+	/// Send a query with an optional specification [`Message`] to the given `selector` without blocking.
+	/// # Errors
+	/// - `NotImplemented`: there is no implementation within this communicator
+	fn get_async(
+		&self,
+		selector: &str,
+		message: Option<Message>,
+		timeout: Option<Duration>,
+	) -> Result<Replies> {
+		let queriers = self
+			.queriers
+			.read()
+			.map_err(|_| Error::ReadAccess("queriers".into()))?;
+
+		#[allow(clippy::single_match_else)]
+		match queriers.get(selector) {
+			Some(querier) => querier.get_async(message, timeout),
+			None => {
+				let comm = self
+					.communicators
+					.read()
+					.map_err(|_| Error::ReadAccess("queriers".into()))?
+					.get(DEFAULT)
+					.ok_or_else(|| Error::NoCommunicator(DEFAULT.into()))
+					.cloned()?;
+
+				match comm.as_ref() {
+					CommunicatorImplementation::Zenoh(zenoh) => {
+						zenoh.get_async(selector, message, timeout)
+					}
+				}
+			}
+		}
+	}
+
 	/// Request an observation for [`Message`] from the given `selector`
 	/// # Errors
 	/// - `NotImplemented`: there is no implementation within this communicator
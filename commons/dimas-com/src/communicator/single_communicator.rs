@@ -25,8 +25,14 @@ use alloc::{
 	sync::Arc,
 	vec::Vec,
 };
+use core::time::Duration;
 use dimas_config::Config;
-use dimas_core::{Result, enums::OperationState, message_types::Message, traits::Capability};
+use dimas_core::{
+	Result,
+	enums::OperationState,
+	message_types::{Message, Replies},
+	traits::Capability,
+};
 use std::{collections::HashMap, sync::RwLock};
 use zenoh::{Session, config::ZenohId};
 // endregion:	--- modules
@@ -184,6 +190,32 @@ impl CommunicatorMethods for SingleCommunicator {
 		}
 	}
 
+	fn get_async(
+		&self,
+		selector: &str,
+		message: Option<Message>,
+		timeout: Option<Duration>,
+	) -> Result<Replies> {
+		let queriers = self
+			.queriers
+			.read()
+			.map_err(|_| Error::ReadAccess("queriers".into()))?;
+
+		#[allow(clippy::single_match_else)]
+		match queriers.get(selector) {
+			Some(querier) => querier.get_async(message, timeout),
+			None =>
+			{
+				#[allow(clippy::match_wildcard_for_single_variants)]
+				match self.communicator.as_ref() {
+					CommunicatorImplementation::Zenoh(zenoh) => {
+						zenoh.get_async(selector, message, timeout)
+					}
+				}
+			}
+		}
+	}
+
 	fn observe(
 		&self,
 		selector: &str,
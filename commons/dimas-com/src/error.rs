@@ -101,6 +101,32 @@ pub enum Error {
 		/// query selector
 		selector: String,
 	},
+	/// A blocking query with retries would stall a current thread runtime.
+	#[error(
+		"query for '{selector}' with retries would block the current thread runtime, use an asynchronous query or a multi threaded runtime"
+	)]
+	BlockingRetry {
+		/// query selector
+		selector: String,
+	},
+	/// All attempts of a query failed.
+	#[error("query for '{selector}' failed after {attempts} attempts, last with: {source}")]
+	RetriesExhausted {
+		/// query selector
+		selector: String,
+		/// the number of attempts made
+		attempts: u32,
+		/// the error of the last attempt
+		source: Box<dyn core::error::Error + Send + Sync>,
+	},
+	/// A queryable answered with an error.
+	#[error("queryable '{selector}' replied with error: {reason}")]
+	ErrorReply {
+		/// query selector
+		selector: String,
+		/// the content of the error reply
+		reason: String,
+	},
 	/// Accessing the [`Observable`] for a [`Observer`] failed.
 	#[error("accessing observable '{selector}' failed")]
 	AccessingObservable {
@@ -7,9 +7,11 @@
 extern crate alloc;
 
 use alloc::string::String;
+use core::time::Duration;
 use dimas_core::{
+	enums::CapabilityKind,
 	error::Result,
-	message_types::{Message, QueryableMsg},
+	message_types::{CapabilityDescription, Message, QueryableMsg, Replies},
 	traits::Capability,
 };
 
@@ -18,6 +20,10 @@ use dimas_core::{
 pub trait LivelinessSubscriber: Capability + Send + Sync {
 	/// get token
 	fn token(&self) -> &String;
+
+	/// Describe the capability for introspection
+	#[must_use]
+	fn describe(&self) -> CapabilityDescription;
 }
 
 /// `Observer` capabilities
@@ -33,6 +39,10 @@ pub trait Observer: Capability + Send + Sync {
 	/// Request an observation with an optional [`Message`].
 	/// # Errors
 	fn request(&self, message: Option<Message>) -> Result<()>;
+
+	/// Describe the capability for introspection
+	#[must_use]
+	fn describe(&self) -> CapabilityDescription;
 }
 
 /// `Publisher` capabilities
@@ -48,6 +58,10 @@ pub trait Publisher: Capability + Send + Sync {
 	/// Send a "delete" message
 	/// # Errors
 	fn delete(&self) -> Result<()>;
+
+	/// Describe the capability for introspection
+	#[must_use]
+	fn describe(&self) -> CapabilityDescription;
 }
 
 /// `Querier` capabilities
@@ -63,6 +77,15 @@ pub trait Querier: Capability + Send + Sync {
 		message: Option<Message>,
 		callback: Option<&mut dyn FnMut(QueryableMsg) -> Result<()>>,
 	) -> Result<()>;
+
+	/// Run a Querier with an optional [`Message`] without blocking,
+	/// a given `timeout` overrides the queriers timeout.
+	/// # Errors
+	fn get_async(&self, message: Option<Message>, timeout: Option<Duration>) -> Result<Replies>;
+
+	/// Describe the capability for introspection
+	#[must_use]
+	fn describe(&self) -> CapabilityDescription;
 }
 
 /// `Responder` capabilities
@@ -70,5 +93,13 @@ pub trait Responder: Capability + Send + Sync {
 	/// Get `selector`
 	#[must_use]
 	fn selector(&self) -> &str;
+
+	/// Get the kind of responder
+	#[must_use]
+	fn kind(&self) -> CapabilityKind;
+
+	/// Describe the capability for introspection
+	#[must_use]
+	fn describe(&self) -> CapabilityDescription;
 }
 // endregion:	--- capabilities
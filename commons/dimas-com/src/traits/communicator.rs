@@ -14,7 +14,12 @@ use super::LivelinessSubscriber;
 use super::{CommunicatorMethods, Observer, Publisher, Querier, Responder};
 use crate::error::Error;
 use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
-use dimas_core::{enums::OperationState, error::Result, traits::Capability};
+use core::time::Duration;
+use dimas_core::{
+	enums::OperationState,
+	error::Result,
+	traits::{Capability, DrainFuture},
+};
 #[cfg(feature = "std")]
 use std::{collections::HashMap, sync::RwLock};
 use tracing::error;
@@ -191,6 +196,74 @@ pub trait Communicator: Capability + CommunicatorMethods + Send + Sync {
 		Ok(())
 	}
 
+	/// Method for gracefully downgrading all registered capabilities during a shutdown.
+	///
+	/// The capabilities are drained in the same order as in [`Communicator::downgrade_capabilities`].
+	/// Returns the futures to await for the draining together with the capabilities selector.
+	///
+	/// # Errors
+	/// if a storage is not accessible
+	fn drain_capabilities(
+		&self,
+		new_state: &OperationState,
+		timeout: Duration,
+	) -> Result<Vec<(String, DrainFuture)>> {
+		let mut drains = Vec::new();
+		for querier in self
+			.queriers()
+			.read()
+			.map_err(|_| Error::ReadAccess("queries".into()))?
+			.values()
+		{
+			drains.push((querier.selector().into(), querier.drain(new_state, timeout)));
+		}
+		for observer in self
+			.observers()
+			.read()
+			.map_err(|_| Error::ReadAccess("observers".into()))?
+			.values()
+		{
+			drains.push((
+				observer.selector().into(),
+				observer.drain(new_state, timeout),
+			));
+		}
+		for publisher in self
+			.publishers()
+			.read()
+			.map_err(|_| Error::ReadAccess("publishers".into()))?
+			.values()
+		{
+			drains.push((
+				publisher.selector().into(),
+				publisher.drain(new_state, timeout),
+			));
+		}
+		for responder in self
+			.responders()
+			.read()
+			.map_err(|_| Error::ReadAccess("responders".into()))?
+			.values()
+		{
+			drains.push((
+				responder.selector().into(),
+				responder.drain(new_state, timeout),
+			));
+		}
+		for subscriber in self
+			.liveliness_subscribers()
+			.read()
+			.map_err(|_| Error::ReadAccess("liveliness subscribers".into()))?
+			.values()
+		{
+			drains.push((
+				subscriber.token().clone(),
+				subscriber.drain(new_state, timeout),
+			));
+		}
+		Ok(drains)
+	}
+
 	/// the uuid of the communicator
 	#[must_use]
 	fn uuid(&self) -> String;
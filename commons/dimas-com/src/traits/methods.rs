@@ -5,9 +5,10 @@
 
 // region:      --- modules
 use crate::error::Error;
+use core::time::Duration;
 use dimas_core::{
 	error::Result,
-	message_types::{Message, QueryableMsg},
+	message_types::{Message, QueryableMsg, Replies},
 };
 // endregion:   --- modules
 
@@ -41,6 +42,18 @@ pub trait CommunicatorMethods {
 		Err(Error::NotImplemented.into())
 	}
 
+	/// Send a query with an optional specification [`Message`] to the given `selector` without blocking.
+	/// # Errors
+	/// - `NotImplemented`: there is no implementation within this communicator
+	fn get_async(
+		&self,
+		_selector: &str,
+		_message: Option<Message>,
+		_timeout: Option<Duration>,
+	) -> Result<Replies> {
+		Err(Error::NotImplemented.into())
+	}
+
 	/// Request an observation for [`Message`] from the given `selector`
 	/// # Errors
 	/// - `NotImplemented`: there is no implementation within this communicator
@@ -135,6 +148,18 @@ pub trait CommunicatorImplementationMethods {
 		Err(Error::NotImplemented.into())
 	}
 
+	/// Send a query with an optional specification [`Message`] to the given `selector` without blocking.
+	/// # Errors
+	/// - `NotImplemented`: there is no implementation within this communicator
+	fn get_async(
+		&self,
+		_selector: &str,
+		_message: Option<Message>,
+		_timeout: Option<Duration>,
+	) -> Result<Replies> {
+		Err(Error::NotImplemented.into())
+	}
+
 	/// Request an observation for [`Message`] from the given `selector`
 	/// # Errors
 	/// - `NotImplemented`: there is no implementation within this communicator
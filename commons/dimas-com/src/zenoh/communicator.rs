@@ -11,7 +11,7 @@ extern crate alloc;
 extern crate std;
 
 // region:		--- modules
-use crate::{error::Error, traits::CommunicatorImplementationMethods};
+use crate::{error::Error, traits::CommunicatorImplementationMethods, zenoh::retry};
 use alloc::{
 	borrow::ToOwned,
 	boxed::Box,
@@ -23,7 +23,9 @@ use core::{fmt::Debug, time::Duration};
 use dimas_core::{
 	Result,
 	enums::OperationState,
-	message_types::{Message, QueryableMsg},
+	message_types::{Headers, Message, QueryableMsg, Replies},
+	policies::RetryPolicy,
+	trace_context,
 	traits::Capability,
 };
 use zenoh::config::WhatAmI;
@@ -31,11 +33,19 @@ use zenoh::config::WhatAmI;
 use zenoh::sample::Locality;
 use zenoh::{
 	Session, Wait,
-	query::{ConsolidationMode, QueryTarget},
+	handlers::FifoChannelHandler,
+	query::{ConsolidationMode, QueryTarget, Reply},
 	sample::SampleKind,
 };
 // endregion:	--- modules
 
+// region:		--- types
+/// the timeout for ad-hoc queries
+const QUERY_TIMEOUT: Duration = Duration::from_millis(5000);
+/// the retry policy for ad-hoc queries
+const QUERY_RETRY: RetryPolicy = RetryPolicy::new();
+// endregion:	--- types
+
 // region:		--- Communicator
 /// [`Communicator`] handles all communication aspects
 #[allow(clippy::module_name_repetitions)]
@@ -58,8 +68,16 @@ impl CommunicatorImplementationMethods for Communicator {
 	/// # Errors
 	#[allow(clippy::needless_pass_by_value)]
 	fn put(&self, selector: &str, message: Message) -> Result<()> {
-		self.session
-			.put(selector, message.value())
+		let builder = self.session.put(selector, message.value());
+		let builder = match message.encoding() {
+			Some(encoding) => builder.encoding(encoding),
+			None => builder,
+		};
+		let builder = match message.attachment() {
+			Some(attachment) => builder.attachment(attachment),
+			None => builder,
+		};
+		builder
 			.wait()
 			.map_err(|source| Error::PublishingPut { source }.into())
 	}
@@ -74,7 +92,12 @@ impl CommunicatorImplementationMethods for Communicator {
 	}
 
 	/// Send a query with an optional [`Message`] using the given `selector`.
-	/// Answers are collected via callback
+	/// Answers are collected via callback.
+	///
+	/// An unanswered query is repeated according to the default [`RetryPolicy`].
+	/// The current thread is blocked until the query is answered or the attempts are exhausted,
+	/// within a multi threaded runtime the other tasks of its worker are moved to another thread.
+	/// Within a current thread runtime a query with retries is rejected, as it would block all tasks.
 	/// # Errors
 	/// # Panics
 	fn get(
@@ -83,37 +106,17 @@ impl CommunicatorImplementationMethods for Communicator {
 		message: Option<Message>,
 		mut callback: Option<&mut dyn FnMut(QueryableMsg) -> Result<()>>,
 	) -> Result<()> {
-		let builder = message
-			.map_or_else(
-				|| self.session.get(selector),
-				|msg| self.session.get(selector).payload(msg.value()),
-			)
-			.consolidation(ConsolidationMode::None)
-			.target(QueryTarget::All);
-
-		#[cfg(feature = "unstable")]
-		let builder = builder.allowed_destination(Locality::Any);
-
-		let query = builder
-			.timeout(Duration::from_millis(5000))
-			.wait()
-			.map_err(|source| Error::QueryCreation { source })?;
-
-		let mut unreached = true;
-		let mut retry_count = 0u8;
-
-		while unreached && retry_count <= 5 {
-			retry_count += 1;
+		let receive = |query: &FifoChannelHandler<Reply>| -> Result<bool> {
+			let mut reached = false;
 			while let Ok(reply) = query.recv() {
 				match reply.result() {
 					Ok(sample) => match sample.kind() {
 						SampleKind::Put => {
-							let content: Vec<u8> = sample.payload().to_bytes().into_owned();
 							// CommunicatorImplementation::Zenoh(zenoh) =>
 							callback.as_deref_mut().map_or_else(
 								|| Err(Error::NotImplemented),
 								|callback| {
-									callback(QueryableMsg(content))
+									callback(QueryableMsg::from(sample))
 										.map_err(|source| Error::QueryCallback { source })
 								},
 							)?;
@@ -127,24 +130,83 @@ impl CommunicatorImplementationMethods for Communicator {
 						std::println!(">> Zenoh Communicator received (ERROR: '{:?}' for {})", &content, &selector);
 					}
 				}
-				unreached = false;
+				reached = true;
 			}
-			if unreached {
-				if retry_count < 5 {
-					std::thread::sleep(Duration::from_millis(1000));
-				} else {
-					return Err(Error::AccessingQueryable {
-						selector: selector.to_string(),
-					}
-					.into());
-				}
-			}
-		}
-		Ok(())
+			Ok(reached)
+		};
+		retry::get_blocking(
+			selector,
+			QUERY_RETRY,
+			QUERY_TIMEOUT,
+			self.sender(selector, message),
+			receive,
+		)
+	}
+
+	/// Send a query with an optional [`Message`] using the given `selector` without blocking.
+	/// Answers are received via the returned [`Replies`].
+	///
+	/// An unanswered query is repeated according to the default [`RetryPolicy`].
+	/// # Errors
+	fn get_async(
+		&self,
+		selector: &str,
+		message: Option<Message>,
+		timeout: Option<Duration>,
+	) -> Result<Replies> {
+		retry::get_async(
+			selector.to_string(),
+			QUERY_RETRY,
+			timeout.unwrap_or(QUERY_TIMEOUT),
+			self.sender(selector, message),
+		)
 	}
 }
 
 impl Communicator {
+	/// Get the function sending a query with an optional [`Message`] using the given `selector`.
+	/// The function gets the timeout of an attempt and returns the receiver for the replies.
+	fn sender(
+		&self,
+		selector: &str,
+		message: Option<Message>,
+	) -> impl FnMut(Duration) -> Result<FifoChannelHandler<Reply>> + Send + 'static {
+		let session = self.session.clone();
+		let selector = selector.to_string();
+		let attachment = message.as_ref().map_or_else(
+			|| trace_context::attachment(&Headers::new()),
+			Message::attachment,
+		);
+		move |timeout| {
+			let builder = message
+				.as_ref()
+				.map_or_else(
+					|| session.get(&selector),
+					|msg| {
+						let builder = session.get(&selector).payload(msg.value());
+						match msg.encoding() {
+							Some(encoding) => builder.encoding(encoding),
+							None => builder,
+						}
+					},
+				)
+				.consolidation(ConsolidationMode::None)
+				.target(QueryTarget::All);
+			let builder = match attachment.clone() {
+				Some(attachment) => builder.attachment(attachment),
+				None => builder,
+			};
+
+			#[cfg(feature = "unstable")]
+			let builder = builder.allowed_destination(Locality::Any);
+
+			builder
+				.timeout(timeout)
+				.wait()
+				.map_err(|source| Error::QueryCreation { source }.into())
+		}
+	}
+
 	/// Constructor
 	/// # Errors
 	pub fn new(config: &zenoh::Config) -> Result<Self> {
@@ -17,15 +17,20 @@ use alloc::{
 	collections::BTreeSet,
 	string::{String, ToString},
 };
+use core::{
+	sync::atomic::{AtomicBool, Ordering},
+	time::Duration,
+};
 use dimas_core::{
 	Result,
-	enums::{OperationState, TaskSignal},
-	traits::{Capability, Context},
+	enums::{CapabilityKind, OperationState, TaskSignal},
+	message_types::CapabilityDescription,
+	traits::{Capability, Context, DrainFuture},
+	utils::{drain_task, supervise},
 };
 use futures::future::BoxFuture;
 #[cfg(feature = "std")]
 use tokio::{sync::Mutex, task::JoinHandle};
-use tracing::info;
 use tracing::{Level, error, instrument, warn};
 use zenoh::Session;
 use zenoh::sample::SampleKind;
@@ -49,9 +54,13 @@ where
 {
 	/// the zenoh session this liveliness subscriber belongs to
 	session: Arc<Session>,
+	/// the id of the zenoh session
+	session_id: String,
 	token: String,
 	context: Context<P>,
 	activation_state: OperationState,
+	/// A paused liveliness subscriber is not started
+	paused: AtomicBool,
 	put_callback: ArcLivelinessCallback<P>,
 	delete_callback: Option<ArcLivelinessCallback<P>>,
 	handle: std::sync::Mutex<Option<JoinHandle<()>>>,
@@ -76,6 +85,22 @@ where
 	fn token(&self) -> &String {
 		&self.token
 	}
+
+	fn describe(&self) -> CapabilityDescription {
+		let running = self.handle.lock().is_ok_and(|handle| {
+			handle
+				.as_ref()
+				.is_some_and(|handle| !handle.is_finished())
+		});
+		CapabilityDescription::new(
+			CapabilityKind::LivelinessSubscriber,
+			self.token.clone(),
+			Some(self.session_id.clone()),
+			self.activation_state.clone(),
+			running,
+			self.is_paused(),
+		)
+	}
 }
 
 impl<P> Capability for LivelinessSubscriber<P>
@@ -83,13 +108,49 @@ where
 	P: Send + Sync + 'static,
 {
 	fn manage_operation_state(&self, state: &OperationState) -> Result<()> {
-		if state >= &self.activation_state {
+		if state >= &self.activation_state && !self.is_paused() {
 			self.start()
-		} else if state < &self.activation_state {
-			self.stop()
 		} else {
-			Ok(())
+			self.stop()
+		}
+	}
+
+	fn set_paused(&self, paused: bool) -> Result<()> {
+		self.paused.store(paused, Ordering::Release);
+		Ok(())
+	}
+
+	fn is_paused(&self) -> bool {
+		self.paused.load(Ordering::Acquire)
+	}
+
+	fn drain(&self, state: &OperationState, timeout: Duration) -> DrainFuture {
+		if state >= &self.activation_state {
+			return Box::pin(core::future::ready(false));
 		}
+		let Some(handle) = self
+			.handle
+			.lock()
+			.map_or(None, |mut handle| handle.take())
+		else {
+			return Box::pin(core::future::ready(false));
+		};
+		let known_agents = self.known_agents.clone();
+		let p_cb = self.put_callback.clone();
+		let d_cb = self.delete_callback.clone();
+		Box::pin(async move {
+			// holding all locks guarantees that no sample is in-flight
+			let idle = async {
+				let agents = known_agents.lock_owned().await;
+				let put = p_cb.lock_owned().await;
+				let delete = match d_cb {
+					Some(cb) => Some(cb.lock_owned().await),
+					None => None,
+				};
+				(agents, put, delete)
+			};
+			drain_task(handle, idle, timeout).await
+		})
 	}
 }
 
@@ -100,6 +161,7 @@ where
 	/// Constructor for a [`LivelinessSubscriber`]
 	pub fn new(
 		session: Arc<Session>,
+		session_id: String,
 		token: String,
 		context: Context<P>,
 		activation_state: OperationState,
@@ -108,9 +170,11 @@ where
 	) -> Self {
 		Self {
 			session,
+			session_id,
 			token,
 			context,
 			activation_state,
+			paused: AtomicBool::new(false),
 			put_callback,
 			delete_callback,
 			handle: std::sync::Mutex::new(None),
@@ -126,38 +190,27 @@ where
 		self.stop()?;
 
 		// liveliness handling
-		let key = self.token.clone();
+		let signal = TaskSignal::RestartLiveliness(self.token.clone());
+		let sender = self.context.sender().clone();
 		let known_agents = self.known_agents.clone();
 		let session2 = self.session.clone();
 		let token2 = self.token.clone();
 		let p_cb2 = self.put_callback.clone();
 		let d_cb = self.delete_callback.clone();
 		let ctx = self.context.clone();
-		let ctx2 = self.context.clone();
 
 		self.handle.lock().map_or_else(
 			|_| todo!(),
 			|mut handle| {
-				handle.replace(tokio::task::spawn(async move {
-					std::panic::set_hook(Box::new(move |reason| {
-						error!("liveliness subscriber panic: {}", reason);
-						if let Err(reason) = ctx
-							.sender()
-							.blocking_send(TaskSignal::RestartLiveliness(key.clone()))
-						{
-							error!("could not restart liveliness subscriber: {}", reason);
-						} else {
-							info!("restarting liveliness subscriber!");
-						}
-					}));
-
-					// the liveliness subscriber with history
+				// the liveliness subscriber with history
+				let task = async move {
 					if let Err(error) =
-						run_liveliness(session2, token2, p_cb2, d_cb, ctx2, known_agents).await
+						run_liveliness(session2, token2, p_cb2, d_cb, ctx, known_agents).await
 					{
 						error!("running liveliness subscriber failed with {error}");
 					}
-				}));
+				};
+				handle.replace(tokio::task::spawn(supervise(task, sender, signal)));
 				Ok(())
 			},
 		)
@@ -169,7 +222,9 @@ where
 		self.handle.lock().map_or_else(
 			|_| todo!(),
 			|mut handle| {
-				handle.take();
+				if let Some(handle) = handle.take() {
+					handle.abort();
+				}
 				Ok(())
 			},
 		)
@@ -17,6 +17,8 @@ pub mod publisher;
 pub mod querier;
 /// the queryable
 pub mod queryable;
+/// the retries of queries
+mod retry;
 /// the subscriber
 pub mod subscriber;
 
@@ -26,7 +28,7 @@ pub use communicator::Communicator;
 pub use liveliness::LivelinessSubscriber;
 pub use observable::Observable;
 pub use observer::Observer;
-pub use publisher::Publisher;
+pub use publisher::{Publisher, TypedPublisher};
 pub use querier::Querier;
 pub use queryable::Queryable;
 pub use subscriber::Subscriber;
@@ -8,23 +8,34 @@ extern crate std;
 // region:		--- modules
 use alloc::{
 	boxed::Box,
+	format,
 	string::{String, ToString},
 	sync::Arc,
 	vec::Vec,
 };
 use bitcode::encode;
-use core::time::Duration;
+use core::{
+	sync::atomic::{AtomicBool, Ordering},
+	time::Duration,
+};
 use dimas_core::{
 	Result,
-	enums::{OperationState, TaskSignal},
-	message_types::{ControlResponse, Message, ObservableResponse},
-	traits::{Capability, Context},
-	utils::feedback_selector_from,
+	enums::{CapabilityKind, OperationState, TaskSignal},
+	message_types::{
+		CapabilityDescription, ControlResponse, Headers, Message, ObservableResponse,
+	},
+	policies::ErrorHandler,
+	trace_context::TraceContext,
+	traits::{Capability, Context, DrainFuture},
+	utils::{drain_task, feedback_selector_from, supervise},
 };
 use futures::future::BoxFuture;
 #[cfg(feature = "std")]
-use tokio::{sync::Mutex, task::JoinHandle};
-use tracing::{Level, error, info, instrument, warn};
+use tokio::{
+	sync::{Mutex, Notify},
+	task::JoinHandle,
+};
+use tracing::{Level, error, instrument, warn};
 use zenoh::Wait;
 #[cfg(feature = "unstable")]
 use zenoh::sample::Locality;
@@ -61,11 +72,15 @@ where
 {
 	/// the zenoh session this observable belongs to
 	session: Arc<Session>,
+	/// the id of the zenoh session
+	session_id: String,
 	/// The observables key expression
 	selector: String,
 	/// Context for the Observable
 	context: Context<P>,
 	activation_state: OperationState,
+	/// A paused observable is not started
+	paused: AtomicBool,
 	feedback_interval: Duration,
 	/// callback for observation request and cancelation
 	control_callback: ArcControlCallback<P>,
@@ -75,6 +90,10 @@ where
 	/// function for observation execution
 	execution_function: ArcExecutionCallback<P>,
 	execution_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+	/// notification about a finished execution, whose result has been published
+	execution_finished: Arc<Notify>,
+	/// Handling of the errors returned by the callbacks and the execution
+	errors: ErrorHandler<P>,
 	handle: std::sync::Mutex<Option<JoinHandle<()>>>,
 }
 
@@ -96,6 +115,27 @@ where
 	fn selector(&self) -> &str {
 		&self.selector
 	}
+
+	fn kind(&self) -> CapabilityKind {
+		CapabilityKind::Observable
+	}
+
+	fn describe(&self) -> CapabilityDescription {
+		let running = self.handle.lock().is_ok_and(|handle| {
+			handle
+				.as_ref()
+				.is_some_and(|handle| !handle.is_finished())
+		});
+		CapabilityDescription::new(
+			CapabilityKind::Observable,
+			self.selector.clone(),
+			Some(self.session_id.clone()),
+			self.activation_state.clone(),
+			running,
+			self.is_paused(),
+		)
+		.with_qos("feedback_interval", format!("{:?}", self.feedback_interval))
+	}
 }
 
 impl<P> Capability for Observable<P>
@@ -103,13 +143,70 @@ where
 	P: Send + Sync + 'static,
 {
 	fn manage_operation_state(&self, state: &OperationState) -> Result<()> {
-		if state >= &self.activation_state {
+		if state >= &self.activation_state && !self.is_paused() {
 			self.start()
-		} else if state < &self.activation_state {
-			self.stop()
 		} else {
-			Ok(())
+			self.stop()
+		}
+	}
+
+	fn set_paused(&self, paused: bool) -> Result<()> {
+		self.paused.store(paused, Ordering::Release);
+		Ok(())
+	}
+
+	fn is_paused(&self) -> bool {
+		self.paused.load(Ordering::Acquire)
+	}
+
+	fn drain(&self, state: &OperationState, timeout: Duration) -> DrainFuture {
+		if state >= &self.activation_state {
+			return Box::pin(core::future::ready(false));
 		}
+		let Some(handle) = self
+			.handle
+			.lock()
+			.map_or(None, |mut handle| handle.take())
+		else {
+			return Box::pin(core::future::ready(false));
+		};
+		let control_callback = self.control_callback.clone();
+		let feedback_callback = self.feedback_callback.clone();
+		let feedback_publisher = self.feedback_publisher.clone();
+		let execution_handle = self.execution_handle.clone();
+		let execution_finished = self.execution_finished.clone();
+		let errors = self.errors.clone();
+		let selector = self.selector.clone();
+		let ctx = self.context.clone();
+		Box::pin(async move {
+			let deadline = tokio::time::Instant::now() + timeout;
+			// a running execution gets the chance to finish and publish its result
+			let finished = tokio::time::timeout(
+				timeout,
+				execution_done(&execution_handle, &execution_finished),
+			)
+			.await
+			.is_ok();
+			// holding the callback locks guarantees that no control request is in-flight
+			let idle = async {
+				let control = control_callback.lock_owned().await;
+				let feedback = feedback_callback.clone().lock_owned().await;
+				(control, feedback)
+			};
+			let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+			let forced = drain_task(handle, idle, remaining).await;
+			// an execution still running is canceled, observers are informed
+			cancel_execution(
+				feedback_publisher,
+				feedback_callback,
+				execution_handle,
+				&errors,
+				&selector,
+				ctx,
+			)
+			.await;
+			forced || !finished
+		})
 	}
 }
 
@@ -122,9 +219,11 @@ where
 	#[must_use]
 	pub fn new(
 		session: Arc<Session>,
+		session_id: String,
 		selector: String,
 		context: Context<P>,
 		activation_state: OperationState,
+		errors: ErrorHandler<P>,
 		feedback_interval: Duration,
 		control_callback: ArcControlCallback<P>,
 		feedback_callback: ArcFeedbackCallback<P>,
@@ -132,15 +231,19 @@ where
 	) -> Self {
 		Self {
 			session,
+			session_id,
 			selector,
 			context,
 			activation_state,
+			paused: AtomicBool::new(false),
 			feedback_interval,
 			control_callback,
 			feedback_callback,
 			feedback_publisher: Arc::new(Mutex::new(None)),
 			execution_function,
 			execution_handle: Arc::new(Mutex::new(None)),
+			execution_finished: Arc::new(Notify::new()),
+			errors,
 			handle: std::sync::Mutex::new(None),
 		}
 	}
@@ -158,33 +261,36 @@ where
 		let fcbp = self.feedback_publisher.clone();
 		let efc = self.execution_function.clone();
 		let efch = self.execution_handle.clone();
-		let ctx1 = self.context.clone();
-		let ctx2 = self.context.clone();
+		let finished = self.execution_finished.clone();
+		let errors = self.errors.clone();
+		let signal = TaskSignal::RestartObservable(self.selector.clone());
+		let sender = self.context.sender().clone();
+		let ctx = self.context.clone();
 		let session = self.session.clone();
 
 		self.handle.lock().map_or_else(
 			|_| todo!(),
 			|mut handle| {
-				handle.replace(tokio::task::spawn(async move {
-					let key = selector.clone();
-					std::panic::set_hook(Box::new(move |reason| {
-						error!("observable panic: {}", reason);
-						if let Err(reason) = ctx1
-							.sender()
-							.blocking_send(TaskSignal::RestartObservable(key.clone()))
-						{
-							error!("could not restart observable: {}", reason);
-						} else {
-							info!("restarting observable!");
-						}
-					}));
-					if let Err(error) =
-						run_observable(session, selector, interval, ccb, fcb, fcbp, efc, efch, ctx2)
-							.await
+				let task = async move {
+					if let Err(error) = run_observable(
+						session,
+						selector,
+						interval,
+						ccb,
+						fcb,
+						fcbp,
+						efc,
+						efch,
+						finished,
+						errors,
+						ctx,
+					)
+					.await
 					{
 						error!("observable failed with {error}");
 					}
-				}));
+				};
+				handle.replace(tokio::task::spawn(supervise(task, sender, signal)));
 
 				Ok(())
 			},
@@ -202,26 +308,19 @@ where
 					let feedback_publisher = self.feedback_publisher.clone();
 					let feedback_callback = self.feedback_callback.clone();
 					let execution_handle = self.execution_handle.clone();
+					let errors = self.errors.clone();
+					let selector = self.selector.clone();
 					let ctx = self.context.clone();
 					tokio::spawn(async move {
-						// stop execution if running
-						if let Some(execution_handle) = execution_handle.lock().await.take() {
-							execution_handle.abort();
-							// send back cancelation message
-							if let Some(publisher) = feedback_publisher.lock().await.take() {
-								let Ok(msg) = feedback_callback.lock().await(ctx).await else {
-									todo!()
-								};
-								let response = ObservableResponse::Canceled(msg.value().clone());
-								match publisher
-									.put(Message::encode(&response).value().clone())
-									.wait()
-								{
-									Ok(()) => {}
-									Err(err) => error!("could not send cancel state due to {err}"),
-								}
-							}
-						}
+						cancel_execution(
+							feedback_publisher,
+							feedback_callback,
+							execution_handle,
+							&errors,
+							&selector,
+							ctx,
+						)
+						.await;
 						handle.abort();
 					});
 				}
@@ -233,6 +332,73 @@ where
 // endregion:	--- Observable
 
 // region:		--- functions
+/// Wait until no execution is running anymore
+async fn execution_done(execution_handle: &Mutex<Option<JoinHandle<()>>>, finished: &Notify) {
+	loop {
+		// registered before checking, so that no notification gets lost
+		let notified = finished.notified();
+		if execution_handle.lock().await.is_none() {
+			return;
+		}
+		notified.await;
+	}
+}
+
+/// Stop a running execution and send back a cancelation message
+#[allow(clippy::significant_drop_tightening)]
+async fn cancel_execution<P>(
+	feedback_publisher: Arc<Mutex<Option<zenoh::pubsub::Publisher<'static>>>>,
+	feedback_callback: ArcFeedbackCallback<P>,
+	execution_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+	errors: &ErrorHandler<P>,
+	selector: &str,
+	ctx: Context<P>,
+) where
+	P: Send + Sync + 'static,
+{
+	// stop execution if running
+	let execution_handle = execution_handle.lock().await.take();
+	if let Some(execution_handle) = execution_handle {
+		execution_handle.abort();
+		// send back cancelation message
+		let publisher = feedback_publisher.lock().await.take();
+		if let Some(publisher) = publisher {
+			let response = canceled(&feedback_callback, errors, selector, ctx).await;
+			match publisher
+				.put(Message::encode(&response).value().clone())
+				.wait()
+			{
+				Ok(()) => {}
+				Err(err) => error!("could not send cancel state due to {err}"),
+			}
+		}
+	}
+}
+
+/// The cancelation message with the content of the feedback callback,
+/// which is empty if the feedback callback failed
+async fn canceled<P>(
+	feedback_callback: &ArcFeedbackCallback<P>,
+	errors: &ErrorHandler<P>,
+	selector: &str,
+	ctx: Context<P>,
+) -> ObservableResponse
+where
+	P: Send + Sync + 'static,
+{
+	let result = feedback_callback.lock().await(ctx.clone()).await;
+	match result {
+		Ok(msg) => {
+			errors.handle(&ctx, selector, Ok(()));
+			ObservableResponse::Canceled(msg.value().clone())
+		}
+		Err(error) => {
+			errors.handle(&ctx, selector, Err(error));
+			ObservableResponse::Canceled(Vec::new())
+		}
+	}
+}
+
 #[allow(clippy::significant_drop_tightening)]
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::too_many_lines)]
@@ -246,6 +412,8 @@ async fn run_observable<P>(
 	feedback_publisher: Arc<Mutex<Option<zenoh::pubsub::Publisher<'static>>>>,
 	execution_function: ArcExecutionCallback<P>,
 	execution_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+	execution_finished: Arc<Notify>,
+	errors: ErrorHandler<P>,
 	ctx: Context<P>,
 ) -> Result<()>
 where
@@ -308,11 +476,16 @@ where
 								content
 							},
 						);
-						let msg = Message::new(content);
+						let msg = Message::new(content)
+							.with_headers(Headers::from_zbytes(query.attachment()));
+						let trace = TraceContext::continue_or_new(msg.headers());
 						let ctx_clone = ctx.clone();
-						let res = control_callback.lock().await(ctx_clone, msg).await;
+						let res = trace
+							.scope(&selector, control_callback.lock().await(ctx_clone, msg))
+							.await;
 						match res {
 							Ok(response) => {
+								errors.handle(&ctx, &selector, Ok(()));
 								if matches!(response, ControlResponse::Accepted ) {
 									// create feedback publisher
 									let mut fp = feedback_publisher.lock().await;
@@ -331,10 +504,14 @@ where
 									let tx_clone = tx.clone();
 									let execution_function_clone = execution_function.clone();
 									let ctx_clone = ctx.clone();
-									execution_handle.lock().await.replace(tokio::spawn( async move {
-										let res = execution_function_clone.lock().await(ctx_clone).await.unwrap_or_else(|_| { todo!() });
+									let execution = async move {
+										let res = execution_function_clone.lock().await(ctx_clone).await;
 										if !matches!(tx_clone.send(res).await, Ok(())) { error!("failed to send back execution result") }
-									}));
+									};
+									// a panicking execution restarts the observable, which cancels the observation
+									let signal = TaskSignal::RestartObservable(selector.clone());
+									let sender = ctx.sender().clone();
+									execution_handle.lock().await.replace(tokio::spawn(supervise(execution, sender, signal)));
 
 									// start feedback timer
 									feedback_timer.set(tokio::time::sleep(feedback_interval));
@@ -347,7 +524,7 @@ where
 									Err(err) => error!("failed to reply with {err}"),
 								}
 							}
-							Err(error) => error!("control callback failed with {error}"),
+							Err(error) => errors.handle(&ctx, &selector, Err(error)),
 						}
 					}
 				} else if p == "cancel" {
@@ -360,9 +537,8 @@ where
 							h.abort();
 							// wait for abortion
 							let _ = h.await;
-							let Ok(msg) = feedback_callback.lock().await(ctx).await else { todo!() };
 							let response =
-								ObservableResponse::Canceled(msg.value().clone());
+								canceled(&feedback_callback, &errors, &selector, ctx).await;
 							if let Some(p) = publisher {
 								match p.put(Message::encode(&response).value().clone()).wait() {
 									Ok(()) => {},
@@ -374,6 +550,7 @@ where
 						} else {
 							error!("unexpected absence of execution handle");
 						}
+						execution_finished.notify_waiters();
 					}
 					// acknowledge cancel request
 					let encoded: Vec<u8> = encode(&ControlResponse::Canceled);
@@ -390,8 +567,17 @@ where
 			Some(result) = rx.recv() => {
 				if is_running {
 					is_running = false;
-					execution_handle.lock().await.take();
-					let response = ObservableResponse::Finished(result.value().clone());
+					// a failed execution is reported to the observers as canceled
+					let response = match result {
+						Ok(msg) => {
+							errors.handle(&ctx, &selector, Ok(()));
+							ObservableResponse::Finished(msg.value().clone())
+						}
+						Err(error) => {
+							errors.handle(&ctx, &selector, Err(error));
+							ObservableResponse::Canceled(Vec::new())
+						}
+					};
 					feedback_publisher.lock().await.take().map_or_else(
 						|| error!("could not publish result"),
 						|p| {
@@ -401,23 +587,32 @@ where
 							}
 						}
 					);
+					// the result is published, a draining observable may stop now
+					execution_handle.lock().await.take();
+					execution_finished.notify_waiters();
 				}
 			}
 
 			// feedback timer expired and observable still is executing
 			() = &mut feedback_timer, if is_running => {
-				let Ok(msg) = feedback_callback.lock().await(ctx).await else { todo!() };
-				let response =
-					ObservableResponse::Feedback(msg.value().clone());
-
-				let lock = feedback_publisher.lock().await;
-				let publisher = lock.as_ref().map_or_else(
-					|| { todo!() },
-					|p| p
-				);
-				match publisher.put(Message::encode(&response).value().clone()).wait() {
-					Ok(()) => {},
-					Err(err) => error!("publishing feedback failed due to {err}"),
+				let result = feedback_callback.lock().await(ctx.clone()).await;
+				match result {
+					Ok(msg) => {
+						errors.handle(&ctx, &selector, Ok(()));
+						let response = ObservableResponse::Feedback(msg.value().clone());
+						let lock = feedback_publisher.lock().await;
+						lock.as_ref().map_or_else(
+							|| error!("missing publisher"),
+							|publisher| {
+								let value = Message::encode(&response).value().clone();
+								match publisher.put(value).wait() {
+									Ok(()) => {},
+									Err(err) => error!("publishing feedback failed due to {err}"),
+								}
+							}
+						);
+					}
+					Err(error) => errors.handle(&ctx, &selector, Err(error)),
 				}
 
 				// restart timer
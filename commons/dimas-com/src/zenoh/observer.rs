@@ -11,16 +11,23 @@ use alloc::sync::Arc;
 use alloc::{
 	borrow::ToOwned,
 	boxed::Box,
+	format,
 	string::{String, ToString},
 	vec::Vec,
 };
 use bitcode::decode;
-use core::time::Duration;
+use core::{
+	sync::atomic::{AtomicBool, Ordering},
+	time::Duration,
+};
 use dimas_core::{
 	Result,
-	enums::OperationState,
-	message_types::{ControlResponse, Message, ObservableResponse},
-	traits::{Capability, Context},
+	enums::{CapabilityKind, OperationState},
+	message_types::{
+		CapabilityDescription, ControlResponse, Headers, Message, ObservableResponse,
+	},
+	trace_context,
+	traits::{Capability, Context, DrainFuture},
 	utils::{cancel_selector_from, feedback_selector_from, request_selector_from},
 };
 use futures::future::BoxFuture;
@@ -60,11 +67,17 @@ where
 {
 	/// the zenoh session this observer belongs to
 	session: Arc<Session>,
+	/// the id of the zenoh session
+	session_id: String,
 	/// The observers key expression
 	selector: String,
 	/// Context for the Observer
 	context: Context<P>,
 	activation_state: OperationState,
+	/// A paused observer is not started
+	paused: AtomicBool,
+	/// Whether the observer is initialized
+	running: AtomicBool,
 	/// callback for control request results
 	control_callback: ArcControlCallback<P>,
 	/// callback for responses
@@ -166,11 +179,16 @@ where
 		let session = self.session.clone();
 		// TODO: make a proper "key: value" implementation
 		let selector = request_selector_from(&self.selector);
+		let attachment = message.as_ref().map_or_else(
+			|| trace_context::attachment(&Headers::new()),
+			Message::attachment,
+		);
 		let mut query = session
 			.get(&selector)
 			.target(QueryTarget::All)
 			.consolidation(ConsolidationMode::None)
-			.timeout(self.timeout);
+			.timeout(self.timeout)
+			.attachment(attachment);
 
 		if let Some(message) = message {
 			let value = message.value().to_owned();
@@ -269,6 +287,18 @@ where
 		}
 		Ok(())
 	}
+
+	fn describe(&self) -> CapabilityDescription {
+		CapabilityDescription::new(
+			CapabilityKind::Observer,
+			self.selector.clone(),
+			Some(self.session_id.clone()),
+			self.activation_state.clone(),
+			self.running.load(Ordering::Acquire),
+			self.is_paused(),
+		)
+		.with_qos("timeout", format!("{:?}", self.timeout))
+	}
 }
 
 impl<P> Capability for Observer<P>
@@ -276,13 +306,28 @@ where
 	P: Send + Sync + 'static,
 {
 	fn manage_operation_state(&self, state: &OperationState) -> Result<()> {
-		if state >= &self.activation_state {
-			return self.init();
-		} else if state < &self.activation_state {
-			return self.de_init();
+		if state >= &self.activation_state && !self.is_paused() {
+			self.init()
+		} else {
+			self.de_init()
 		}
+	}
+
+	fn set_paused(&self, paused: bool) -> Result<()> {
+		self.paused.store(paused, Ordering::Release);
 		Ok(())
 	}
+
+	fn is_paused(&self) -> bool {
+		self.paused.load(Ordering::Acquire)
+	}
+
+	fn drain(&self, state: &OperationState, _timeout: Duration) -> DrainFuture {
+		if state < &self.activation_state {
+			let _ = self.de_init();
+		}
+		Box::pin(core::future::ready(false))
+	}
 }
 
 impl<P> Observer<P>
@@ -290,9 +335,11 @@ where
 	P: Send + Sync + 'static,
 {
 	/// Constructor for an [`Observer`]
+	#[allow(clippy::too_many_arguments)]
 	#[must_use]
 	pub fn new(
 		session: Arc<Session>,
+		session_id: String,
 		selector: String,
 		context: Context<P>,
 		activation_state: OperationState,
@@ -302,9 +349,12 @@ where
 	) -> Self {
 		Self {
 			session,
+			session_id,
 			selector,
 			context,
 			activation_state,
+			paused: AtomicBool::new(false),
+			running: AtomicBool::new(false),
 			control_callback,
 			response_callback,
 			timeout,
@@ -317,7 +367,9 @@ where
 	///
 	#[instrument(level = Level::TRACE, skip_all)]
 	fn init(&self) -> Result<()> {
-		self.de_init()
+		self.de_init()?;
+		self.running.store(true, Ordering::Release);
+		Ok(())
 	}
 
 	/// De-Initialize
@@ -327,6 +379,7 @@ where
 	fn de_init(&self) -> Result<()> {
 		// cancel current request before stopping
 		let _ = crate::traits::Observer::cancel(self);
+		self.running.store(false, Ordering::Release);
 		self.handle.lock().map_or_else(
 			|_| todo!(),
 			|mut handle| {
@@ -10,12 +10,31 @@ extern crate std;
 
 // region:		--- modules
 use crate::error::Error;
-use alloc::{string::String, sync::Arc};
-use core::fmt::Debug;
-use dimas_core::{Result, enums::OperationState, message_types::Message, traits::Capability};
+use crate::traits::Publisher as PublisherTrait;
+use alloc::{
+	boxed::Box,
+	format,
+	string::{String, ToString},
+	sync::Arc,
+};
+use core::{
+	fmt::Debug,
+	marker::PhantomData,
+	sync::atomic::{AtomicBool, Ordering},
+	time::Duration,
+};
+use dimas_core::{
+	Result,
+	codec::{Bitcode, Encoder},
+	enums::{CapabilityKind, OperationState},
+	handle::CapabilityHandle,
+	message_types::{CapabilityDescription, Headers, Message},
+	traits::{Capability, Context, DrainFuture},
+};
 use tracing::{Level, instrument};
 use zenoh::{
 	Session, Wait,
+	bytes::Encoding,
 	qos::{CongestionControl, Priority},
 };
 #[cfg(feature = "unstable")]
@@ -27,8 +46,12 @@ use zenoh::{qos::Reliability, sample::Locality};
 pub struct Publisher {
 	/// the zenoh session this publisher belongs to
 	session: Arc<Session>,
+	/// the id of the zenoh session
+	session_id: String,
 	selector: String,
 	activation_state: OperationState,
+	/// A paused publisher is not started
+	paused: AtomicBool,
 	#[cfg(feature = "unstable")]
 	allowed_destination: Locality,
 	congestion_control: CongestionControl,
@@ -49,7 +72,7 @@ impl Debug for Publisher {
 	}
 }
 
-impl crate::traits::Publisher for Publisher {
+impl PublisherTrait for Publisher {
 	/// Get `selector`
 	fn selector(&self) -> &str {
 		&self.selector
@@ -57,19 +80,38 @@ impl crate::traits::Publisher for Publisher {
 
 	/// Send a "put" message
 	/// # Errors
-	///
+	/// if the message is encoded with a codec not matching the publishers encoding
 	#[instrument(name="publish", level = Level::ERROR, skip_all)]
 	fn put(&self, message: Message) -> Result<()> {
+		if let Some(encoding) = message.encoding() {
+			if !self.accepts(encoding) {
+				return Err(dimas_core::error::Error::UnexpectedEncoding {
+					expected: self.encoding.clone(),
+					found: encoding.to_string(),
+				}
+				.into());
+			}
+		}
 		self.declared_publ.lock().map_or_else(
 			|_| todo!(),
-			|publisher| match publisher
-				.as_ref()
-				.ok_or(Error::AccessPublisher)?
-				.put(message.value())
-				.wait()
-			{
-				Ok(()) => Ok(()),
-				Err(source) => Err(Error::PublishingPut { source }.into()),
+			|publisher| {
+				let builder = publisher
+					.as_ref()
+					.ok_or(Error::AccessPublisher)?
+					.put(message.value());
+				// a message encoded with a codec overrides the publishers encoding
+				let builder = match message.encoding() {
+					Some(encoding) => builder.encoding(encoding),
+					None => builder,
+				};
+				let builder = match message.attachment() {
+					Some(attachment) => builder.attachment(attachment),
+					None => builder,
+				};
+				match builder.wait() {
+					Ok(()) => Ok(()),
+					Err(source) => Err(Error::PublishingPut { source }.into()),
+				}
 			},
 		)
 	}
@@ -92,17 +134,62 @@ impl crate::traits::Publisher for Publisher {
 			},
 		)
 	}
+
+	fn describe(&self) -> CapabilityDescription {
+		let running = self
+			.declared_publ
+			.lock()
+			.is_ok_and(|publisher| publisher.is_some());
+		let description = CapabilityDescription::new(
+			CapabilityKind::Publisher,
+			self.selector.clone(),
+			Some(self.session_id.clone()),
+			self.activation_state.clone(),
+			running,
+			self.is_paused(),
+		)
+		.with_qos(
+			"congestion_control",
+			format!("{:?}", self.congestion_control),
+		)
+		.with_qos("encoding", self.encoding.clone())
+		.with_qos("express", self.express.to_string())
+		.with_qos("priority", format!("{:?}", self.priority));
+		#[cfg(feature = "unstable")]
+		let description = description
+			.with_qos(
+				"allowed_destination",
+				format!("{:?}", self.allowed_destination),
+			)
+			.with_qos("reliability", format!("{:?}", self.reliability));
+		description
+	}
 }
 
 impl Capability for Publisher {
 	fn manage_operation_state(&self, state: &OperationState) -> Result<()> {
-		if state >= &self.activation_state {
-			return self.init();
-		} else if state < &self.activation_state {
-			return self.de_init();
+		if state >= &self.activation_state && !self.is_paused() {
+			self.init()
+		} else {
+			self.de_init()
 		}
+	}
+
+	fn set_paused(&self, paused: bool) -> Result<()> {
+		self.paused.store(paused, Ordering::Release);
 		Ok(())
 	}
+
+	fn is_paused(&self) -> bool {
+		self.paused.load(Ordering::Acquire)
+	}
+
+	fn drain(&self, state: &OperationState, _timeout: Duration) -> DrainFuture {
+		if state < &self.activation_state {
+			let _ = self.de_init();
+		}
+		Box::pin(core::future::ready(false))
+	}
 }
 
 impl Publisher {
@@ -111,6 +198,7 @@ impl Publisher {
 	#[must_use]
 	pub const fn new(
 		session: Arc<Session>,
+		session_id: String,
 		selector: String,
 		activation_state: OperationState,
 		#[cfg(feature = "unstable")] allowed_destination: Locality,
@@ -122,8 +210,10 @@ impl Publisher {
 	) -> Self {
 		Self {
 			session,
+			session_id,
 			selector,
 			activation_state,
+			paused: AtomicBool::new(false),
 			#[cfg(feature = "unstable")]
 			allowed_destination,
 			congestion_control,
@@ -166,6 +256,13 @@ impl Publisher {
 		)
 	}
 
+	/// Whether a message with the given zenoh `encoding` may be published.
+	/// A publisher without specific encoding publishes messages of any encoding.
+	fn accepts(&self, encoding: &str) -> bool {
+		let own = Encoding::from(self.encoding.as_str());
+		own == Encoding::ZENOH_BYTES || own == Encoding::from(encoding)
+	}
+
 	/// De-Initialize
 	/// # Errors
 	///
@@ -182,15 +279,112 @@ impl Publisher {
 }
 // endregion:	--- Publisher
 
+// region:		--- TypedPublisher
+/// A [`Publisher`] registered in an agent, which publishes values of type `T`.
+///
+/// The values are encoded with the [`Codec`](dimas_core::codec::Codec) `C` before publishing,
+/// so that subscribers created with `SubscriberBuilder::typed::<T>()` receive them decoded.
+pub struct TypedPublisher<P, T, C = Bitcode>
+where
+	P: Send + Sync + 'static,
+{
+	handle: CapabilityHandle<P, Box<dyn PublisherTrait>>,
+	context: Context<P>,
+	value: PhantomData<fn(&T)>,
+	codec: PhantomData<fn() -> C>,
+}
+
+impl<P, T, C> Debug for TypedPublisher<P, T, C>
+where
+	P: Send + Sync + 'static,
+{
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("TypedPublisher")
+			.field("selector", &self.handle.selector())
+			.field("type", &core::any::type_name::<T>())
+			.field("codec", &core::any::type_name::<C>())
+			.finish_non_exhaustive()
+	}
+}
+
+impl<P, T, C> TypedPublisher<P, T, C>
+where
+	P: Send + Sync + 'static,
+	C: Encoder<T>,
+{
+	/// Constructor for a [`TypedPublisher`] using the publisher registered with `handle`
+	#[must_use]
+	pub const fn new(
+		handle: CapabilityHandle<P, Box<dyn PublisherTrait>>,
+		context: Context<P>,
+	) -> Self {
+		Self {
+			handle,
+			context,
+			value: PhantomData,
+			codec: PhantomData,
+		}
+	}
+
+	/// Get `selector`
+	#[must_use]
+	pub fn selector(&self) -> &str {
+		self.handle.selector()
+	}
+
+	/// Get the [`CapabilityHandle`] to pause or resume the publisher
+	#[must_use]
+	pub const fn handle(&self) -> &CapabilityHandle<P, Box<dyn PublisherTrait>> {
+		&self.handle
+	}
+
+	/// Encode and publish `value`
+	/// # Errors
+	/// if encoding fails, the publisher is not declared or publishing fails
+	pub fn put(&self, value: &T) -> Result<()> {
+		self.context
+			.put_with(self.handle.selector(), Message::encode_with::<C, T>(value)?)
+	}
+
+	/// Encode and publish `value` with the `headers` as attachment
+	/// # Errors
+	/// if encoding fails, the publisher is not declared or publishing fails
+	pub fn put_with_headers(&self, value: &T, headers: Headers) -> Result<()> {
+		let message = Message::encode_with::<C, T>(value)?.with_headers(headers);
+		self.context
+			.put_with(self.handle.selector(), message)
+	}
+
+	/// Send a "delete" message
+	/// # Errors
+	/// if the publisher is not declared or publishing fails
+	pub fn delete(&self) -> Result<()> {
+		self.context.delete_with(self.handle.selector())
+	}
+
+	/// Stop the publisher and remove it from the agent.
+	/// # Errors
+	/// if the storage is not accessible
+	pub fn remove(self) -> Result<Option<Box<dyn PublisherTrait>>> {
+		self.handle.remove()
+	}
+}
+// endregion:	--- TypedPublisher
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 
+	#[derive(Debug)]
+	struct Props {}
+
 	// check, that the auto traits are available
 	const fn is_normal<T: Sized + Send + Sync>() {}
 
 	#[test]
 	const fn normal_types() {
 		is_normal::<Publisher>();
+		is_normal::<TypedPublisher<Props, u32>>();
+		is_normal::<TypedPublisher<Props, u32, dimas_core::codec::Json>>();
 	}
 }
@@ -9,30 +9,41 @@ extern crate alloc;
 extern crate std;
 
 // region:		--- modules
-use crate::error::Error;
+use crate::{error::Error, zenoh::retry};
 use alloc::sync::Arc;
-use core::{fmt::Debug, time::Duration};
+use core::{
+	fmt::Debug,
+	sync::atomic::{AtomicBool, Ordering},
+	time::Duration,
+};
 use dimas_core::{
 	Result,
-	enums::OperationState,
-	message_types::{Message, QueryableMsg},
-	traits::{Capability, Context},
+	enums::{CapabilityKind, OperationState},
+	message_types::{CapabilityDescription, Headers, Message, QueryableMsg, Replies},
+	policies::RetryPolicy,
+	trace_context,
+	traits::{Capability, Context, DrainFuture},
+};
+use futures::{
+	StreamExt,
+	future::{self, BoxFuture},
 };
-use futures::future::BoxFuture;
 #[cfg(feature = "std")]
 use std::{
 	boxed::Box,
+	format,
 	string::{String, ToString},
-	vec::Vec,
 };
 #[cfg(feature = "std")]
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, watch};
 use tracing::{Level, error, instrument, warn};
 #[cfg(feature = "unstable")]
 use zenoh::sample::Locality;
 use zenoh::{
 	Session, Wait,
-	query::{ConsolidationMode, QueryTarget},
+	handlers::FifoChannelHandler,
+	key_expr::KeyExpr,
+	query::{ConsolidationMode, QueryTarget, Reply},
 	sample::SampleKind,
 };
 // endregion:	--- modules
@@ -53,10 +64,14 @@ where
 {
 	/// the zenoh session this querier belongs to
 	session: Arc<Session>,
+	/// the id of the zenoh session
+	session_id: String,
 	selector: String,
 	/// Context for the Querier
 	context: Context<P>,
 	activation_state: OperationState,
+	/// A paused querier is not started
+	paused: AtomicBool,
 	callback: ArcGetCallback<P>,
 	mode: ConsolidationMode,
 	#[cfg(feature = "unstable")]
@@ -64,7 +79,10 @@ where
 	encoding: String,
 	target: QueryTarget,
 	timeout: Duration,
+	retry: RetryPolicy,
 	key_expr: std::sync::Mutex<Option<zenoh::key_expr::KeyExpr<'static>>>,
+	/// The number of pending queries
+	pending: watch::Sender<usize>,
 }
 
 impl<P> Debug for Querier<P>
@@ -99,95 +117,67 @@ where
 	}
 
 	/// Run a Querier with an optional [`Message`].
+	///
+	/// An unanswered query is repeated according to the queriers [`RetryPolicy`].
+	/// The current thread is blocked until the query is answered or the attempts are exhausted,
+	/// within a multi threaded runtime the other tasks of its worker are moved to another thread.
+	/// Within a current thread runtime a query with retries is rejected, as it would block all tasks.
 	#[instrument(name="Querier", level = Level::ERROR, skip_all)]
 	fn get(
 		&self,
 		message: Option<Message>,
 		mut callback: Option<&mut dyn FnMut(QueryableMsg) -> Result<()>>,
 	) -> Result<()> {
-		let cb = self.callback.clone();
-		self.key_expr.lock().map_or_else(
-			|_| todo!(),
-			|key_expr| {
-				let key_expr = key_expr
-					.clone()
-					.ok_or_else(|| Error::InvalidSelector("querier".into()))?;
-
-				let builder = message
-					.map_or_else(
-						|| self.session.get(&key_expr),
-						|msg| {
-							self.session
-								.get(&self.selector)
-								.payload(msg.value())
-						},
-					)
-					.encoding(self.encoding.as_str())
-					.target(self.target)
-					.consolidation(self.mode)
-					.timeout(self.timeout);
-
-				#[cfg(feature = "unstable")]
-				let builder = builder.allowed_destination(self.allowed_destination);
-
-				let query = builder
-					.wait()
-					.map_err(|source| Error::QueryCreation { source })?;
-
-				let mut unreached = true;
-				let mut retry_count = 0u8;
-
-				while unreached && retry_count <= 5 {
-					retry_count += 1;
-					while let Ok(reply) = query.recv() {
-						match reply.result() {
-							Ok(sample) => match sample.kind() {
-								SampleKind::Put => {
-									let content: Vec<u8> = sample.payload().to_bytes().into_owned();
-									let msg = QueryableMsg(content);
-									if callback.is_none() {
-										let cb = cb.clone();
-										let ctx = self.context.clone();
-										tokio::task::spawn(async move {
-											let mut lock = cb.lock().await;
-											if let Err(error) = lock(ctx, msg).await {
-												error!("querier callback failed with {error}");
-											}
-										});
-									} else {
-										let callback = callback.as_mut().ok_or_else(|| {
-											Error::AccessingQuerier {
-												selector: key_expr.to_string(),
-											}
-										})?;
-										callback(msg)
-											.map_err(|source| Error::QueryCallback { source })?;
-									}
-								}
-								SampleKind::Delete => {
-									error!("Delete in Querier");
-								}
-							},
-							Err(err) => error!("receive error: {:?})", err),
-						}
-						unreached = false;
-					}
-					if unreached {
-						if retry_count < 5 {
-							std::thread::sleep(self.timeout);
-						} else {
-							return Err(Error::AccessingQueryable {
-								selector: key_expr.to_string(),
-							}
-							.into());
-						}
-					}
-				}
+		let key_expr = self.key_expr()?;
+		let _pending = PendingQuery::new(&self.pending);
+		retry::get_blocking(
+			key_expr.as_ref(),
+			self.retry,
+			self.timeout,
+			self.sender(key_expr.clone(), message),
+			|replies| self.receive(replies, &mut callback),
+		)
+	}
 
-				Ok(())
-			},
+	/// Run a Querier with an optional [`Message`] without blocking.
+	///
+	/// An unanswered query is repeated according to the queriers [`RetryPolicy`].
+	#[instrument(name="Querier", level = Level::ERROR, skip_all)]
+	fn get_async(&self, message: Option<Message>, timeout: Option<Duration>) -> Result<Replies> {
+		let key_expr = self.key_expr()?;
+		retry::get_async(
+			key_expr.to_string(),
+			self.retry,
+			timeout.unwrap_or(self.timeout),
+			self.sender(key_expr, message),
 		)
 	}
+
+	fn describe(&self) -> CapabilityDescription {
+		let running = self
+			.key_expr
+			.lock()
+			.is_ok_and(|key_expr| key_expr.is_some());
+		let description = CapabilityDescription::new(
+			CapabilityKind::Querier,
+			self.selector.clone(),
+			Some(self.session_id.clone()),
+			self.activation_state.clone(),
+			running,
+			self.is_paused(),
+		)
+		.with_qos("mode", format!("{:?}", self.mode))
+		.with_qos("encoding", self.encoding.clone())
+		.with_qos("target", format!("{:?}", self.target))
+		.with_qos("timeout", format!("{:?}", self.timeout))
+		.with_qos("retry", format!("{:?}", self.retry));
+		#[cfg(feature = "unstable")]
+		let description = description.with_qos(
+			"allowed_destination",
+			format!("{:?}", self.allowed_destination),
+		);
+		description
+	}
 }
 
 impl<P> Capability for Querier<P>
@@ -195,13 +185,35 @@ where
 	P: Send + Sync + 'static,
 {
 	fn manage_operation_state(&self, state: &OperationState) -> Result<()> {
-		if state >= &self.activation_state {
-			return self.init();
-		} else if state < &self.activation_state {
-			return self.de_init();
+		if state >= &self.activation_state && !self.is_paused() {
+			self.init()
+		} else {
+			self.de_init()
 		}
+	}
+
+	fn set_paused(&self, paused: bool) -> Result<()> {
+		self.paused.store(paused, Ordering::Release);
 		Ok(())
 	}
+
+	fn is_paused(&self) -> bool {
+		self.paused.load(Ordering::Acquire)
+	}
+
+	fn drain(&self, state: &OperationState, timeout: Duration) -> DrainFuture {
+		if state >= &self.activation_state {
+			return Box::pin(core::future::ready(false));
+		}
+		// no new queries can be started, but the pending ones get the chance to finish
+		let _ = self.de_init();
+		let mut pending = self.pending.subscribe();
+		Box::pin(async move {
+			tokio::time::timeout(timeout, pending.wait_for(|pending| *pending == 0))
+				.await
+				.is_err()
+		})
+	}
 }
 
 impl<P> Querier<P>
@@ -213,6 +225,7 @@ where
 	#[allow(clippy::too_many_arguments)]
 	pub fn new(
 		session: Arc<Session>,
+		session_id: String,
 		selector: String,
 		context: Context<P>,
 		activation_state: OperationState,
@@ -222,12 +235,15 @@ where
 		encoding: String,
 		target: QueryTarget,
 		timeout: Duration,
+		retry: RetryPolicy,
 	) -> Self {
 		Self {
 			session,
+			session_id,
 			selector,
 			context,
 			activation_state,
+			paused: AtomicBool::new(false),
 			callback: response_callback,
 			mode,
 			#[cfg(feature = "unstable")]
@@ -235,10 +251,110 @@ where
 			encoding,
 			target,
 			timeout,
+			retry,
 			key_expr: std::sync::Mutex::new(None),
+			pending: watch::Sender::new(0),
 		}
 	}
 
+	/// Get the declared key expression
+	/// # Errors
+	/// if the querier is not initialized
+	fn key_expr(&self) -> Result<KeyExpr<'static>> {
+		self.key_expr
+			.lock()
+			.map_err(|_| Error::ReadAccess("querier".into()))?
+			.clone()
+			.ok_or_else(|| Error::InvalidSelector("querier".into()).into())
+	}
+
+	/// Get the function sending a query for `key_expr` with an optional [`Message`].
+	/// The function gets the timeout of an attempt and returns the receiver for the replies.
+	fn sender(
+		&self,
+		key_expr: KeyExpr<'static>,
+		message: Option<Message>,
+	) -> impl FnMut(Duration) -> Result<FifoChannelHandler<Reply>> + Send + 'static {
+		let session = self.session.clone();
+		let selector = self.selector.clone();
+		// a message encoded with a codec brings its own encoding
+		let encoding = message
+			.as_ref()
+			.and_then(Message::encoding)
+			.unwrap_or(&self.encoding)
+			.to_string();
+		let attachment = message.as_ref().map_or_else(
+			|| trace_context::attachment(&Headers::new()),
+			Message::attachment,
+		);
+		let target = self.target;
+		let mode = self.mode;
+		#[cfg(feature = "unstable")]
+		let allowed_destination = self.allowed_destination;
+		move |timeout| {
+			let builder = message
+				.as_ref()
+				.map_or_else(
+					|| session.get(&key_expr),
+					|msg| session.get(&selector).payload(msg.value()),
+				)
+				.encoding(encoding.clone())
+				.target(target)
+				.consolidation(mode)
+				.timeout(timeout);
+			let builder = match attachment.clone() {
+				Some(attachment) => builder.attachment(attachment),
+				None => builder,
+			};
+
+			#[cfg(feature = "unstable")]
+			let builder = builder.allowed_destination(allowed_destination);
+
+			builder
+				.wait()
+				.map_err(|source| Error::QueryCreation { source }.into())
+		}
+	}
+
+	/// Hand the replies of a `query` to the given `callback` or to the queriers callback.
+	/// Returns `false`, if there was no reply.
+	/// # Errors
+	/// if the given callback fails
+	fn receive(
+		&self,
+		query: &FifoChannelHandler<Reply>,
+		callback: &mut Option<&mut dyn FnMut(QueryableMsg) -> Result<()>>,
+	) -> Result<bool> {
+		let mut reached = false;
+		while let Ok(reply) = query.recv() {
+			match reply.result() {
+				Ok(sample) => match sample.kind() {
+					SampleKind::Put => {
+						let msg = QueryableMsg::from(sample);
+						if let Some(callback) = callback.as_mut() {
+							callback(msg).map_err(|source| Error::QueryCallback { source })?;
+						} else {
+							let cb = self.callback.clone();
+							let ctx = self.context.clone();
+							tokio::task::spawn(async move {
+								let mut lock = cb.lock().await;
+								if let Err(error) = lock(ctx, msg).await {
+									error!("querier callback failed with {error}");
+								}
+							});
+						}
+					}
+					SampleKind::Delete => {
+						error!("Delete in Querier");
+					}
+				},
+				Err(err) => error!("receive error: {:?})", err),
+			}
+			reached = true;
+		}
+		Ok(reached)
+	}
+
 	/// Initialize
 	/// # Errors
 	fn init(&self) -> Result<()>
@@ -276,6 +392,41 @@ where
 		)
 	}
 }
+
+/// Counts a blocking query as pending as long as it exists
+struct PendingQuery<'a>(&'a watch::Sender<usize>);
+
+impl<'a> PendingQuery<'a> {
+	fn new(pending: &'a watch::Sender<usize>) -> Self {
+		pending.send_modify(|pending| *pending += 1);
+		Self(pending)
+	}
+}
+
+impl Drop for PendingQuery<'_> {
+	fn drop(&mut self) {
+		self.0.send_modify(|pending| *pending -= 1);
+	}
+}
+
+/// Receive the `replies` to a query for `selector` as [`Replies`].
+/// Deletions are skipped, error replies are passed on as [`Error::ErrorReply`].
+pub(crate) fn replies(selector: String, replies: FifoChannelHandler<Reply>) -> Replies {
+	Replies::new(replies.into_stream().filter_map(move |reply| {
+		let reply = match reply.result() {
+			Ok(sample) => match sample.kind() {
+				SampleKind::Put => Some(Ok(QueryableMsg::from(sample))),
+				SampleKind::Delete => None,
+			},
+			Err(err) => Some(Err(Error::ErrorReply {
+				selector: selector.clone(),
+				reason: String::from_utf8_lossy(&err.payload().to_bytes()).into_owned(),
+			}
+			.into())),
+		};
+		future::ready(reply)
+	}))
+}
 // endregion:	--- Querier
 
 #[cfg(test)]
@@ -9,19 +9,31 @@ extern crate alloc;
 extern crate std;
 
 // region:		--- modules
+#[cfg(feature = "unstable")]
+use alloc::format;
 use alloc::sync::Arc;
-use alloc::{boxed::Box, string::String};
-use core::fmt::Debug;
+use alloc::{
+	boxed::Box,
+	string::{String, ToString},
+};
+use core::{
+	fmt::Debug,
+	sync::atomic::{AtomicBool, Ordering},
+	time::Duration,
+};
 use dimas_core::{
 	Result,
-	enums::{OperationState, TaskSignal},
-	message_types::QueryMsg,
-	traits::{Capability, Context},
+	enums::{CapabilityKind, OperationState, TaskSignal},
+	message_types::{CapabilityDescription, QueryMsg},
+	policies::ErrorHandler,
+	trace_context::TraceContext,
+	traits::{Capability, Context, DrainFuture},
+	utils::{drain_task, supervise},
 };
 use futures::future::BoxFuture;
 #[cfg(feature = "std")]
 use tokio::{sync::Mutex, task::JoinHandle};
-use tracing::{Level, error, info, instrument, warn};
+use tracing::{Level, error, instrument, warn};
 use zenoh::Session;
 #[cfg(feature = "unstable")]
 use zenoh::sample::Locality;
@@ -43,11 +55,17 @@ where
 {
 	/// the zenoh session this queryable belongs to
 	session: Arc<Session>,
+	/// the id of the zenoh session
+	session_id: String,
 	selector: String,
 	/// Context for the Subscriber
 	context: Context<P>,
 	activation_state: OperationState,
+	/// A paused queryable is not started
+	paused: AtomicBool,
 	callback: ArcGetCallback<P>,
+	/// Handling of the errors returned by the callback
+	errors: ErrorHandler<P>,
 	completeness: bool,
 	#[cfg(feature = "unstable")]
 	allowed_origin: Locality,
@@ -74,6 +92,30 @@ where
 	fn selector(&self) -> &str {
 		&self.selector
 	}
+
+	fn kind(&self) -> CapabilityKind {
+		CapabilityKind::Queryable
+	}
+
+	fn describe(&self) -> CapabilityDescription {
+		let running = self.handle.lock().is_ok_and(|handle| {
+			handle
+				.as_ref()
+				.is_some_and(|handle| !handle.is_finished())
+		});
+		let description = CapabilityDescription::new(
+			CapabilityKind::Queryable,
+			self.selector.clone(),
+			Some(self.session_id.clone()),
+			self.activation_state.clone(),
+			running,
+			self.is_paused(),
+		)
+		.with_qos("completeness", self.completeness.to_string());
+		#[cfg(feature = "unstable")]
+		let description = description.with_qos("allowed_origin", format!("{:?}", self.allowed_origin));
+		description
+	}
 }
 
 impl<P> Capability for Queryable<P>
@@ -81,13 +123,36 @@ where
 	P: Send + Sync + 'static,
 {
 	fn manage_operation_state(&self, state: &OperationState) -> Result<()> {
-		if state >= &self.activation_state {
+		if state >= &self.activation_state && !self.is_paused() {
 			self.start()
-		} else if state < &self.activation_state {
-			self.stop()
 		} else {
-			Ok(())
+			self.stop()
+		}
+	}
+
+	fn set_paused(&self, paused: bool) -> Result<()> {
+		self.paused.store(paused, Ordering::Release);
+		Ok(())
+	}
+
+	fn is_paused(&self) -> bool {
+		self.paused.load(Ordering::Acquire)
+	}
+
+	fn drain(&self, state: &OperationState, timeout: Duration) -> DrainFuture {
+		if state >= &self.activation_state {
+			return Box::pin(core::future::ready(false));
 		}
+		let Some(handle) = self
+			.handle
+			.lock()
+			.map_or(None, |mut handle| handle.take())
+		else {
+			return Box::pin(core::future::ready(false));
+		};
+		let callback = self.callback.clone();
+		// holding the callback lock guarantees that no request is in-flight
+		Box::pin(drain_task(handle, callback.lock_owned(), timeout))
 	}
 }
 
@@ -96,22 +161,28 @@ where
 	P: Send + Sync + 'static,
 {
 	/// Constructor for a [`Queryable`]
+	#[allow(clippy::too_many_arguments)]
 	#[must_use]
 	pub fn new(
 		session: Arc<Session>,
+		session_id: String,
 		selector: String,
 		context: Context<P>,
 		activation_state: OperationState,
+		errors: ErrorHandler<P>,
 		request_callback: ArcGetCallback<P>,
 		completeness: bool,
 		#[cfg(feature = "unstable")] allowed_origin: Locality,
 	) -> Self {
 		Self {
 			session,
+			session_id,
 			selector,
 			context,
 			activation_state,
+			paused: AtomicBool::new(false),
 			callback: request_callback,
+			errors,
 			completeness,
 			#[cfg(feature = "unstable")]
 			allowed_origin,
@@ -130,40 +201,32 @@ where
 		let allowed_origin = self.allowed_origin;
 		let selector = self.selector.clone();
 		let cb = self.callback.clone();
-		let ctx1 = self.context.clone();
-		let ctx2 = self.context.clone();
+		let errors = self.errors.clone();
+		let signal = TaskSignal::RestartQueryable(self.selector.clone());
+		let sender = self.context.sender().clone();
+		let ctx = self.context.clone();
 		let session = self.session.clone();
 
 		self.handle.lock().map_or_else(
 			|_| todo!(),
 			|mut handle| {
-				handle.replace(tokio::task::spawn(async move {
-					let key = selector.clone();
-					std::panic::set_hook(Box::new(move |reason| {
-						error!("queryable panic: {}", reason);
-						if let Err(reason) = ctx1
-							.sender()
-							.blocking_send(TaskSignal::RestartQueryable(key.clone()))
-						{
-							error!("could not restart queryable: {}", reason);
-						} else {
-							info!("restarting queryable!");
-						}
-					}));
+				let task = async move {
 					if let Err(error) = run_queryable(
 						session,
 						selector,
 						cb,
+						errors,
 						completeness,
 						#[cfg(feature = "unstable")]
 						allowed_origin,
-						ctx2,
+						ctx,
 					)
 					.await
 					{
 						error!("queryable failed with {error}");
 					}
-				}));
+				};
+				handle.replace(tokio::task::spawn(supervise(task, sender, signal)));
 				Ok(())
 			},
 		)
@@ -175,7 +238,9 @@ where
 		self.handle.lock().map_or_else(
 			|_| todo!(),
 			|mut handle| {
-				handle.take();
+				if let Some(handle) = handle.take() {
+					handle.abort();
+				}
 				Ok(())
 			},
 		)
@@ -187,6 +252,7 @@ async fn run_queryable<P>(
 	session: Arc<Session>,
 	selector: String,
 	callback: ArcGetCallback<P>,
+	errors: ErrorHandler<P>,
 	completeness: bool,
 	#[cfg(feature = "unstable")] allowed_origin: Locality,
 	ctx: Context<P>,
@@ -205,12 +271,14 @@ where
 	loop {
 		let query = queryable.recv_async().await?;
 		let request = QueryMsg(query);
+		let trace = TraceContext::continue_or_new(&request.headers());
 
-		let ctx = ctx.clone();
 		let mut lock = callback.lock().await;
-		if let Err(error) = lock(ctx, request).await {
-			error!("queryable callback failed with {error}");
-		}
+		let result = trace
+			.scope(&selector, lock(ctx.clone(), request))
+			.await;
+		drop(lock);
+		errors.handle(&ctx, &selector, result);
 	}
 }
 // endregion:	--- Queryable
@@ -0,0 +1,250 @@
+// Copyright © 2025 Stephan Kunz
+
+//! Module `retry` repeats unanswered queries according to a [`RetryPolicy`].
+
+#[doc(hidden)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// region:		--- modules
+use crate::{error::Error, zenoh::querier::replies};
+use alloc::{boxed::Box, string::String};
+use core::time::Duration;
+use dimas_core::{
+	Result,
+	message_types::{QueryableMsg, Replies},
+	policies::RetryPolicy,
+};
+use futures::StreamExt;
+#[cfg(feature = "std")]
+use std::time::Instant;
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tracing::warn;
+use zenoh::{handlers::FifoChannelHandler, query::Reply};
+// endregion:	--- modules
+
+// region:		--- types
+/// Type definition for the error of an attempt
+type AttemptError = Box<dyn core::error::Error + Send + Sync + 'static>;
+// endregion:	--- types
+
+// region:		--- Attempts
+/// Counts the attempts of a query according to a [`RetryPolicy`]
+#[derive(Debug)]
+struct Attempts {
+	policy: RetryPolicy,
+	timeout: Duration,
+	started: Instant,
+	count: u32,
+}
+
+impl Attempts {
+	/// Start counting the attempts of a query, each attempt lasting at most `timeout`
+	fn new(policy: RetryPolicy, timeout: Duration) -> Self {
+		Self {
+			policy,
+			timeout,
+			started: Instant::now(),
+			count: 0,
+		}
+	}
+
+	/// Start the next attempt and get its timeout, an attempt must not last beyond the deadline
+	fn next(&mut self) -> Duration {
+		self.count += 1;
+		self.policy
+			.deadline
+			.map_or(self.timeout, |deadline| {
+				deadline
+					.saturating_sub(self.started.elapsed())
+					.min(self.timeout)
+			})
+	}
+
+	/// The delay before the next attempt, `None` if no further attempt is allowed
+	fn delay(&self) -> Option<Duration> {
+		self.policy.delay(self.count).filter(|delay| {
+			self.policy
+				.deadline
+				.is_none_or(|deadline| self.started.elapsed() + *delay < deadline)
+		})
+	}
+
+	/// The error after the last attempt for `selector` failed with `source`
+	fn exhausted(&self, selector: &str, source: AttemptError) -> Error {
+		Error::RetriesExhausted {
+			selector: selector.into(),
+			attempts: self.count,
+			source,
+		}
+	}
+}
+// endregion:	--- Attempts
+
+// region:		--- functions
+/// Send a query for `selector` with `query` and hand its replies to `receive`,
+/// which returns `false` if there was no reply.
+/// An unanswered query is repeated according to `policy`, each attempt lasting at most `timeout`.
+///
+/// The current thread is blocked until the query is answered or the attempts are exhausted.
+/// Within a multi threaded runtime the other tasks of the current worker are moved
+/// to another thread meanwhile.
+/// # Errors
+/// if `policy` allows retries within a current thread runtime, which would be blocked completely,
+/// or all attempts failed
+pub fn get_blocking<Q, R>(
+	selector: &str,
+	policy: RetryPolicy,
+	timeout: Duration,
+	mut query: Q,
+	mut receive: R,
+) -> Result<()>
+where
+	Q: FnMut(Duration) -> Result<FifoChannelHandler<Reply>>,
+	R: FnMut(&FifoChannelHandler<Reply>) -> Result<bool>,
+{
+	let mut run = || {
+		let mut attempts = Attempts::new(policy, timeout);
+		loop {
+			let error = match query(attempts.next()) {
+				Ok(replies) => {
+					if receive(&replies)? {
+						return Ok(());
+					}
+					Error::AccessingQueryable {
+						selector: selector.into(),
+					}
+					.into()
+				}
+				Err(error) => error,
+			};
+			let Some(delay) = attempts.delay() else {
+				return Err(attempts.exhausted(selector, error).into());
+			};
+			warn!(
+				"attempt {} of query for '{selector}' failed with {error}",
+				attempts.count
+			);
+			std::thread::sleep(delay);
+		}
+	};
+	match Handle::try_current() {
+		Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+			tokio::task::block_in_place(run)
+		}
+		// waiting between the attempts would stall all tasks of the runtime
+		Ok(_) if policy.max_attempts > 1 => Err(Error::BlockingRetry {
+			selector: selector.into(),
+		}
+		.into()),
+		_ => run(),
+	}
+}
+
+/// Send a query for `selector` with `query`, its replies are received via the returned [`Replies`].
+/// An unanswered query is repeated according to `policy`, each attempt lasting at most `timeout`.
+///
+/// The first attempt is sent immediately, further ones while the [`Replies`] are polled.
+/// If all attempts failed, the [`Replies`] end with an [`Error::RetriesExhausted`].
+/// # Errors
+/// if the first query could not be created
+pub fn get_async<Q>(
+	selector: String,
+	policy: RetryPolicy,
+	timeout: Duration,
+	mut query: Q,
+) -> Result<Replies>
+where
+	Q: FnMut(Duration) -> Result<FifoChannelHandler<Reply>> + Send + 'static,
+{
+	let mut attempts = Attempts::new(policy, timeout);
+	let first = replies(selector.clone(), query(attempts.next())?);
+	let retry = Retry {
+		selector,
+		attempts,
+		query,
+		replies: Some(first),
+		answered: false,
+	};
+	Ok(Replies::new(futures::stream::unfold(
+		retry,
+		Retry::next_reply,
+	)))
+}
+
+// endregion:	--- functions
+
+// region:		--- Retry
+/// The state of an asynchronous query with retries
+struct Retry<Q> {
+	selector: String,
+	attempts: Attempts,
+	query: Q,
+	/// The replies of the current attempt, `None` when finished
+	replies: Option<Replies>,
+	/// Whether there was a reply to the current attempt
+	answered: bool,
+}
+
+impl<Q> Retry<Q>
+where
+	Q: FnMut(Duration) -> Result<FifoChannelHandler<Reply>> + Send + 'static,
+{
+	/// Receive the next reply, repeating an unanswered query
+	async fn next_reply(mut self) -> Option<(Result<QueryableMsg>, Self)> {
+		loop {
+			if let Some(reply) = self.replies.as_mut()?.next().await {
+				self.answered = true;
+				return Some((reply, self));
+			}
+			if self.answered {
+				return None;
+			}
+			let mut error: AttemptError = Error::AccessingQueryable {
+				selector: self.selector.clone(),
+			}
+			.into();
+			self.replies = loop {
+				let Some(delay) = self.attempts.delay() else {
+					self.replies = None;
+					let error = self.attempts.exhausted(&self.selector, error);
+					return Some((Err(error.into()), self));
+				};
+				warn!(
+					"attempt {} of query for '{}' failed with {error}",
+					self.attempts.count, self.selector
+				);
+				tokio::time::sleep(delay).await;
+				match (self.query)(self.attempts.next()) {
+					Ok(query) => break Some(replies(self.selector.clone(), query)),
+					Err(next) => error = next,
+				}
+			};
+		}
+	}
+}
+// endregion:	--- Retry
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn blocking_retry_rejected() {
+		let query = |_| -> Result<FifoChannelHandler<Reply>> { panic!("no query expected") };
+		let error = get_blocking(
+			"test/retry",
+			RetryPolicy::new(),
+			Duration::from_millis(100),
+			query,
+			|_| Ok(true),
+		)
+		.expect_err("snh");
+		assert!(matches!(
+			error.downcast_ref::<Error>(),
+			Some(Error::BlockingRetry { .. })
+		));
+	}
+}
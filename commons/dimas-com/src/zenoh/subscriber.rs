@@ -11,18 +11,27 @@ extern crate std;
 
 // region:		--- modules
 use crate::error::Error;
+#[cfg(feature = "unstable")]
+use alloc::format;
 use alloc::sync::Arc;
-use alloc::{boxed::Box, string::String, vec::Vec};
+use alloc::{boxed::Box, string::String};
+use core::{
+	sync::atomic::{AtomicBool, Ordering},
+	time::Duration,
+};
 use dimas_core::{
 	Result,
-	enums::{OperationState, TaskSignal},
-	message_types::Message,
-	traits::{Capability, Context},
+	enums::{CapabilityKind, OperationState, TaskSignal},
+	message_types::{CapabilityDescription, Headers, Message},
+	policies::ErrorHandler,
+	trace_context::TraceContext,
+	traits::{Capability, Context, DrainFuture},
+	utils::{drain_task, supervise},
 };
 use futures::future::BoxFuture;
 #[cfg(feature = "std")]
 use tokio::{sync::Mutex, task::JoinHandle};
-use tracing::{Level, error, info, instrument, warn};
+use tracing::{Level, error, instrument, warn};
 use zenoh::Session;
 #[cfg(feature = "unstable")]
 use zenoh::sample::Locality;
@@ -40,6 +49,10 @@ pub type DeleteCallback<P> =
 	Box<dyn FnMut(Context<P>) -> BoxFuture<'static, Result<()>> + Send + Sync>;
 /// Type definition for a subscribers atomic reference counted `delete` callback
 pub type ArcDeleteCallback<P> = Arc<Mutex<DeleteCallback<P>>>;
+/// Type definition for the callback of a typed subscriber informed about a message,
+/// which could not be decoded. The arguments are the message and the decoding error.
+pub type DecodeErrorCallback<P> =
+	Box<dyn FnMut(Context<P>, &Message, &(dyn core::error::Error + Send + Sync)) + Send + Sync>;
 // endregion: 	--- types
 
 // region:		--- Subscriber
@@ -50,16 +63,22 @@ where
 {
 	/// the zenoh session this subscriber belongs to
 	session: Arc<Session>,
+	/// the id of the zenoh session
+	session_id: String,
 	/// The subscribers key expression
 	selector: String,
 	/// Context for the Subscriber
 	context: Context<P>,
 	/// [`OperationState`] on which this subscriber is started
 	activation_state: OperationState,
+	/// A paused subscriber is not started
+	paused: AtomicBool,
 	#[cfg(feature = "unstable")]
 	allowed_origin: Locality,
 	put_callback: ArcPutCallback<P>,
 	delete_callback: Option<ArcDeleteCallback<P>>,
+	/// Handling of the errors returned by the callbacks
+	errors: ErrorHandler<P>,
 	handle: std::sync::Mutex<Option<JoinHandle<()>>>,
 }
 
@@ -82,6 +101,29 @@ where
 	fn selector(&self) -> &str {
 		&self.selector
 	}
+
+	fn kind(&self) -> CapabilityKind {
+		CapabilityKind::Subscriber
+	}
+
+	fn describe(&self) -> CapabilityDescription {
+		let running = self.handle.lock().is_ok_and(|handle| {
+			handle
+				.as_ref()
+				.is_some_and(|handle| !handle.is_finished())
+		});
+		let description = CapabilityDescription::new(
+			CapabilityKind::Subscriber,
+			self.selector.clone(),
+			Some(self.session_id.clone()),
+			self.activation_state.clone(),
+			running,
+			self.is_paused(),
+		);
+		#[cfg(feature = "unstable")]
+		let description = description.with_qos("allowed_origin", format!("{:?}", self.allowed_origin));
+		description
+	}
 }
 
 impl<P> Capability for Subscriber<P>
@@ -89,14 +131,48 @@ where
 	P: Send + Sync + 'static,
 {
 	fn manage_operation_state(&self, state: &OperationState) -> Result<()> {
-		if state >= &self.activation_state {
+		if state >= &self.activation_state && !self.is_paused() {
 			self.start()
-		} else if state < &self.activation_state {
-			self.stop()
 		} else {
-			Ok(())
+			self.stop()
 		}
 	}
+
+	fn set_paused(&self, paused: bool) -> Result<()> {
+		self.paused.store(paused, Ordering::Release);
+		Ok(())
+	}
+
+	fn is_paused(&self) -> bool {
+		self.paused.load(Ordering::Acquire)
+	}
+
+	fn drain(&self, state: &OperationState, timeout: Duration) -> DrainFuture {
+		if state >= &self.activation_state {
+			return Box::pin(core::future::ready(false));
+		}
+		let Some(handle) = self
+			.handle
+			.lock()
+			.map_or(None, |mut handle| handle.take())
+		else {
+			return Box::pin(core::future::ready(false));
+		};
+		let p_cb = self.put_callback.clone();
+		let d_cb = self.delete_callback.clone();
+		Box::pin(async move {
+			// holding both callback locks guarantees that no callback is in-flight
+			let idle = async {
+				let put = p_cb.lock_owned().await;
+				let delete = match d_cb {
+					Some(cb) => Some(cb.lock_owned().await),
+					None => None,
+				};
+				(put, delete)
+			};
+			drain_task(handle, idle, timeout).await
+		})
+	}
 }
 
 impl<P> Subscriber<P>
@@ -104,25 +180,31 @@ where
 	P: Send + Sync + 'static,
 {
 	/// Constructor for a [`Subscriber`].
+	#[allow(clippy::too_many_arguments)]
 	#[must_use]
 	pub fn new(
 		session: Arc<Session>,
+		session_id: String,
 		selector: String,
 		context: Context<P>,
 		activation_state: OperationState,
+		errors: ErrorHandler<P>,
 		#[cfg(feature = "unstable")] allowed_origin: Locality,
 		put_callback: ArcPutCallback<P>,
 		delete_callback: Option<ArcDeleteCallback<P>>,
 	) -> Self {
 		Self {
 			session,
+			session_id,
 			selector,
 			context,
 			activation_state,
+			paused: AtomicBool::new(false),
 			#[cfg(feature = "unstable")]
 			allowed_origin,
 			put_callback,
 			delete_callback,
+			errors,
 			handle: std::sync::Mutex::new(None),
 		}
 	}
@@ -135,8 +217,10 @@ where
 		let selector = self.selector.clone();
 		let p_cb = self.put_callback.clone();
 		let d_cb = self.delete_callback.clone();
-		let ctx1 = self.context.clone();
-		let ctx2 = self.context.clone();
+		let errors = self.errors.clone();
+		let signal = TaskSignal::RestartSubscriber(self.selector.clone());
+		let sender = self.context.sender().clone();
+		let ctx = self.context.clone();
 		let session = self.session.clone();
 		#[cfg(feature = "unstable")]
 		let allowed_origin = self.allowed_origin;
@@ -144,19 +228,7 @@ where
 		self.handle.lock().map_or_else(
 			|_| todo!(),
 			|mut handle| {
-				handle.replace(tokio::task::spawn(async move {
-					let key = selector.clone();
-					std::panic::set_hook(Box::new(move |reason| {
-						error!("subscriber panic: {}", reason);
-						if let Err(reason) = ctx1
-							.sender()
-							.blocking_send(TaskSignal::RestartSubscriber(key.clone()))
-						{
-							error!("could not restart subscriber: {}", reason);
-						} else {
-							info!("restarting subscriber!");
-						}
-					}));
+				let task = async move {
 					if let Err(error) = run_subscriber(
 						session,
 						selector,
@@ -164,13 +236,15 @@ where
 						allowed_origin,
 						p_cb,
 						d_cb,
-						ctx2.clone(),
+						errors,
+						ctx,
 					)
 					.await
 					{
 						error!("spawning subscriber failed with {error}");
 					}
-				}));
+				};
+				handle.replace(tokio::task::spawn(supervise(task, sender, signal)));
 				Ok(())
 			},
 		)
@@ -182,7 +256,9 @@ where
 		self.handle.lock().map_or_else(
 			|_| todo!(),
 			|mut handle| {
-				handle.take();
+				if let Some(handle) = handle.take() {
+					handle.abort();
+				}
 				Ok(())
 			},
 		)
@@ -196,6 +272,7 @@ async fn run_subscriber<P>(
 	#[cfg(feature = "unstable")] allowed_origin: Locality,
 	p_cb: ArcPutCallback<P>,
 	d_cb: Option<ArcDeleteCallback<P>>,
+	errors: ErrorHandler<P>,
 	ctx: Context<P>,
 ) -> Result<()>
 where
@@ -216,21 +293,23 @@ where
 
 		match sample.kind() {
 			SampleKind::Put => {
-				let content: Vec<u8> = sample.payload().to_bytes().into_owned();
-				let msg = Message::new(content);
+				let msg = Message::from(&sample);
+				let trace = TraceContext::continue_or_new(msg.headers());
 				let mut lock = p_cb.lock().await;
-				let ctx = ctx.clone();
-				if let Err(error) = lock(ctx, msg).await {
-					error!("subscriber put callback failed with {error}");
-				}
+				let result = trace
+					.scope(&selector, lock(ctx.clone(), msg))
+					.await;
+				drop(lock);
+				errors.handle(&ctx, &selector, result);
 			}
 			SampleKind::Delete => {
 				if let Some(cb) = d_cb.clone() {
-					let ctx = ctx.clone();
+					let headers = Headers::from_zbytes(sample.attachment());
+					let trace = TraceContext::continue_or_new(&headers);
 					let mut lock = cb.lock().await;
-					if let Err(error) = lock(ctx).await {
-						error!("subscriber delete callback failed with {error}");
-					}
+					let result = trace.scope(&selector, lock(ctx.clone())).await;
+					drop(lock);
+					errors.handle(&ctx, &selector, result);
 				}
 			}
 		}
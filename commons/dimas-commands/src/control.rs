@@ -9,7 +9,7 @@ extern crate alloc;
 extern crate std;
 
 // region:		--- modules
-use crate::messages::AboutEntity;
+use crate::messages::{AboutEntity, ParametersEntity, StateEntity};
 use alloc::{
 	string::{String, ToString},
 	vec::Vec,
@@ -17,7 +17,7 @@ use alloc::{
 use dimas_com::{traits::CommunicatorImplementationMethods, zenoh::Communicator};
 use dimas_core::{
 	Result,
-	enums::{OperationState, Signal},
+	enums::{OperationState, ParameterRequest, Signal},
 	message_types::Message,
 	utils::selector_from,
 };
@@ -26,15 +26,17 @@ use std::collections::HashMap;
 // endregion:	--- modules
 
 // region:		--- set_state
-/// Set the [`OperationState`] of `DiMAS` entities
+/// Set the [`OperationState`] of `DiMAS` entities.
+///
+/// Entities may reject the change, the reason is available in the returned [`StateEntity`]s.
 /// # Errors
 #[cfg(feature = "std")]
 pub fn set_state(
 	com: &Communicator,
 	base_selector: &String,
 	state: Option<OperationState>,
-) -> Result<Vec<AboutEntity>> {
-	let mut map: HashMap<String, AboutEntity> = HashMap::new();
+) -> Result<Vec<StateEntity>> {
+	let mut map: HashMap<String, StateEntity> = HashMap::new();
 
 	let selector = selector_from("signal", Some(base_selector));
 	let message = Message::encode(&Signal::State { state });
@@ -43,19 +45,53 @@ pub fn set_state(
 		&selector,
 		Some(message),
 		Some(&mut |response| -> Result<()> {
-			let response: AboutEntity = response.decode()?;
+			let response: StateEntity = response.decode()?;
 			map.entry(response.zid().to_string())
 				.or_insert(response);
 			Ok(())
 		}),
 	)?;
 
-	let result: Vec<AboutEntity> = map.values().cloned().collect();
+	let result: Vec<StateEntity> = map.values().cloned().collect();
 
 	Ok(result)
 }
 // endregion:	--- set_state
 
+// region:		--- recover
+/// Recover `DiMAS` entities from [`OperationState::Error`]
+/// by tearing them down to the given `state`
+///
+/// Entities may reject the recovery, the reason is available in the returned [`StateEntity`]s.
+/// # Errors
+#[cfg(feature = "std")]
+pub fn recover(
+	com: &Communicator,
+	base_selector: &String,
+	state: OperationState,
+) -> Result<Vec<StateEntity>> {
+	let mut map: HashMap<String, StateEntity> = HashMap::new();
+
+	let selector = selector_from("signal", Some(base_selector));
+	let message = Message::encode(&Signal::Recover { state });
+	// recover entities matching the selector
+	com.get(
+		&selector,
+		Some(message),
+		Some(&mut |response| -> Result<()> {
+			let response: StateEntity = response.decode()?;
+			map.entry(response.zid().to_string())
+				.or_insert(response);
+			Ok(())
+		}),
+	)?;
+
+	let result: Vec<StateEntity> = map.values().cloned().collect();
+
+	Ok(result)
+}
+// endregion:	--- recover
+
 // region:		--- shutdown
 /// Shutdown of `DiMAS` entities
 /// # Errors
@@ -82,3 +118,74 @@ pub fn shutdown(com: &Communicator, base_selector: &String) -> Result<Vec<AboutE
 	Ok(result)
 }
 // endregion:	--- shutdown
+
+// region:		--- parameters
+/// List the parameters of `DiMAS` entities
+/// # Errors
+#[cfg(feature = "std")]
+pub fn list_parameters(
+	com: &Communicator,
+	base_selector: &String,
+) -> Result<Vec<ParametersEntity>> {
+	parameter_request(com, base_selector, &ParameterRequest::List)
+}
+
+/// Get a single parameter of `DiMAS` entities
+/// # Errors
+#[cfg(feature = "std")]
+pub fn get_parameter(
+	com: &Communicator,
+	base_selector: &String,
+	name: &str,
+) -> Result<Vec<ParametersEntity>> {
+	let request = ParameterRequest::Get {
+		name: name.to_string(),
+	};
+	parameter_request(com, base_selector, &request)
+}
+
+/// Set a single parameter of `DiMAS` entities to a JSON encoded `value`.
+///
+/// Entities may reject the change, the reason is available in the returned [`ParametersEntity`]s.
+/// # Errors
+#[cfg(feature = "std")]
+pub fn set_parameter(
+	com: &Communicator,
+	base_selector: &String,
+	name: &str,
+	value: &str,
+) -> Result<Vec<ParametersEntity>> {
+	let request = ParameterRequest::Set {
+		name: name.to_string(),
+		value: value.to_string(),
+	};
+	parameter_request(com, base_selector, &request)
+}
+
+#[cfg(feature = "std")]
+fn parameter_request(
+	com: &Communicator,
+	base_selector: &String,
+	request: &ParameterRequest,
+) -> Result<Vec<ParametersEntity>> {
+	let mut map: HashMap<String, ParametersEntity> = HashMap::new();
+
+	let selector = selector_from("params", Some(base_selector));
+	let message = Message::encode(request);
+	// send request to entities matching the selector
+	com.get(
+		&selector,
+		Some(message),
+		Some(&mut |response| -> Result<()> {
+			let response: ParametersEntity = response.decode()?;
+			map.entry(response.zid().to_string())
+				.or_insert(response);
+			Ok(())
+		}),
+	)?;
+
+	let result: Vec<ParametersEntity> = map.values().cloned().collect();
+
+	Ok(result)
+}
+// endregion:	--- parameters
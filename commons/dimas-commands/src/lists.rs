@@ -9,17 +9,18 @@ extern crate alloc;
 extern crate std;
 
 // region:		--- modules
-use crate::messages::{AboutEntity, PingEntity, ScoutingEntity};
+use crate::messages::{AboutEntity, CapabilitiesEntity, PingEntity, ScoutingEntity};
 use alloc::vec::Vec;
 use alloc::{
 	borrow::ToOwned,
 	string::{String, ToString},
 };
-use chrono::Local;
 use core::time::Duration;
 use dimas_com::{traits::CommunicatorImplementationMethods, zenoh::Communicator};
 use dimas_config::Config;
-use dimas_core::{Result, enums::Signal, message_types::Message, utils::selector_from};
+use dimas_core::{
+	Result, enums::Signal, message_types::Message, traits::Clock, utils::selector_from,
+};
 #[cfg(feature = "std")]
 use std::collections::HashMap;
 use zenoh::{
@@ -55,30 +56,56 @@ pub fn about_list(com: &Communicator, base_selector: &String) -> Result<Vec<Abou
 }
 // endregion:	--- about_list
 
+// region:		--- capabilities_list
+/// Fetch the registered capabilities from all reachable `DiMAS` entities
+/// # Errors
+#[cfg(feature = "std")]
+pub fn capabilities_list(
+	com: &Communicator,
+	base_selector: &String,
+) -> Result<Vec<CapabilitiesEntity>> {
+	let mut map: HashMap<String, CapabilitiesEntity> = HashMap::new();
+
+	let selector = selector_from("signal", Some(base_selector));
+	let message = Message::encode(&Signal::Capabilities);
+	// fetch capabilities from entities matching the selector
+	com.get(
+		&selector,
+		Some(message),
+		Some(&mut |response| -> Result<()> {
+			let response: CapabilitiesEntity = response.decode()?;
+			map.entry(response.zid().to_string())
+				.or_insert(response);
+			Ok(())
+		}),
+	)?;
+
+	let result: Vec<CapabilitiesEntity> = map.values().cloned().collect();
+
+	Ok(result)
+}
+// endregion:	--- capabilities_list
+
 // region:		--- ping_list
-/// Ping all reachable `DiMAS` entities
+/// Ping all reachable `DiMAS` entities, measuring the roundtrip with the given [`Clock`]
 /// # Errors
 #[cfg(feature = "std")]
-pub fn ping_list(com: &Communicator, base_selector: &String) -> Result<Vec<(PingEntity, i64)>> {
+pub fn ping_list(
+	com: &Communicator,
+	base_selector: &String,
+	clock: &dyn Clock,
+) -> Result<Vec<(PingEntity, i64)>> {
 	let mut map: HashMap<String, (PingEntity, i64)> = HashMap::new();
 
 	let selector = selector_from("signal", Some(base_selector));
-	let sent = Local::now()
-		.naive_utc()
-		.and_utc()
-		.timestamp_nanos_opt()
-		.unwrap_or(0);
+	let sent = clock.timestamp();
 	let message = Message::encode(&Signal::Ping { sent });
 	// set state for entities matching the selector
 	com.get(
 		&selector,
 		Some(message),
 		Some(&mut |response| -> Result<()> {
-			let received = Local::now()
-				.naive_utc()
-				.and_utc()
-				.timestamp_nanos_opt()
-				.unwrap_or(0);
+			let received = clock.timestamp();
 
 			let response: PingEntity = response.decode()?;
 			let roundtrip = received - sent;
@@ -22,6 +22,7 @@ pub struct AboutEntity {
 	kind: String,
 	zid: String,
 	state: OperationState,
+	error: Option<String>,
 }
 
 impl Display for AboutEntity {
@@ -30,19 +31,30 @@ impl Display for AboutEntity {
 			f,
 			"name: {} kind: {} state: {} zid: {}",
 			&self.name, &self.kind, &self.state, &self.zid
-		)
+		)?;
+		if let Some(cause) = &self.error {
+			write!(f, " last error: {cause}")?;
+		}
+		Ok(())
 	}
 }
 
 impl AboutEntity {
 	/// Constructor
 	#[must_use]
-	pub const fn new(name: String, kind: String, zid: String, state: OperationState) -> Self {
+	pub const fn new(
+		name: String,
+		kind: String,
+		zid: String,
+		state: OperationState,
+		error: Option<String>,
+	) -> Self {
 		Self {
 			name,
 			kind,
 			zid,
 			state,
+			error,
 		}
 	}
 
@@ -69,5 +81,11 @@ impl AboutEntity {
 	pub const fn state(&self) -> &OperationState {
 		&self.state
 	}
+
+	/// Get the cause of the last error
+	#[must_use]
+	pub fn error(&self) -> Option<&str> {
+		self.error.as_deref()
+	}
 }
 // endregion:	--- AboutEntity
@@ -0,0 +1,65 @@
+// Copyright © 2024 Stephan Kunz
+#![allow(clippy::non_canonical_partial_ord_impl)]
+
+//! The registered capabilities of an agent.
+
+#[doc(hidden)]
+extern crate alloc;
+
+// region:		--- modules
+use alloc::{string::String, vec::Vec};
+use bitcode::{Decode, Encode};
+use core::fmt::Display;
+use dimas_core::message_types::CapabilityDescription;
+// endregion:	--- modules
+
+// region:		--- CapabilitiesEntity
+/// A `DiMAS` entity
+#[repr(C)]
+#[derive(Encode, Clone, Decode)]
+pub struct CapabilitiesEntity {
+	name: String,
+	zid: String,
+	capabilities: Vec<CapabilityDescription>,
+}
+
+impl Display for CapabilitiesEntity {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "name: {} zid: {}", &self.name, &self.zid)?;
+		for capability in &self.capabilities {
+			write!(f, "\n  {capability}")?;
+		}
+		Ok(())
+	}
+}
+
+impl CapabilitiesEntity {
+	/// Constructor
+	#[must_use]
+	pub const fn new(name: String, zid: String, capabilities: Vec<CapabilityDescription>) -> Self {
+		Self {
+			name,
+			zid,
+			capabilities,
+		}
+	}
+
+	/// Get the Name
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Get the Zenoh ID
+	#[must_use]
+	pub fn zid(&self) -> &str {
+		&self.zid
+	}
+
+	/// Get the descriptions of the capabilities
+	#[must_use]
+	pub fn capabilities(&self) -> &[CapabilityDescription] {
+		&self.capabilities
+	}
+}
+// endregion:	--- CapabilitiesEntity
@@ -4,10 +4,16 @@
 //! Module `Messages` provides the different messages used with DiMAS.
 
 mod about_entity;
+mod capabilities_entity;
+mod parameter_entity;
 mod ping_entity;
 mod scouting_entity;
+mod state_entity;
 
 // flatten
 pub use about_entity::*;
+pub use capabilities_entity::*;
+pub use parameter_entity::*;
 pub use ping_entity::*;
 pub use scouting_entity::*;
+pub use state_entity::*;
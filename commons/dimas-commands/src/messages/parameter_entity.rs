@@ -0,0 +1,131 @@
+// Copyright © 2024 Stephan Kunz
+#![allow(clippy::non_canonical_partial_ord_impl)]
+
+//! The parameters of an agent.
+
+#[doc(hidden)]
+extern crate alloc;
+
+// region:		--- modules
+use alloc::{string::String, vec::Vec};
+use bitcode::{Decode, Encode};
+use core::fmt::Display;
+// endregion:	--- modules
+
+// region:		--- ParameterEntity
+/// A single parameter of a `DiMAS` entity
+#[repr(C)]
+#[derive(Encode, Clone, Decode)]
+pub struct ParameterEntity {
+	name: String,
+	type_description: String,
+	value: String,
+}
+
+impl Display for ParameterEntity {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(
+			f,
+			"{}: {} = {}",
+			&self.name, &self.type_description, &self.value
+		)
+	}
+}
+
+impl ParameterEntity {
+	/// Constructor
+	#[must_use]
+	pub const fn new(name: String, type_description: String, value: String) -> Self {
+		Self {
+			name,
+			type_description,
+			value,
+		}
+	}
+
+	/// Get the Name
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Get the description of the parameters type
+	#[must_use]
+	pub fn type_description(&self) -> &str {
+		&self.type_description
+	}
+
+	/// Get the JSON encoded value
+	#[must_use]
+	pub fn value(&self) -> &str {
+		&self.value
+	}
+}
+// endregion:	--- ParameterEntity
+
+// region:		--- ParametersEntity
+/// A `DiMAS` entity after a parameter request
+#[repr(C)]
+#[derive(Encode, Clone, Decode)]
+pub struct ParametersEntity {
+	name: String,
+	zid: String,
+	parameters: Vec<ParameterEntity>,
+	rejection: Option<String>,
+}
+
+impl Display for ParametersEntity {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "name: {} zid: {}", &self.name, &self.zid)?;
+		if let Some(reason) = &self.rejection {
+			write!(f, " rejected: {reason}")?;
+		}
+		for parameter in &self.parameters {
+			write!(f, "\n  {parameter}")?;
+		}
+		Ok(())
+	}
+}
+
+impl ParametersEntity {
+	/// Constructor
+	#[must_use]
+	pub const fn new(
+		name: String,
+		zid: String,
+		parameters: Vec<ParameterEntity>,
+		rejection: Option<String>,
+	) -> Self {
+		Self {
+			name,
+			zid,
+			parameters,
+			rejection,
+		}
+	}
+
+	/// Get the Name
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Get the Zenoh ID
+	#[must_use]
+	pub fn zid(&self) -> &str {
+		&self.zid
+	}
+
+	/// Get the parameters
+	#[must_use]
+	pub fn parameters(&self) -> &[ParameterEntity] {
+		&self.parameters
+	}
+
+	/// Get the reason, why the request was rejected
+	#[must_use]
+	pub fn rejection(&self) -> Option<&str> {
+		self.rejection.as_deref()
+	}
+}
+// endregion:	--- ParametersEntity
@@ -0,0 +1,91 @@
+// Copyright © 2024 Stephan Kunz
+#![allow(clippy::non_canonical_partial_ord_impl)]
+
+//! The result of a state change request to an agent.
+
+#[doc(hidden)]
+extern crate alloc;
+
+// region:		--- modules
+use alloc::string::String;
+use bitcode::{Decode, Encode};
+use core::fmt::Display;
+use dimas_core::enums::OperationState;
+// endregion:	--- modules
+
+// region:		--- StateEntity
+/// A `DiMAS` entity after a state change request
+#[repr(C)]
+#[derive(Encode, Clone, Decode)]
+pub struct StateEntity {
+	name: String,
+	kind: String,
+	zid: String,
+	state: OperationState,
+	rejection: Option<String>,
+}
+
+impl Display for StateEntity {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(
+			f,
+			"name: {} kind: {} state: {} zid: {}",
+			&self.name, &self.kind, &self.state, &self.zid
+		)?;
+		if let Some(reason) = &self.rejection {
+			write!(f, " rejected: {reason}")?;
+		}
+		Ok(())
+	}
+}
+
+impl StateEntity {
+	/// Constructor
+	#[must_use]
+	pub const fn new(
+		name: String,
+		kind: String,
+		zid: String,
+		state: OperationState,
+		rejection: Option<String>,
+	) -> Self {
+		Self {
+			name,
+			kind,
+			zid,
+			state,
+			rejection,
+		}
+	}
+
+	/// Get the Name
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Get the Kind
+	#[must_use]
+	pub fn kind(&self) -> &str {
+		&self.kind
+	}
+
+	/// Get the Zenoh ID
+	#[must_use]
+	pub fn zid(&self) -> &str {
+		&self.zid
+	}
+
+	/// Get the state
+	#[must_use]
+	pub const fn state(&self) -> &OperationState {
+		&self.state
+	}
+
+	/// Get the reason, why the requested state change was rejected
+	#[must_use]
+	pub fn rejection(&self) -> Option<&str> {
+		self.rejection.as_deref()
+	}
+}
+// endregion:	--- StateEntity
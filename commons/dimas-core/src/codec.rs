@@ -0,0 +1,200 @@
+// Copyright © 2024 Stephan Kunz
+
+//! Module `codec` provides the [`Codec`]s for encoding and decoding the payload of messages.
+//!
+//! A codec is selected per capability and its encoding is put on the wire,
+//! so that receivers, including tools not written in Rust, know how to decode the payload.
+//! Available are [`Bitcode`], the default, [`Json`] and [`Cbor`].
+
+#[doc(hidden)]
+extern crate alloc;
+
+// region:		--- modules
+use crate::error::{Error, Result};
+use alloc::{boxed::Box, vec::Vec};
+use serde::{Serialize, de::DeserializeOwned};
+use zenoh::bytes::Encoding;
+// endregion:	--- modules
+
+// region:		--- Codec
+/// A format for the payload of messages
+pub trait Codec {
+	/// The zenoh encoding put on the wire
+	const ENCODING: &'static str;
+
+	/// Whether a payload with the given zenoh `encoding` can be decoded with this codec.
+	/// Payloads without a specific encoding are accepted by every codec.
+	#[must_use]
+	fn accepts(encoding: &str) -> bool {
+		let encoding = Encoding::from(encoding);
+		encoding == Encoding::ZENOH_BYTES || encoding == Encoding::from(Self::ENCODING)
+	}
+}
+
+/// A [`Codec`] able to encode values of type `T`
+pub trait Encoder<T>: Codec {
+	/// Encode `value`
+	/// # Errors
+	/// if the value can not be represented in the format
+	fn encode(value: &T) -> Result<Vec<u8>>;
+}
+
+/// A [`Codec`] able to decode values of type `T`
+pub trait Decoder<T>: Codec {
+	/// Decode a value from `payload`
+	/// # Errors
+	/// if the payload is not a valid `T`
+	fn decode(payload: &[u8]) -> Result<T>;
+}
+// endregion:	--- Codec
+
+// region:		--- Bitcode
+/// The compact binary format of [`bitcode`], only readable by Rust
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bitcode;
+
+impl Codec for Bitcode {
+	const ENCODING: &'static str = "zenoh/bytes;bitcode";
+}
+
+impl<T> Encoder<T> for Bitcode
+where
+	T: bitcode::Encode,
+{
+	fn encode(value: &T) -> Result<Vec<u8>> {
+		Ok(bitcode::encode(value))
+	}
+}
+
+impl<T> Decoder<T> for Bitcode
+where
+	T: for<'a> bitcode::Decode<'a>,
+{
+	fn decode(payload: &[u8]) -> Result<T> {
+		bitcode::decode(payload).map_err(|source| {
+			Error::Decoding {
+				source: Box::new(source),
+			}
+			.into()
+		})
+	}
+}
+// endregion:	--- Bitcode
+
+// region:		--- Json
+/// The JSON format using [`serde`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json;
+
+impl Codec for Json {
+	const ENCODING: &'static str = "application/json";
+}
+
+impl<T> Encoder<T> for Json
+where
+	T: Serialize,
+{
+	fn encode(value: &T) -> Result<Vec<u8>> {
+		serde_json::to_vec(value).map_err(|source| {
+			Error::Encoding {
+				source: Box::new(source),
+			}
+			.into()
+		})
+	}
+}
+
+impl<T> Decoder<T> for Json
+where
+	T: DeserializeOwned,
+{
+	fn decode(payload: &[u8]) -> Result<T> {
+		serde_json::from_slice(payload).map_err(|source| {
+			Error::Decoding {
+				source: Box::new(source),
+			}
+			.into()
+		})
+	}
+}
+// endregion:	--- Json
+
+// region:		--- Cbor
+/// The CBOR format using [`serde`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cbor;
+
+impl Codec for Cbor {
+	const ENCODING: &'static str = "application/cbor";
+}
+
+impl<T> Encoder<T> for Cbor
+where
+	T: Serialize,
+{
+	fn encode(value: &T) -> Result<Vec<u8>> {
+		let mut payload = Vec::new();
+		ciborium::into_writer(value, &mut payload).map_err(|source| Error::Encoding {
+			source: Box::new(source),
+		})?;
+		Ok(payload)
+	}
+}
+
+impl<T> Decoder<T> for Cbor
+where
+	T: DeserializeOwned,
+{
+	fn decode(payload: &[u8]) -> Result<T> {
+		ciborium::from_reader(payload).map_err(|source| {
+			Error::Decoding {
+				source: Box::new(source),
+			}
+			.into()
+		})
+	}
+}
+// endregion:	--- Cbor
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::{string::String, vec};
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<Bitcode>();
+		is_normal::<Json>();
+		is_normal::<Cbor>();
+	}
+
+	#[test]
+	fn round_trips() {
+		let value = (42_u32, String::from("dimas"), vec![1.5_f64, -2.0]);
+
+		let payload = <Bitcode as Encoder<_>>::encode(&value).expect("snh");
+		assert_eq!(Bitcode::decode(&payload).ok(), Some(value.clone()));
+
+		let payload = <Json as Encoder<_>>::encode(&value).expect("snh");
+		assert_eq!(payload, br#"[42,"dimas",[1.5,-2.0]]"#);
+		assert_eq!(Json::decode(&payload).ok(), Some(value.clone()));
+
+		let payload = <Cbor as Encoder<_>>::encode(&value).expect("snh");
+		assert_eq!(Cbor::decode(&payload).ok(), Some(value));
+
+		assert!(<Json as Decoder<u32>>::decode(b"dimas").is_err());
+	}
+
+	#[test]
+	fn accepted_encodings() {
+		assert!(Json::accepts("application/json"));
+		assert!(Json::accepts("zenoh/bytes"));
+		assert!(!Json::accepts("application/cbor"));
+		assert!(Bitcode::accepts(Bitcode::ENCODING));
+		assert!(!Bitcode::accepts("application/json"));
+		assert!(!Cbor::accepts(Bitcode::ENCODING));
+	}
+}
@@ -13,7 +13,10 @@ use alloc::{
 	string::{String, ToString},
 };
 use bitcode::{Decode, Encode};
-use core::fmt::{Debug, Display};
+use core::{
+	fmt::{Debug, Display},
+	time::Duration,
+};
 // endregion:	--- modules
 
 // region:		--- OperationState
@@ -70,8 +73,47 @@ impl Display for OperationState {
 }
 // endregion:	--- OperationState
 
+// region:		--- CapabilityKind
+/// The kinds of capabilities an agent can have
+#[derive(Debug, Decode, Encode, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum CapabilityKind {
+	/// A liveliness subscriber
+	LivelinessSubscriber,
+	/// An observable
+	Observable,
+	/// An observer
+	Observer,
+	/// A publisher
+	Publisher,
+	/// A querier
+	Querier,
+	/// A queryable
+	Queryable,
+	/// A subscriber
+	Subscriber,
+	/// A timer
+	Timer,
+}
+
+impl Display for CapabilityKind {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			Self::LivelinessSubscriber => write!(f, "LivelinessSubscriber"),
+			Self::Observable => write!(f, "Observable"),
+			Self::Observer => write!(f, "Observer"),
+			Self::Publisher => write!(f, "Publisher"),
+			Self::Querier => write!(f, "Querier"),
+			Self::Queryable => write!(f, "Queryable"),
+			Self::Subscriber => write!(f, "Subscriber"),
+			Self::Timer => write!(f, "Timer"),
+		}
+	}
+}
+// endregion:	--- CapabilityKind
+
 // region:		--- Signal
-/// All defined commands of `DiMAS`
+/// All defined commands of `DiMAS`.
+/// The encoding depends on the order of the variants, so new ones are appended.
 #[derive(Debug, Decode, Encode)]
 pub enum Signal {
 	/// About
@@ -88,9 +130,37 @@ pub enum Signal {
 		/// Optional `OperationState` to set
 		state: Option<OperationState>,
 	},
+	/// Recover from state `Error`
+	Recover {
+		/// The `OperationState` to tear down to
+		state: OperationState,
+	},
+	/// List the registered capabilities
+	Capabilities,
 }
 // endregion:	--- Signal
 
+// region:		--- ParameterRequest
+/// Requests handled by the parameter server of an agent
+#[derive(Debug, Decode, Encode)]
+pub enum ParameterRequest {
+	/// Get a single parameter
+	Get {
+		/// Name of the parameter
+		name: String,
+	},
+	/// List all parameters
+	List,
+	/// Set a single parameter
+	Set {
+		/// Name of the parameter
+		name: String,
+		/// The new value, JSON encoded
+		value: String,
+	},
+}
+// endregion:	--- ParameterRequest
+
 // region:		--- TaskSignal
 /// Internal signals, used by panic hooks to inform that someting has happened.
 #[derive(Debug, Clone)]
@@ -105,7 +175,64 @@ pub enum TaskSignal {
 	RestartSubscriber(String),
 	/// Restart a certain timer, identified by its key expression
 	RestartTimer(String),
+	/// A certain capability, identified by its key expression, has been removed or replaced
+	Unregistered(String),
 	/// Shutdown whole process
 	Shutdown,
 }
 // endregion:	--- TaskSignal
+
+// region:		--- TimerCommand
+/// Commands to control a timer at runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerCommand {
+	/// Change the interval of the timer
+	SetInterval(Duration),
+	/// Pause the timer
+	Pause,
+	/// Resume a paused timer
+	Resume,
+	/// Fire the timer immediately once
+	TriggerNow,
+}
+// endregion:	--- TimerCommand
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// The [`Signal`] as known to agents before recovery and capabilities were added
+	#[derive(Debug, Decode, Encode)]
+	enum LegacySignal {
+		About,
+		Ping { sent: i64 },
+		Shutdown,
+		State { state: Option<OperationState> },
+	}
+
+	#[test]
+	fn signal_compatibility() {
+		let encoded = bitcode::encode(&LegacySignal::State {
+			state: Some(OperationState::Active),
+		});
+		let decoded: Signal = bitcode::decode(&encoded).expect("snh");
+		assert!(matches!(
+			decoded,
+			Signal::State {
+				state: Some(OperationState::Active)
+			}
+		));
+
+		let encoded = bitcode::encode(&Signal::Shutdown);
+		let decoded: LegacySignal = bitcode::decode(&encoded).expect("snh");
+		assert!(matches!(decoded, LegacySignal::Shutdown));
+
+		let encoded = bitcode::encode(&LegacySignal::Ping { sent: 42 });
+		let decoded: Signal = bitcode::decode(&encoded).expect("snh");
+		assert!(matches!(decoded, Signal::Ping { sent: 42 }));
+
+		// new signals are unknown to old agents
+		let encoded = bitcode::encode(&Signal::Capabilities);
+		assert!(bitcode::decode::<LegacySignal>(&encoded).is_err());
+	}
+}
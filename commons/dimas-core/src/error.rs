@@ -25,12 +25,26 @@ pub type Result<T> = core::result::Result<T, Box<dyn core::error::Error + Send +
 /// `dimas-core` error type.
 #[derive(Error, Debug)]
 pub enum Error {
+	/// encoding failed
+	#[error("encoding failed: reason {source}")]
+	Encoding {
+		/// the original codec error
+		source: Box<dyn core::error::Error + Send + Sync>,
+	},
 	/// decoding failed
 	#[error("decoding failed: reason {source}")]
 	Decoding {
 		/// the original bitcode error
 		source: Box<dyn core::error::Error + Send + Sync>,
 	},
+	/// a message has an encoding not matching the codec
+	#[error("expected a message encoded as {expected}, found {found}")]
+	UnexpectedEncoding {
+		/// the encoding of the codec
+		expected: String,
+		/// the encoding of the message
+		found: String,
+	},
 	/// sending reply failed
 	#[error("sending a reply failed: reason {source}")]
 	Reply {
@@ -40,9 +54,24 @@ pub enum Error {
 	/// empty request
 	#[error("query was empty")]
 	EmptyQuery,
+	/// an invalid `traceparent` header
+	#[error("the traceparent '{0}' is invalid")]
+	InvalidTraceparent(String),
 	/// Not available/implemented
 	#[error("no implementation available")]
 	NotImplemented,
+	/// a capability with the same selector is already registered
+	#[error("a capability for {0} is already registered")]
+	AlreadyRegistered(String),
+	/// a capability is not registered
+	#[error("no capability registered for {0}")]
+	NotRegistered(String),
+	/// read access to a storage failed
+	#[error("read access to storage for {0} failed")]
+	ReadAccess(String),
+	/// write access to a storage failed
+	#[error("write access to storage for {0} failed")]
+	WriteAccess(String),
 	/// An unknown [`OperationState`] is given
 	#[error("the operation state {state} is unknown")]
 	UnknownOperationState {
@@ -0,0 +1,467 @@
+// Copyright © 2024 Stephan Kunz
+
+//! Handles to the capabilities registered in an agent.
+//!
+//! A [`CapabilityHandle`] is returned when a capability is added to an agent.
+//! It allows to remove, pause and resume that capability at runtime.
+//! A [`TimerRef`] is returned by the contexts `timer()` method to control a timer at runtime.
+
+#[doc(hidden)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// region:		--- modules
+use crate::{
+	enums::{OperationState, TaskSignal, TimerCommand},
+	error::{Error, Result},
+	message_types::TimerStats,
+	traits::{Capability, Context, ContextAbstraction},
+};
+use alloc::{
+	string::{String, ToString},
+	sync::Arc,
+};
+use core::{fmt::Debug, hash::BuildHasher, time::Duration};
+#[cfg(feature = "std")]
+use std::{collections::HashMap, sync::RwLock};
+// endregion:	--- modules
+
+// region:		--- functions
+/// Register `capability` under `selector` in `storage` and adjust it to the given [`OperationState`].
+/// # Errors
+/// if a capability is already registered with the same selector
+/// or the storage is not accessible
+pub fn register<C, S>(
+	storage: &RwLock<HashMap<String, C, S>>,
+	selector: String,
+	capability: C,
+	state: &OperationState,
+) -> Result<()>
+where
+	C: Capability,
+	S: BuildHasher,
+{
+	let mut storage = storage
+		.write()
+		.map_err(|_| Error::WriteAccess(selector.clone()))?;
+	if storage.contains_key(&selector) {
+		return Err(Error::AlreadyRegistered(selector).into());
+	}
+	adjust(&capability, state)?;
+	storage.insert(selector, capability);
+	drop(storage);
+	Ok(())
+}
+
+/// Register `capability` under `selector` in `storage` and adjust it to the given [`OperationState`].
+///
+/// A capability already registered with the same selector is stopped and returned.
+/// # Errors
+/// if the storage is not accessible
+pub fn replace<C, S>(
+	storage: &RwLock<HashMap<String, C, S>>,
+	selector: String,
+	capability: C,
+	state: &OperationState,
+) -> Result<Option<C>>
+where
+	C: Capability,
+	S: BuildHasher,
+{
+	let mut storage = storage
+		.write()
+		.map_err(|_| Error::WriteAccess(selector.clone()))?;
+	let replaced = storage.remove(&selector);
+	if let Some(replaced) = &replaced {
+		stop(replaced)?;
+	}
+	adjust(&capability, state)?;
+	storage.insert(selector, capability);
+	drop(storage);
+	Ok(replaced)
+}
+
+/// Stop the capability registered under `selector` and remove it from `storage`.
+///
+/// Returns the removed capability or `None` if nothing was registered under `selector`.
+/// # Errors
+/// if the storage is not accessible
+pub fn unregister<C, S>(
+	storage: &RwLock<HashMap<String, C, S>>,
+	selector: &str,
+) -> Result<Option<C>>
+where
+	C: Capability,
+	S: BuildHasher,
+{
+	let removed = storage
+		.write()
+		.map_err(|_| Error::WriteAccess(selector.to_string()))?
+		.remove(selector);
+	if let Some(removed) = &removed {
+		stop(removed)?;
+	}
+	Ok(removed)
+}
+
+/// In state `Created` the agent has not been started yet,
+/// its capabilities are started with the first state change
+fn adjust<C>(capability: &C, state: &OperationState) -> Result<()>
+where
+	C: Capability,
+{
+	if state == &OperationState::Created {
+		return Ok(());
+	}
+	capability.manage_operation_state(state)
+}
+
+/// Inform the agent, that the capability registered under `selector` is gone,
+/// so that a capability added later with the same selector starts with fresh restart counting.
+fn unregistered<P>(context: &Context<P>, selector: &str) {
+	// with a full channel the restarts are forgotten after their counting period
+	let _ = context
+		.sender()
+		.try_send(TaskSignal::Unregistered(selector.into()));
+}
+
+/// `Error` is the lowest [`OperationState`], so every capability is stopped
+fn stop<C>(capability: &C) -> Result<()>
+where
+	C: Capability,
+{
+	capability.manage_operation_state(&OperationState::Error)
+}
+// endregion:	--- functions
+
+// region:		--- CapabilityHandle
+/// A handle to a capability registered in an agent.
+/// Dropping the handle leaves the capability registered.
+pub struct CapabilityHandle<P, C>
+where
+	P: Send + Sync + 'static,
+	C: Capability + Send + Sync + 'static,
+{
+	selector: String,
+	context: Context<P>,
+	storage: Arc<RwLock<HashMap<String, C>>>,
+}
+
+impl<P, C> Debug for CapabilityHandle<P, C>
+where
+	P: Send + Sync + 'static,
+	C: Capability + Send + Sync + 'static,
+{
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("CapabilityHandle")
+			.field("selector", &self.selector)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<P, C> CapabilityHandle<P, C>
+where
+	P: Send + Sync + 'static,
+	C: Capability + Send + Sync + 'static,
+{
+	/// Register `capability` under `selector` in `storage` and create a handle for it.
+	///
+	/// The capability is adjusted to the current [`OperationState`] of a running agent.
+	/// # Errors
+	/// if a capability is already registered with the same selector,
+	/// the storage is not accessible or the capability can not be adjusted
+	pub fn register(
+		selector: impl Into<String>,
+		capability: C,
+		context: Context<P>,
+		storage: Arc<RwLock<HashMap<String, C>>>,
+	) -> Result<Self> {
+		let selector = selector.into();
+		register(&storage, selector.clone(), capability, &context.state())?;
+		Ok(Self {
+			selector,
+			context,
+			storage,
+		})
+	}
+
+	/// Register `capability` under `selector` in `storage` and create a handle for it.
+	///
+	/// The capability is adjusted to the current [`OperationState`] of a running agent.
+	/// A capability already registered with the same selector is stopped and replaced.
+	/// # Errors
+	/// if the storage is not accessible or the capability can not be adjusted
+	pub fn replace(
+		selector: impl Into<String>,
+		capability: C,
+		context: Context<P>,
+		storage: Arc<RwLock<HashMap<String, C>>>,
+	) -> Result<Self> {
+		let selector = selector.into();
+		if replace(&storage, selector.clone(), capability, &context.state())?.is_some() {
+			unregistered(&context, &selector);
+		}
+		Ok(Self {
+			selector,
+			context,
+			storage,
+		})
+	}
+
+	/// Get `selector`
+	#[must_use]
+	pub fn selector(&self) -> &str {
+		&self.selector
+	}
+
+	/// Stop the capability and remove it from the agent.
+	///
+	/// Returns the removed capability or `None` if it has already been removed.
+	/// # Errors
+	/// if the storage is not accessible
+	pub fn remove(self) -> Result<Option<C>> {
+		let removed = unregister(&self.storage, &self.selector)?;
+		if removed.is_some() {
+			unregistered(&self.context, &self.selector);
+		}
+		Ok(removed)
+	}
+
+	/// Pause the capability, it will be stopped until it is resumed.
+	/// # Errors
+	/// if the capability is no longer registered or can not be paused
+	pub fn pause(&self) -> Result<()> {
+		self.set_paused(true)
+	}
+
+	/// Resume a paused capability, it will be started again if the agents state requires.
+	/// # Errors
+	/// if the capability is no longer registered
+	pub fn resume(&self) -> Result<()> {
+		self.set_paused(false)
+	}
+
+	/// Whether the capability is paused
+	/// # Errors
+	/// if the capability is no longer registered
+	pub fn is_paused(&self) -> Result<bool> {
+		Ok(self
+			.storage
+			.read()
+			.map_err(|_| Error::ReadAccess(self.selector.clone()))?
+			.get(&self.selector)
+			.ok_or_else(|| Error::NotRegistered(self.selector.clone()))?
+			.is_paused())
+	}
+
+	#[allow(clippy::significant_drop_tightening)]
+	fn set_paused(&self, paused: bool) -> Result<()> {
+		let storage = self
+			.storage
+			.read()
+			.map_err(|_| Error::ReadAccess(self.selector.clone()))?;
+		let capability = storage
+			.get(&self.selector)
+			.ok_or_else(|| Error::NotRegistered(self.selector.clone()))?;
+		if capability.is_paused() == paused {
+			return Ok(());
+		}
+		capability.set_paused(paused)?;
+		capability.manage_operation_state(&self.context.state())
+	}
+}
+// endregion:	--- CapabilityHandle
+
+// region:		--- TimerRef
+/// A reference to a timer registered in an agent, used to control it at runtime.
+/// The timer is looked up anew for each command.
+pub struct TimerRef<'a, P> {
+	name: String,
+	context: &'a dyn ContextAbstraction<Props = P>,
+}
+
+impl<P> Debug for TimerRef<'_, P> {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("TimerRef")
+			.field("name", &self.name)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<'a, P> TimerRef<'a, P> {
+	/// Create a reference to the timer `name` of `context`
+	#[must_use]
+	pub fn new(name: impl Into<String>, context: &'a dyn ContextAbstraction<Props = P>) -> Self {
+		Self {
+			name: name.into(),
+			context,
+		}
+	}
+
+	/// Get `name`
+	#[must_use]
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Change the interval of the timer, a running timer fires next one new interval after the change.
+	/// # Errors
+	/// if there is no such timer, the interval is zero or the timer has no interval
+	pub fn set_interval(&self, interval: Duration) -> Result<()> {
+		self.context
+			.control_timer(&self.name, TimerCommand::SetInterval(interval))
+	}
+
+	/// Pause the timer, it will be stopped until it is resumed.
+	/// # Errors
+	/// if there is no such timer
+	pub fn pause(&self) -> Result<()> {
+		self.context
+			.control_timer(&self.name, TimerCommand::Pause)
+	}
+
+	/// Resume a paused timer, it will be started again if the agents state requires.
+	/// # Errors
+	/// if there is no such timer
+	pub fn resume(&self) -> Result<()> {
+		self.context
+			.control_timer(&self.name, TimerCommand::Resume)
+	}
+
+	/// Fire the timer immediately once, independent of its schedule.
+	/// # Errors
+	/// if there is no such timer
+	pub fn trigger_now(&self) -> Result<()> {
+		self.context
+			.control_timer(&self.name, TimerCommand::TriggerNow)
+	}
+
+	/// Get the execution statistics of the timer
+	/// # Errors
+	/// if there is no such timer
+	pub fn stats(&self) -> Result<TimerStats> {
+		self.context.timer_stats(&self.name)
+	}
+}
+// endregion:	--- TimerRef
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use core::sync::atomic::{AtomicBool, Ordering};
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[derive(Debug)]
+	struct Props {}
+
+	#[derive(Debug, Default)]
+	struct Dummy {
+		running: AtomicBool,
+		paused: AtomicBool,
+	}
+
+	impl Capability for Dummy {
+		fn manage_operation_state(&self, state: &OperationState) -> Result<()> {
+			let run = state >= &OperationState::Active && !self.is_paused();
+			self.running.store(run, Ordering::Relaxed);
+			Ok(())
+		}
+
+		fn set_paused(&self, paused: bool) -> Result<()> {
+			self.paused.store(paused, Ordering::Relaxed);
+			Ok(())
+		}
+
+		fn is_paused(&self) -> bool {
+			self.paused.load(Ordering::Relaxed)
+		}
+	}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<CapabilityHandle<Props, Dummy>>();
+		is_normal::<TimerRef<'static, Props>>();
+	}
+
+	#[test]
+	fn register_and_unregister() {
+		let storage = RwLock::new(HashMap::new());
+		register(
+			&storage,
+			"selector".into(),
+			Dummy::default(),
+			&OperationState::Active,
+		)
+		.expect("snh");
+		assert!(
+			storage.read().expect("snh")["selector"]
+				.running
+				.load(Ordering::Relaxed)
+		);
+
+		// a duplicate is rejected and the registered one is untouched
+		assert!(
+			register(
+				&storage,
+				"selector".into(),
+				Dummy::default(),
+				&OperationState::Active
+			)
+			.is_err()
+		);
+		assert!(
+			storage.read().expect("snh")["selector"]
+				.running
+				.load(Ordering::Relaxed)
+		);
+
+		let removed = unregister(&storage, "selector").expect("snh");
+		assert!(
+			!removed
+				.expect("snh")
+				.running
+				.load(Ordering::Relaxed)
+		);
+		assert!(
+			unregister(&storage, "selector")
+				.expect("snh")
+				.is_none()
+		);
+	}
+
+	#[test]
+	fn replacing() {
+		let storage = RwLock::new(HashMap::new());
+		let replaced = replace(
+			&storage,
+			"selector".into(),
+			Dummy::default(),
+			&OperationState::Active,
+		)
+		.expect("snh");
+		assert!(replaced.is_none());
+
+		let replaced = replace(
+			&storage,
+			"selector".into(),
+			Dummy::default(),
+			&OperationState::Active,
+		)
+		.expect("snh");
+		assert!(
+			!replaced
+				.expect("snh")
+				.running
+				.load(Ordering::Relaxed)
+		);
+		assert!(
+			storage.read().expect("snh")["selector"]
+				.running
+				.load(Ordering::Relaxed)
+		);
+	}
+}
@@ -6,12 +6,22 @@
 /// States for usage in builders
 #[cfg(feature = "std")]
 pub mod builder_states;
+/// Codecs for the payload of messages
+pub mod codec;
 /// Enums
 pub mod enums;
 /// Error handling
 pub mod error;
+/// Handles to registered capabilities
+#[cfg(feature = "std")]
+pub mod handle;
 /// `Message`, `Request`, `Response`, `Feedback`
 pub mod message_types;
+/// Policies
+pub mod policies;
+/// Propagation of the trace context across agents
+#[cfg(feature = "std")]
+pub mod trace_context;
 /// Traits
 pub mod traits;
 /// Utilities
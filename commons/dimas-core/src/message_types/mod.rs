@@ -6,29 +6,225 @@
 extern crate alloc;
 
 // region:		--- modules
-use crate::error::{Error, Result};
-use alloc::{boxed::Box, string::ToString, vec::Vec};
-use bitcode::{Decode, Encode, decode, encode};
-use core::ops::Deref;
-use zenoh::{Wait, query::Query};
+use crate::{
+	codec::{Bitcode, Codec, Decoder, Encoder},
+	enums::{CapabilityKind, OperationState},
+	error::{Error, Result},
+	trace_context,
+};
+use alloc::{
+	boxed::Box,
+	collections::BTreeMap,
+	string::{String, ToString},
+	vec::Vec,
+};
+use bitcode::{Decode, Encode, encode};
+use core::{
+	fmt::{Debug, Display},
+	ops::Deref,
+	pin::Pin,
+	task::{Context, Poll},
+	time::Duration,
+};
+use futures::{Stream, StreamExt, TryStreamExt, stream::BoxStream};
+use zenoh::{Wait, bytes::ZBytes, qos::Priority, query::Query, sample::Sample, time::Timestamp};
 // endregion:	--- modules
 
+// region:		--- Headers
+/// User defined headers of a message, carried as zenoh attachment.
+///
+/// The headers are encoded as a JSON object with string values,
+/// so that tools not written in Rust can read them as well.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Headers(BTreeMap<String, String>);
+
+impl Headers {
+	/// Create empty [`Headers`]
+	#[must_use]
+	pub const fn new() -> Self {
+		Self(BTreeMap::new())
+	}
+
+	/// Add the header `name` with `value`
+	#[must_use]
+	pub fn with(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+		self.insert(name, value);
+		self
+	}
+
+	/// Set the header `name` to `value`, returning the previous value
+	pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) -> Option<String> {
+		self.0.insert(name.into(), value.into())
+	}
+
+	/// Remove the header `name`, returning its value
+	pub fn remove(&mut self, name: &str) -> Option<String> {
+		self.0.remove(name)
+	}
+
+	/// Get the value of the header `name`
+	#[must_use]
+	pub fn get(&self, name: &str) -> Option<&str> {
+		self.0.get(name).map(String::as_str)
+	}
+
+	/// Whether there are no headers
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+
+	/// Iterate over the headers names and values
+	pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+		self.0
+			.iter()
+			.map(|(name, value)| (name.as_str(), value.as_str()))
+	}
+
+	/// Encode the headers as zenoh attachment
+	#[must_use]
+	pub fn to_attachment(&self) -> Vec<u8> {
+		// a map of strings can always be represented as JSON
+		serde_json::to_vec(&self.0).unwrap_or_default()
+	}
+
+	/// Decode headers from a zenoh attachment
+	///
+	/// # Errors
+	/// if the attachment is not a JSON object with string values
+	pub fn from_attachment(attachment: &[u8]) -> Result<Self> {
+		serde_json::from_slice(attachment)
+			.map(Self)
+			.map_err(|source| {
+				Error::Decoding {
+					source: Box::new(source),
+				}
+				.into()
+			})
+	}
+
+	/// Decode headers from an optional zenoh attachment,
+	/// an attachment, which is not a header map, results in empty headers
+	#[must_use]
+	pub fn from_zbytes(attachment: Option<&ZBytes>) -> Self {
+		attachment
+			.and_then(|attachment| Self::from_attachment(&attachment.to_bytes()).ok())
+			.unwrap_or_default()
+	}
+}
+// endregion:	--- Headers
+
+// region:		--- SampleInfo
+/// Metadata of a received sample
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SampleInfo {
+	key_expr: String,
+	timestamp: Option<Timestamp>,
+	encoding: String,
+	priority: Priority,
+	attachment: Option<Vec<u8>>,
+}
+
+impl From<&Sample> for SampleInfo {
+	fn from(sample: &Sample) -> Self {
+		Self {
+			key_expr: sample.key_expr().to_string(),
+			timestamp: sample.timestamp().copied(),
+			encoding: sample.encoding().to_string(),
+			priority: sample.priority(),
+			attachment: sample
+				.attachment()
+				.map(|attachment| attachment.to_bytes().into_owned()),
+		}
+	}
+}
+
+impl SampleInfo {
+	/// Get the key expression the sample was published with,
+	/// which is the concrete key for samples received via a wildcard selector
+	#[must_use]
+	pub fn key_expr(&self) -> &str {
+		&self.key_expr
+	}
+
+	/// Get the timestamp of the sample, if the sender or a router added one
+	#[must_use]
+	pub const fn timestamp(&self) -> Option<&Timestamp> {
+		self.timestamp.as_ref()
+	}
+
+	/// Get the time of the timestamp as duration since the unix epoch
+	#[must_use]
+	pub fn time(&self) -> Option<Duration> {
+		self.timestamp
+			.map(|timestamp| timestamp.get_time().to_duration())
+	}
+
+	/// Get the id of the source, which created the timestamp
+	#[must_use]
+	pub fn source_id(&self) -> Option<String> {
+		self.timestamp
+			.map(|timestamp| timestamp.get_id().to_string())
+	}
+
+	/// Get the zenoh encoding of the sample
+	#[must_use]
+	pub fn encoding(&self) -> &str {
+		&self.encoding
+	}
+
+	/// Get the priority the sample was sent with
+	#[must_use]
+	pub const fn priority(&self) -> Priority {
+		self.priority
+	}
+
+	/// Get the attachment of the sample
+	#[must_use]
+	pub fn attachment(&self) -> Option<&[u8]> {
+		self.attachment.as_deref()
+	}
+
+	/// Get the [`Headers`] carried in the attachment of the sample
+	#[must_use]
+	pub fn headers(&self) -> Headers {
+		self.attachment
+			.as_deref()
+			.and_then(|attachment| Headers::from_attachment(attachment).ok())
+			.unwrap_or_default()
+	}
+}
+// endregion:	--- SampleInfo
+
 // region:		--- Message
 /// Implementation of a [`Message`].
 #[derive(Debug)]
-pub struct Message(Vec<u8>);
+pub struct Message {
+	value: Vec<u8>,
+	/// The zenoh encoding of the value, if known
+	encoding: Option<String>,
+	/// The headers sent as attachment
+	headers: Headers,
+	/// The metadata of a received Message
+	info: Option<SampleInfo>,
+}
 
 impl Deref for Message {
 	type Target = Vec<u8>;
 
 	fn deref(&self) -> &Self::Target {
-		&self.0
+		&self.value
 	}
 }
 
 impl Clone for Message {
 	fn clone(&self) -> Self {
-		Self(self.0.clone())
+		Self {
+			value: self.value.clone(),
+			encoding: self.encoding.clone(),
+			headers: self.headers.clone(),
+			info: self.info.clone(),
+		}
 	}
 }
 
@@ -36,39 +232,131 @@ impl Message {
 	/// Create a Message from raw data
 	#[must_use]
 	pub const fn new(value: Vec<u8>) -> Self {
-		Self(value)
+		Self {
+			value,
+			encoding: None,
+			headers: Headers::new(),
+			info: None,
+		}
 	}
 
-	/// Encode Message
+	/// Encode Message with [`Bitcode`], the Message is published with its encoding.
 	#[must_use]
 	pub fn encode<T>(message: &T) -> Self
 	where
 		T: Encode,
 	{
 		let content = encode(message);
-		Self(content)
+		Self::new(content).with_encoding(Bitcode::ENCODING)
+	}
+
+	/// Encode Message with the [`Codec`] `C`,
+	/// the Message is published with the codecs encoding.
+	///
+	/// # Errors
+	/// if the codec fails
+	pub fn encode_with<C, T>(message: &T) -> Result<Self>
+	where
+		C: Encoder<T>,
+	{
+		let content = C::encode(message)?;
+		Ok(Self::new(content).with_encoding(C::ENCODING))
 	}
 
-	/// Decode Message
+	/// Decode Message with [`Bitcode`]
 	///
 	/// # Errors
+	/// if the Message has an encoding other than [`Bitcode`] or decoding fails
 	pub fn decode<T>(self) -> Result<T>
 	where
 		T: for<'a> Decode<'a>,
 	{
-		let value: Vec<u8> = self.0;
-		decode::<T>(value.as_slice()).map_err(|source| {
-			Error::Decoding {
-				source: Box::new(source),
+		self.decode_with::<Bitcode, T>()
+	}
+
+	/// Decode Message with the [`Codec`] `C`
+	///
+	/// # Errors
+	/// if the Message has an encoding the codec does not accept or the codec fails
+	pub fn decode_with<C, T>(self) -> Result<T>
+	where
+		C: Decoder<T>,
+	{
+		if let Some(encoding) = self.encoding {
+			if !C::accepts(&encoding) {
+				return Err(Error::UnexpectedEncoding {
+					expected: C::ENCODING.to_string(),
+					found: encoding,
+				}
+				.into());
 			}
-			.into()
-		})
+		}
+		C::decode(&self.value)
+	}
+
+	/// Set the zenoh encoding of the Message
+	#[must_use]
+	pub fn with_encoding(mut self, encoding: impl Into<String>) -> Self {
+		self.encoding = Some(encoding.into());
+		self
+	}
+
+	/// Get the zenoh encoding of the Message, `None` if it is not known
+	#[must_use]
+	pub fn encoding(&self) -> Option<&str> {
+		self.encoding.as_deref()
+	}
+
+	/// Add the header `name` with `value`, sent as attachment of the Message
+	#[must_use]
+	pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+		self.headers.insert(name, value);
+		self
+	}
+
+	/// Set the [`Headers`] sent as attachment of the Message
+	#[must_use]
+	pub fn with_headers(mut self, headers: Headers) -> Self {
+		self.headers = headers;
+		self
+	}
+
+	/// Get the [`Headers`] of the Message
+	#[must_use]
+	pub const fn headers(&self) -> &Headers {
+		&self.headers
+	}
+
+	/// Get the [`Headers`] as zenoh attachment, including the `traceparent`
+	/// of the current [`TraceContext`](crate::trace_context::TraceContext).
+	/// Returns `None` if there is nothing to attach.
+	#[must_use]
+	pub fn attachment(&self) -> Option<Vec<u8>> {
+		trace_context::attachment(&self.headers)
+	}
+
+	/// Get the [`SampleInfo`] of a received Message
+	#[must_use]
+	pub const fn info(&self) -> Option<&SampleInfo> {
+		self.info.as_ref()
 	}
 
 	/// Get value of [`Message`]
 	#[must_use]
 	pub const fn value(&self) -> &Vec<u8> {
-		&self.0
+		&self.value
+	}
+}
+
+impl From<&Sample> for Message {
+	fn from(sample: &Sample) -> Self {
+		let info = SampleInfo::from(sample);
+		Self {
+			value: sample.payload().to_bytes().into_owned(),
+			encoding: Some(info.encoding.clone()),
+			headers: Headers::from_zbytes(sample.attachment()),
+			info: Some(info),
+		}
 	}
 }
 // endregion:	--- Message
@@ -104,36 +392,98 @@ impl QueryMsg {
 		let key = self.0.selector().key_expr().to_string();
 		let encoded: Vec<u8> = encode(&value);
 
-		self.0
-			.reply(&key, encoded)
+		let builder = self.0.reply(&key, encoded);
+		let builder = match trace_context::attachment(&Headers::new()) {
+			Some(attachment) => builder.attachment(attachment),
+			None => builder,
+		};
+		builder
 			.wait()
 			.map_err(|source| Error::Reply { source })?;
 		Ok(())
 	}
 
+	/// Reply to the given [`QueryMsg`] with a value encoded by the [`Codec`] `C`
+	///
+	/// # Errors
+	/// if the codec fails or the reply can not be sent
+	#[allow(clippy::needless_pass_by_value)]
+	pub fn reply_with<C, T>(self, value: T) -> Result<()>
+	where
+		C: Encoder<T>,
+	{
+		self.reply_with_headers::<C, T>(value, &Headers::new())
+	}
+
+	/// Reply to the given [`QueryMsg`] with a value encoded by the [`Codec`] `C`
+	/// and the `headers` as attachment
+	///
+	/// # Errors
+	/// if the codec fails or the reply can not be sent
+	#[allow(clippy::needless_pass_by_value)]
+	pub fn reply_with_headers<C, T>(self, value: T, headers: &Headers) -> Result<()>
+	where
+		C: Encoder<T>,
+	{
+		let key = self.0.selector().key_expr().to_string();
+		let encoded: Vec<u8> = C::encode(&value)?;
+
+		let builder = self.0.reply(&key, encoded).encoding(C::ENCODING);
+		let builder = match trace_context::attachment(headers) {
+			Some(attachment) => builder.attachment(attachment),
+			None => builder,
+		};
+		builder
+			.wait()
+			.map_err(|source| Error::Reply { source })?;
+		Ok(())
+	}
+
+	/// Get the [`Headers`] sent as attachment of the query
+	#[must_use]
+	pub fn headers(&self) -> Headers {
+		Headers::from_zbytes(self.0.attachment())
+	}
+
 	/// Access the queries parameters
 	#[must_use]
 	pub fn parameters(&self) -> &str {
 		self.0.parameters().as_str()
 	}
 
-	/// Decode [`QueryMsg`]
+	/// Decode [`QueryMsg`] with [`Bitcode`]
 	///
 	/// # Errors
+	/// if the query is empty, has an encoding other than [`Bitcode`] or decoding fails
 	pub fn decode<T>(&self) -> Result<T>
 	where
 		T: for<'a> Decode<'a>,
 	{
-		if let Some(value) = self.0.payload() {
-			let content: Vec<u8> = value.to_bytes().into_owned();
-			return decode::<T>(content.as_slice()).map_err(|source| {
-				Error::Decoding {
-					source: Box::new(source),
+		self.decode_with::<Bitcode, T>()
+	}
+
+	/// Decode [`QueryMsg`] with the [`Codec`] `C`
+	///
+	/// # Errors
+	/// if the query is empty, has an encoding the codec does not accept or the codec fails
+	pub fn decode_with<C, T>(&self) -> Result<T>
+	where
+		C: Decoder<T>,
+	{
+		let Some(value) = self.0.payload() else {
+			return Err(Error::EmptyQuery.into());
+		};
+		if let Some(encoding) = self.0.encoding() {
+			let encoding = encoding.to_string();
+			if !C::accepts(&encoding) {
+				return Err(Error::UnexpectedEncoding {
+					expected: C::ENCODING.to_string(),
+					found: encoding,
 				}
-				.into()
-			});
+				.into());
+			}
 		}
-		Err(Error::EmptyQuery.into())
+		C::decode(&value.to_bytes())
 	}
 }
 // endregion: 	--- QueryMsg
@@ -141,11 +491,27 @@ impl QueryMsg {
 // region:		--- QueryableMsg
 /// Implementation of a `Queryable` message handled by a `Query`
 #[derive(Debug)]
-pub struct QueryableMsg(pub Vec<u8>);
+pub struct QueryableMsg {
+	value: Vec<u8>,
+	/// The metadata of a received reply
+	info: Option<SampleInfo>,
+}
 
 impl Clone for QueryableMsg {
 	fn clone(&self) -> Self {
-		Self(self.0.clone())
+		Self {
+			value: self.value.clone(),
+			info: self.info.clone(),
+		}
+	}
+}
+
+impl From<&Sample> for QueryableMsg {
+	fn from(sample: &Sample) -> Self {
+		Self {
+			value: sample.payload().to_bytes().into_owned(),
+			info: Some(SampleInfo::from(sample)),
+		}
 	}
 }
 
@@ -153,38 +519,162 @@ impl Deref for QueryableMsg {
 	type Target = Vec<u8>;
 
 	fn deref(&self) -> &Self::Target {
-		&self.0
+		&self.value
 	}
 }
 
 impl QueryableMsg {
+	/// Create a [`QueryableMsg`] from raw data
+	#[must_use]
+	pub const fn new(value: Vec<u8>) -> Self {
+		Self { value, info: None }
+	}
+
 	/// Encode [`QueryableMsg`]
 	pub fn encode<T>(message: &T) -> Self
 	where
 		T: Encode,
 	{
 		let content = encode(message);
-		Self(content)
+		Self::new(content)
+	}
+
+	/// Get value of [`QueryableMsg`]
+	#[must_use]
+	pub const fn value(&self) -> &Vec<u8> {
+		&self.value
+	}
+
+	/// Get the [`SampleInfo`] of a received reply
+	#[must_use]
+	pub const fn info(&self) -> Option<&SampleInfo> {
+		self.info.as_ref()
+	}
+
+	/// Get the [`Headers`] sent as attachment of a received reply
+	#[must_use]
+	pub fn headers(&self) -> Headers {
+		self.info
+			.as_ref()
+			.map(SampleInfo::headers)
+			.unwrap_or_default()
 	}
 
-	/// Decode [`QueryableMsg`]
+	/// Decode [`QueryableMsg`] with [`Bitcode`]
 	///
 	/// # Errors
+	/// if the reply has an encoding other than [`Bitcode`] or decoding fails
 	pub fn decode<T>(self) -> Result<T>
 	where
 		T: for<'a> Decode<'a>,
 	{
-		let value: Vec<u8> = self.0;
-		decode::<T>(value.as_slice()).map_err(|source| {
-			Error::Decoding {
-				source: Box::new(source),
+		self.decode_with::<Bitcode, T>()
+	}
+
+	/// Encode [`QueryableMsg`] with the [`Codec`] `C`
+	///
+	/// # Errors
+	/// if the codec fails
+	pub fn encode_with<C, T>(message: &T) -> Result<Self>
+	where
+		C: Encoder<T>,
+	{
+		Ok(Self::new(C::encode(message)?))
+	}
+
+	/// Decode [`QueryableMsg`] with the [`Codec`] `C`
+	///
+	/// # Errors
+	/// if the reply has an encoding the codec does not accept or the codec fails
+	pub fn decode_with<C, T>(self) -> Result<T>
+	where
+		C: Decoder<T>,
+	{
+		if let Some(info) = &self.info {
+			if !C::accepts(info.encoding()) {
+				return Err(Error::UnexpectedEncoding {
+					expected: C::ENCODING.to_string(),
+					found: info.encoding().to_string(),
+				}
+				.into());
 			}
-			.into()
-		})
+		}
+		C::decode(&self.value)
 	}
 }
 // endregion:	--- QueryableMsg
 
+// region:		--- Replies
+/// The replies to a query as [`Stream`].
+///
+/// The stream ends, when all queryables have answered or the timeout of the query has elapsed,
+/// so an empty stream means, that no queryable answered in time.
+/// Dropping the stream stops receiving, replies arriving later are discarded.
+pub struct Replies(BoxStream<'static, Result<QueryableMsg>>);
+
+impl Debug for Replies {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("Replies").finish_non_exhaustive()
+	}
+}
+
+impl Stream for Replies {
+	type Item = Result<QueryableMsg>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		self.0.poll_next_unpin(cx)
+	}
+}
+
+impl Replies {
+	/// Create [`Replies`] from a stream of received replies
+	#[must_use]
+	pub fn new(replies: impl Stream<Item = Result<QueryableMsg>> + Send + 'static) -> Self {
+		Self(replies.boxed())
+	}
+
+	/// Decode the replies with [`Bitcode`]
+	pub fn typed<T>(self) -> impl Stream<Item = Result<T>> + Send + 'static
+	where
+		T: for<'a> Decode<'a> + Send + 'static,
+	{
+		self.typed_with::<Bitcode, T>()
+	}
+
+	/// Decode the replies with the [`Codec`] `C`
+	pub fn typed_with<C, T>(self) -> impl Stream<Item = Result<T>> + Send + 'static
+	where
+		C: Decoder<T>,
+		T: Send + 'static,
+	{
+		self.and_then(|reply| async move { reply.decode_with::<C, T>() })
+	}
+
+	/// Wait for all replies and decode them with [`Bitcode`]
+	///
+	/// # Errors
+	/// at the first reply, which is an error or can not be decoded
+	pub async fn collect_typed<T>(self) -> Result<Vec<T>>
+	where
+		T: for<'a> Decode<'a> + Send + 'static,
+	{
+		self.typed::<T>().try_collect().await
+	}
+
+	/// Wait for all replies and decode them with the [`Codec`] `C`
+	///
+	/// # Errors
+	/// at the first reply, which is an error or can not be decoded
+	pub async fn collect_typed_with<C, T>(self) -> Result<Vec<T>>
+	where
+		C: Decoder<T>,
+		T: Send + 'static,
+	{
+		self.typed_with::<C, T>().try_collect().await
+	}
+}
+// endregion:	--- Replies
+
 // region:		--- ControlResponse
 #[derive(Debug, Encode, Decode)]
 /// ?
@@ -213,9 +703,246 @@ pub enum ObservableResponse {
 }
 // endregion:	--- ObservableResponse
 
+// region:		--- TimerStats
+/// Execution statistics of a timer, durations are stored in nanoseconds
+#[derive(Debug, Encode, Decode, Clone, Default, PartialEq, Eq)]
+pub struct TimerStats {
+	count: u64,
+	total: u64,
+	min: u64,
+	max: u64,
+	jitter_count: u64,
+	jitter_total: u64,
+	jitter_max: u64,
+	overruns: u64,
+}
+
+impl Display for TimerStats {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(
+			f,
+			"calls: {} duration: {:?}/{:?}/{:?} jitter: {:?}/{:?} overruns: {}",
+			self.count,
+			self.min(),
+			self.mean(),
+			self.max(),
+			self.mean_jitter(),
+			self.max_jitter(),
+			self.overruns
+		)
+	}
+}
+
+impl TimerStats {
+	/// Record a callback, which took `duration` and started `jitter` after its scheduled time
+	pub fn record(&mut self, duration: Duration, jitter: Option<Duration>, overrun: bool) {
+		let duration = nanos(duration);
+		self.min = if self.count == 0 {
+			duration
+		} else {
+			self.min.min(duration)
+		};
+		self.max = self.max.max(duration);
+		self.total = self.total.saturating_add(duration);
+		self.count += 1;
+		if let Some(jitter) = jitter {
+			let jitter = nanos(jitter);
+			self.jitter_max = self.jitter_max.max(jitter);
+			self.jitter_total = self.jitter_total.saturating_add(jitter);
+			self.jitter_count += 1;
+		}
+		if overrun {
+			self.overruns += 1;
+		}
+	}
+
+	/// Get the number of recorded callbacks
+	#[must_use]
+	pub const fn count(&self) -> u64 {
+		self.count
+	}
+
+	/// Get the minimal duration of the callbacks
+	#[must_use]
+	pub const fn min(&self) -> Duration {
+		Duration::from_nanos(self.min)
+	}
+
+	/// Get the mean duration of the callbacks
+	#[must_use]
+	pub const fn mean(&self) -> Duration {
+		Duration::from_nanos(mean(self.total, self.count))
+	}
+
+	/// Get the maximal duration of the callbacks
+	#[must_use]
+	pub const fn max(&self) -> Duration {
+		Duration::from_nanos(self.max)
+	}
+
+	/// Get the mean delay of the callbacks behind their scheduled time
+	#[must_use]
+	pub const fn mean_jitter(&self) -> Duration {
+		Duration::from_nanos(mean(self.jitter_total, self.jitter_count))
+	}
+
+	/// Get the maximal delay of the callbacks behind their scheduled time
+	#[must_use]
+	pub const fn max_jitter(&self) -> Duration {
+		Duration::from_nanos(self.jitter_max)
+	}
+
+	/// Get the number of callbacks, which exceeded their budget
+	#[must_use]
+	pub const fn overruns(&self) -> u64 {
+		self.overruns
+	}
+}
+
+fn nanos(duration: Duration) -> u64 {
+	u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+const fn mean(total: u64, count: u64) -> u64 {
+	if count == 0 { 0 } else { total / count }
+}
+// endregion:	--- TimerStats
+
+// region:		--- CapabilityDescription
+/// Description of a capability registered in an agent, used for introspection
+#[derive(Debug, Encode, Decode, Clone)]
+pub struct CapabilityDescription {
+	kind: CapabilityKind,
+	selector: String,
+	session_id: Option<String>,
+	activation_state: OperationState,
+	running: bool,
+	paused: bool,
+	qos: Vec<(String, String)>,
+	timer_stats: Option<TimerStats>,
+}
+
+impl Display for CapabilityDescription {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(
+			f,
+			"{} {} activation: {} status: {}",
+			&self.kind,
+			&self.selector,
+			&self.activation_state,
+			if self.paused {
+				"paused"
+			} else if self.running {
+				"running"
+			} else {
+				"stopped"
+			}
+		)?;
+		if let Some(session_id) = &self.session_id {
+			write!(f, " session: {session_id}")?;
+		}
+		for (name, value) in &self.qos {
+			write!(f, " {name}: {value}")?;
+		}
+		if let Some(stats) = &self.timer_stats {
+			write!(f, " {stats}")?;
+		}
+		Ok(())
+	}
+}
+
+impl CapabilityDescription {
+	/// Constructor for a [`CapabilityDescription`] without `QoS` settings
+	#[must_use]
+	pub const fn new(
+		kind: CapabilityKind,
+		selector: String,
+		session_id: Option<String>,
+		activation_state: OperationState,
+		running: bool,
+		paused: bool,
+	) -> Self {
+		Self {
+			kind,
+			selector,
+			session_id,
+			activation_state,
+			running,
+			paused,
+			qos: Vec::new(),
+			timer_stats: None,
+		}
+	}
+
+	/// Add a `QoS` setting
+	#[must_use]
+	pub fn with_qos(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+		self.qos.push((name.into(), value.into()));
+		self
+	}
+
+	/// Add the execution statistics of a timer
+	#[must_use]
+	pub const fn with_timer_stats(mut self, stats: TimerStats) -> Self {
+		self.timer_stats.replace(stats);
+		self
+	}
+
+	/// Get the kind of capability
+	#[must_use]
+	pub const fn kind(&self) -> CapabilityKind {
+		self.kind
+	}
+
+	/// Get `selector`
+	#[must_use]
+	pub fn selector(&self) -> &str {
+		&self.selector
+	}
+
+	/// Get the id of the session the capability uses
+	#[must_use]
+	pub fn session_id(&self) -> Option<&str> {
+		self.session_id.as_deref()
+	}
+
+	/// Get the [`OperationState`] in which the capability is active
+	#[must_use]
+	pub const fn activation_state(&self) -> &OperationState {
+		&self.activation_state
+	}
+
+	/// Whether the capability is currently running
+	#[must_use]
+	pub const fn is_running(&self) -> bool {
+		self.running
+	}
+
+	/// Whether the capability is paused
+	#[must_use]
+	pub const fn is_paused(&self) -> bool {
+		self.paused
+	}
+
+	/// Get the `QoS` settings as pairs of name and value
+	#[must_use]
+	pub fn qos(&self) -> &[(String, String)] {
+		&self.qos
+	}
+
+	/// Get the execution statistics, if the capability is a timer
+	#[must_use]
+	pub const fn timer_stats(&self) -> Option<&TimerStats> {
+		self.timer_stats.as_ref()
+	}
+}
+// endregion:	--- CapabilityDescription
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::codec::{Cbor, Codec, Json};
+	use futures::executor::block_on;
 
 	// check, that the auto traits are available
 	const fn is_normal<T: Sized + Send + Sync>() {}
@@ -225,7 +952,97 @@ mod tests {
 		is_normal::<Message>();
 		is_normal::<QueryMsg>();
 		is_normal::<QueryableMsg>();
+		is_normal::<SampleInfo>();
+		is_normal::<Headers>();
 		is_normal::<ControlResponse>();
 		is_normal::<ObservableResponse>();
+		is_normal::<CapabilityDescription>();
+		is_normal::<TimerStats>();
+	}
+
+	#[test]
+	fn replies() {
+		let replies = || {
+			Replies::new(futures::stream::iter([
+				Ok(QueryableMsg::encode(&1_u32)),
+				Ok(QueryableMsg::encode(&2_u32)),
+			]))
+		};
+		let values = block_on(replies().collect_typed::<u32>()).expect("snh");
+		assert_eq!(values, [1, 2]);
+		assert!(block_on(replies().collect_typed_with::<Json, u32>()).is_err());
+
+		let replies = Replies::new(futures::stream::iter([
+			Ok(QueryableMsg::encode_with::<Json, _>(&"dimas").expect("snh")),
+			Err(Error::EmptyQuery.into()),
+		]));
+		let mut typed = Box::pin(replies.typed_with::<Json, String>());
+		assert_eq!(
+			block_on(typed.next()).expect("snh").ok(),
+			Some("dimas".into())
+		);
+		assert!(block_on(typed.next()).expect("snh").is_err());
+		assert!(block_on(typed.next()).is_none());
+	}
+
+	#[test]
+	fn timer_stats() {
+		let mut stats = TimerStats::default();
+		assert_eq!(stats.mean(), Duration::ZERO);
+		stats.record(
+			Duration::from_millis(2),
+			Some(Duration::from_millis(1)),
+			false,
+		);
+		stats.record(Duration::from_millis(6), None, true);
+		stats.record(
+			Duration::from_millis(4),
+			Some(Duration::from_millis(3)),
+			false,
+		);
+		assert_eq!(stats.count(), 3);
+		assert_eq!(stats.min(), Duration::from_millis(2));
+		assert_eq!(stats.mean(), Duration::from_millis(4));
+		assert_eq!(stats.max(), Duration::from_millis(6));
+		assert_eq!(stats.mean_jitter(), Duration::from_millis(2));
+		assert_eq!(stats.max_jitter(), Duration::from_millis(3));
+		assert_eq!(stats.overruns(), 1);
+	}
+
+	#[test]
+	fn headers() {
+		let headers = Headers::new()
+			.with("correlation-id", "4711")
+			.with("schema", "v2");
+		let attachment = headers.to_attachment();
+		assert_eq!(attachment, br#"{"correlation-id":"4711","schema":"v2"}"#);
+		let decoded = Headers::from_attachment(&attachment).expect("snh");
+		assert_eq!(decoded.get("correlation-id"), Some("4711"));
+		assert_eq!(decoded, headers);
+		assert!(Headers::from_attachment(b"[1,2]").is_err());
+
+		let msg = Message::new(Vec::new());
+		assert_eq!(msg.attachment(), None);
+		let msg = msg.with_header("schema", "v2");
+		assert_eq!(msg.attachment(), Some(br#"{"schema":"v2"}"#.to_vec()));
+	}
+
+	#[test]
+	fn message_encoding() {
+		let msg = Message::encode_with::<Json, _>(&42_u32).expect("snh");
+		assert_eq!(msg.encoding(), Some(Json::ENCODING));
+		assert_eq!(msg.clone().decode_with::<Json, u32>().ok(), Some(42));
+		assert!(msg.decode_with::<Cbor, u32>().is_err());
+
+		// a message without specific encoding is decoded by every codec
+		let msg = Message::new(b"42".to_vec()).with_encoding("zenoh/bytes");
+		assert_eq!(msg.decode_with::<Json, u32>().ok(), Some(42));
+
+		// the default decoding does only accept bitcode
+		let msg = Message::encode(&42_u32);
+		assert_eq!(msg.encoding(), Some(Bitcode::ENCODING));
+		assert_eq!(msg.decode::<u32>().ok(), Some(42));
+		let msg = Message::encode_with::<Json, _>(&42_u32).expect("snh");
+		assert!(msg.decode::<u32>().is_err());
 	}
 }
@@ -0,0 +1,426 @@
+// Copyright © 2024 Stephan Kunz
+
+//! Policies controlling the behaviour of an agent in case of failures.
+//!
+
+#[doc(hidden)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// region:		--- modules
+use crate::enums::OperationState;
+#[cfg(feature = "std")]
+use crate::{Result, traits::Context, utils::random};
+#[cfg(feature = "std")]
+use alloc::{boxed::Box, format, sync::Arc};
+use core::time::Duration;
+#[cfg(feature = "std")]
+use core::{
+	error::Error,
+	fmt::Debug,
+	sync::atomic::{AtomicU32, Ordering},
+};
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+#[cfg(feature = "std")]
+use tracing::{error, warn};
+// endregion:	--- modules
+
+// region:		--- types
+/// Type definition for the callback informed about a failed callback.
+/// The arguments are the selector of the failing capability and the error.
+#[cfg(feature = "std")]
+pub type ErrorCallback<P> =
+	Box<dyn FnMut(Context<P>, &str, &(dyn Error + Send + Sync)) + Send + Sync>;
+/// Type definition for the atomic reference counted callback informed about a failed callback
+#[cfg(feature = "std")]
+pub type ArcErrorCallback<P> = Arc<Mutex<ErrorCallback<P>>>;
+// endregion:	--- types
+
+// region:		--- RestartPolicy
+/// Policy for restarting a capability whose task panicked.
+///
+/// A capability is restarted at most `max_restarts` times within `period`,
+/// the delay before a restart starts with `backoff` and doubles with each further
+/// restart up to `max_backoff`. When the restarts are exhausted, the capability stays
+/// stopped and with `escalate` set the agent switches into `OperationState::Error`.
+///
+/// The policy has no effect in binaries built with `panic = 'abort'`,
+/// as a panic aborts the process before it can be caught.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestartPolicy {
+	/// Maximum number of restarts within `period`
+	pub max_restarts: u32,
+	/// The period after which the counting of restarts begins again
+	pub period: Duration,
+	/// Delay before the first restart
+	pub backoff: Duration,
+	/// Upper limit for the delay before a restart
+	pub max_backoff: Duration,
+	/// Whether to switch the agent into `OperationState::Error` when the restarts are exhausted
+	pub escalate: bool,
+}
+
+impl Default for RestartPolicy {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl RestartPolicy {
+	/// Constructor for the default policy:
+	/// at most 5 restarts per minute with a backoff from 100ms up to 10s, then escalate.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			max_restarts: 5,
+			period: Duration::from_secs(60),
+			backoff: Duration::from_millis(100),
+			max_backoff: Duration::from_secs(10),
+			escalate: true,
+		}
+	}
+
+	/// Constructor for a policy, which restarts immediately and without limit.
+	#[must_use]
+	pub const fn always() -> Self {
+		Self {
+			max_restarts: u32::MAX,
+			period: Duration::ZERO,
+			backoff: Duration::ZERO,
+			max_backoff: Duration::ZERO,
+			escalate: false,
+		}
+	}
+
+	/// Constructor for a policy, which never restarts.
+	#[must_use]
+	pub const fn never(escalate: bool) -> Self {
+		Self {
+			max_restarts: 0,
+			period: Duration::ZERO,
+			backoff: Duration::ZERO,
+			max_backoff: Duration::ZERO,
+			escalate,
+		}
+	}
+
+	/// The delay before the next restart, if there already have been `restarts` restarts
+	/// within `period`, `None` if the restarts are exhausted.
+	#[must_use]
+	pub fn delay(&self, restarts: u32) -> Option<Duration> {
+		if restarts >= self.max_restarts {
+			return None;
+		}
+		let factor = 2_u32.saturating_pow(restarts);
+		Some(
+			self.backoff
+				.saturating_mul(factor)
+				.min(self.max_backoff),
+		)
+	}
+}
+// endregion:	--- RestartPolicy
+
+// region:		--- RetryPolicy
+/// The growth of the delay between the attempts of a [`RetryPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backoff {
+	/// The same delay before every retry
+	Fixed(Duration),
+	/// A delay starting with `initial` and doubling with every further retry up to `max`
+	Exponential {
+		/// Delay before the first retry
+		initial: Duration,
+		/// Upper limit for the delay
+		max: Duration,
+	},
+}
+
+/// Policy for retrying a query, which was not answered.
+///
+/// A query is sent at most `max_attempts` times, waiting between the attempts as given
+/// by `backoff` plus a random `jitter` of up to the given duration.
+/// No further attempt is started, if it would begin after the overall `deadline`,
+/// which is measured from the start of the first attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+	/// Maximum number of attempts including the first one
+	pub max_attempts: u32,
+	/// Delay between the attempts
+	pub backoff: Backoff,
+	/// Upper limit for the random delay added to the backoff
+	pub jitter: Duration,
+	/// Overall time limit for all attempts
+	pub deadline: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl RetryPolicy {
+	/// Constructor for the default policy:
+	/// at most 5 attempts with a fixed backoff of 100ms, without jitter and deadline.
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			max_attempts: 5,
+			backoff: Backoff::Fixed(Duration::from_millis(100)),
+			jitter: Duration::ZERO,
+			deadline: None,
+		}
+	}
+
+	/// Constructor for a policy, which does only a single attempt.
+	#[must_use]
+	pub const fn never() -> Self {
+		Self {
+			max_attempts: 1,
+			backoff: Backoff::Fixed(Duration::ZERO),
+			jitter: Duration::ZERO,
+			deadline: None,
+		}
+	}
+
+	/// The backoff before the next attempt without jitter, if there already have been
+	/// `attempts` attempts, `None` if the attempts are exhausted.
+	#[must_use]
+	pub fn backoff(&self, attempts: u32) -> Option<Duration> {
+		if attempts >= self.max_attempts {
+			return None;
+		}
+		match self.backoff {
+			Backoff::Fixed(delay) => Some(delay),
+			Backoff::Exponential { initial, max } => {
+				let factor = 2_u32.saturating_pow(attempts.saturating_sub(1));
+				Some(initial.saturating_mul(factor).min(max))
+			}
+		}
+	}
+
+	/// The delay before the next attempt including a random jitter, if there already have been
+	/// `attempts` attempts, `None` if the attempts are exhausted.
+	#[cfg(feature = "std")]
+	#[must_use]
+	pub fn delay(&self, attempts: u32) -> Option<Duration> {
+		let backoff = self.backoff(attempts)?;
+		let jitter = u64::try_from(self.jitter.as_nanos()).unwrap_or(u64::MAX);
+		let jitter = random()
+			.checked_rem(jitter.saturating_add(1))
+			.unwrap_or(0);
+		Some(backoff.saturating_add(Duration::from_nanos(jitter)))
+	}
+}
+// endregion:	--- RetryPolicy
+
+// region:		--- ErrorPolicy
+/// Policy for the errors returned by the callbacks of a capability.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ErrorPolicy {
+	/// Errors are silently ignored
+	Ignore,
+	/// Errors are logged
+	#[default]
+	Log,
+	/// Errors are logged and after the given number of consecutive errors
+	/// the agent is switched into `OperationState::Error`
+	Escalate(u32),
+	/// Errors are logged and the agent is switched into the given [`OperationState`]
+	Transition(OperationState),
+}
+// endregion:	--- ErrorPolicy
+
+// region:		--- ErrorHandler
+/// Applies the [`ErrorPolicy`] of a capability to the results of its callbacks
+/// and informs an optional [`ErrorCallback`] about the errors.
+#[cfg(feature = "std")]
+pub struct ErrorHandler<P>
+where
+	P: Send + Sync + 'static,
+{
+	policy: ErrorPolicy,
+	callback: Option<ArcErrorCallback<P>>,
+	/// The number of consecutive errors
+	errors: Arc<AtomicU32>,
+}
+
+#[cfg(feature = "std")]
+impl<P> Clone for ErrorHandler<P>
+where
+	P: Send + Sync + 'static,
+{
+	fn clone(&self) -> Self {
+		Self {
+			policy: self.policy.clone(),
+			callback: self.callback.clone(),
+			errors: self.errors.clone(),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<P> Debug for ErrorHandler<P>
+where
+	P: Send + Sync + 'static,
+{
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("ErrorHandler")
+			.field("policy", &self.policy)
+			.field("callback", &self.callback.is_some())
+			.field("errors", &self.errors)
+			.finish()
+	}
+}
+
+#[cfg(feature = "std")]
+impl<P> Default for ErrorHandler<P>
+where
+	P: Send + Sync + 'static,
+{
+	fn default() -> Self {
+		Self::new(ErrorPolicy::default(), None)
+	}
+}
+
+#[cfg(feature = "std")]
+impl<P> ErrorHandler<P>
+where
+	P: Send + Sync + 'static,
+{
+	/// Constructor for an [`ErrorHandler`] applying `policy`.
+	/// The `callback` is informed about every error independent of the `policy`.
+	#[must_use]
+	pub fn new(policy: ErrorPolicy, callback: Option<ArcErrorCallback<P>>) -> Self {
+		Self {
+			policy,
+			callback,
+			errors: Arc::new(AtomicU32::new(0)),
+		}
+	}
+
+	/// Get the [`ErrorPolicy`]
+	#[must_use]
+	pub const fn policy(&self) -> &ErrorPolicy {
+		&self.policy
+	}
+
+	/// Handle the `result` of a callback of the capability registered under `selector`
+	pub fn handle(&self, ctx: &Context<P>, selector: &str, result: Result<()>) {
+		let Err(error) = result else {
+			self.errors.store(0, Ordering::Relaxed);
+			return;
+		};
+		self.inform(ctx, selector, &*error);
+		if self.policy == ErrorPolicy::Ignore {
+			return;
+		}
+		error!("callback of '{selector}' failed with {error}");
+		match &self.policy {
+			ErrorPolicy::Ignore | ErrorPolicy::Log => {}
+			ErrorPolicy::Escalate(limit) => self.escalate(ctx, selector, &*error, *limit),
+			ErrorPolicy::Transition(state) => {
+				if let Err(err) = ctx.set_state(state.clone()) {
+					warn!("could not change state to {state}: {err}");
+				}
+			}
+		}
+	}
+
+	/// Call the error callback, if there is one
+	fn inform(&self, ctx: &Context<P>, selector: &str, error: &(dyn Error + Send + Sync)) {
+		if let Some(callback) = &self.callback {
+			match callback.lock() {
+				Ok(mut callback) => callback(ctx.clone(), selector, error),
+				Err(err) => warn!("error callback lock failed with {err}"),
+			}
+		}
+	}
+
+	/// Count the error and switch into [`OperationState::Error`] when reaching `limit`
+	fn escalate(
+		&self,
+		ctx: &Context<P>,
+		selector: &str,
+		error: &(dyn Error + Send + Sync),
+		limit: u32,
+	) {
+		let errors = self.errors.fetch_add(1, Ordering::Relaxed) + 1;
+		if errors < limit {
+			return;
+		}
+		self.errors.store(0, Ordering::Relaxed);
+		let cause = format!("callback of '{selector}' failed {errors} times, last with {error}");
+		if let Err(err) = ctx.set_error(cause) {
+			warn!("could not escalate error: {err}");
+		}
+	}
+}
+// endregion:	--- ErrorHandler
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[derive(Debug)]
+	struct Props {}
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<RestartPolicy>();
+		is_normal::<RetryPolicy>();
+		is_normal::<ErrorPolicy>();
+		is_normal::<ErrorHandler<Props>>();
+	}
+
+	#[test]
+	fn delays() {
+		let policy = RestartPolicy::new();
+		assert_eq!(policy.delay(0), Some(Duration::from_millis(100)));
+		assert_eq!(policy.delay(1), Some(Duration::from_millis(200)));
+		assert_eq!(policy.delay(4), Some(Duration::from_millis(1600)));
+		assert_eq!(policy.delay(5), None);
+
+		let policy = RestartPolicy {
+			max_restarts: 100,
+			..RestartPolicy::new()
+		};
+		assert_eq!(policy.delay(50), Some(Duration::from_secs(10)));
+
+		assert_eq!(RestartPolicy::always().delay(1_000), Some(Duration::ZERO));
+		assert_eq!(RestartPolicy::never(true).delay(0), None);
+	}
+
+	#[test]
+	fn retries() {
+		let policy = RetryPolicy::new();
+		assert_eq!(policy.backoff(1), Some(Duration::from_millis(100)));
+		assert_eq!(policy.backoff(4), Some(Duration::from_millis(100)));
+		assert_eq!(policy.backoff(5), None);
+
+		let policy = RetryPolicy {
+			max_attempts: 10,
+			backoff: Backoff::Exponential {
+				initial: Duration::from_millis(50),
+				max: Duration::from_secs(1),
+			},
+			jitter: Duration::from_millis(10),
+			deadline: None,
+		};
+		assert_eq!(policy.backoff(1), Some(Duration::from_millis(50)));
+		assert_eq!(policy.backoff(3), Some(Duration::from_millis(200)));
+		assert_eq!(policy.backoff(9), Some(Duration::from_secs(1)));
+		assert_eq!(policy.backoff(10), None);
+		let delay = policy.delay(2).expect("snh");
+		assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(110));
+
+		assert_eq!(RetryPolicy::never().backoff(1), None);
+	}
+}
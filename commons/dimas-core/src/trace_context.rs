@@ -0,0 +1,261 @@
+// Copyright © 2024 Stephan Kunz
+
+//! Module `trace_context` propagates a W3C trace context across agents.
+//!
+//! Messages, queries and replies sent from within a callback carry the callbacks context
+//! in the `traceparent` header of their attachment. A callback receiving such a header
+//! continues that trace, otherwise a new trace is started. The callback runs within a span
+//! recording the `trace_id`, its own `span_id` and the `parent_id` of the sender,
+//! so that a request can be followed across a chain of agents.
+//!
+//! The ids are only recorded as string fields of that span, they are not bridged into
+//! the span context of an OpenTelemetry exporter. A `tracing-opentelemetry` layer exports
+//! the span with ids of its own, the recorded fields have to be used to correlate the spans.
+//! Callbacks not triggered by a message, like the callback of a timer, start a new root trace
+//! on every call.
+
+#[doc(hidden)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// region:		--- modules
+use crate::{error::Error, message_types::Headers, utils::random};
+use alloc::{format, string::ToString, vec::Vec};
+use core::{fmt::Display, future::Future, str::FromStr};
+use tracing::{Instrument, error_span};
+// endregion:	--- modules
+
+// region:		--- TraceContext
+/// Name of the header carrying the trace context
+pub const TRACEPARENT: &str = "traceparent";
+
+tokio::task_local! {
+	static CURRENT: TraceContext;
+}
+
+/// The position of a callback within a distributed trace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceContext {
+	trace_id: u128,
+	span_id: u64,
+	parent_id: Option<u64>,
+	flags: u8,
+}
+
+impl Display for TraceContext {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(
+			f,
+			"00-{:032x}-{:016x}-{:02x}",
+			self.trace_id, self.span_id, self.flags
+		)
+	}
+}
+
+impl FromStr for TraceContext {
+	type Err = Error;
+
+	/// Parse a `traceparent` header, the parent id of the result is not set
+	fn from_str(traceparent: &str) -> core::result::Result<Self, Self::Err> {
+		let invalid = || Error::InvalidTraceparent(traceparent.to_string());
+		let parts: Vec<&str> = traceparent.trim().split('-').collect();
+		// future versions may append fields
+		let [version, trace_id, span_id, flags, ..] = parts.as_slice() else {
+			return Err(invalid());
+		};
+		if version.len() != 2
+			|| *version == "ff"
+			|| trace_id.len() != 32
+			|| span_id.len() != 16
+			|| flags.len() != 2
+			|| (*version == "00" && parts.len() != 4)
+		{
+			return Err(invalid());
+		}
+		let hex = |value: &str| u128::from_str_radix(value, 16).map_err(|_| invalid());
+		let context = Self {
+			trace_id: hex(trace_id)?,
+			span_id: u64::try_from(hex(span_id)?).map_err(|_| invalid())?,
+			parent_id: None,
+			flags: u8::try_from(hex(flags)?).map_err(|_| invalid())?,
+		};
+		if context.trace_id == 0 || context.span_id == 0 {
+			return Err(invalid());
+		}
+		Ok(context)
+	}
+}
+
+impl TraceContext {
+	/// Start a new sampled trace
+	#[must_use]
+	pub fn new_root() -> Self {
+		Self {
+			trace_id: (u128::from(random()) << 64) | u128::from(random()),
+			span_id: random(),
+			parent_id: None,
+			flags: 1,
+		}
+	}
+
+	/// Continue the trace of `parent` with a new span
+	#[must_use]
+	pub fn continue_from(parent: &Self) -> Self {
+		Self {
+			trace_id: parent.trace_id,
+			span_id: random(),
+			parent_id: Some(parent.span_id),
+			flags: parent.flags,
+		}
+	}
+
+	/// Continue the trace given in the `traceparent` of the received `headers`,
+	/// or start a new trace if there is none
+	#[must_use]
+	pub fn continue_or_new(headers: &Headers) -> Self {
+		headers
+			.get(TRACEPARENT)
+			.and_then(|traceparent| traceparent.parse().ok())
+			.map_or_else(Self::new_root, |parent| Self::continue_from(&parent))
+	}
+
+	/// Get the context of the currently running callback
+	#[must_use]
+	pub fn current() -> Option<Self> {
+		CURRENT.try_with(|current| *current).ok()
+	}
+
+	/// Get the trace id
+	#[must_use]
+	pub const fn trace_id(&self) -> u128 {
+		self.trace_id
+	}
+
+	/// Get the span id
+	#[must_use]
+	pub const fn span_id(&self) -> u64 {
+		self.span_id
+	}
+
+	/// Get the span id of the sender, `None` for the root of a trace
+	#[must_use]
+	pub const fn parent_id(&self) -> Option<u64> {
+		self.parent_id
+	}
+
+	/// Whether the trace is sampled
+	#[must_use]
+	pub const fn is_sampled(&self) -> bool {
+		self.flags & 1 == 1
+	}
+
+	/// Run `future` with this context as current context
+	/// within a new span recording the ids of the context as string fields.
+	/// The span is not linked to an OpenTelemetry span context.
+	pub async fn scope<F>(self, selector: &str, future: F) -> F::Output
+	where
+		F: Future,
+	{
+		let span = error_span!(
+			"trace",
+			selector,
+			trace_id = format!("{:032x}", self.trace_id),
+			span_id = format!("{:016x}", self.span_id),
+			parent_id = self.parent_id.map(|id| format!("{id:016x}")),
+		);
+		CURRENT.scope(self, future.instrument(span)).await
+	}
+}
+
+/// Encode `headers` as zenoh attachment, together with the `traceparent`
+/// of the current context. Returns `None` if there is nothing to attach.
+#[must_use]
+pub fn attachment(headers: &Headers) -> Option<Vec<u8>> {
+	TraceContext::current().map_or_else(
+		|| (!headers.is_empty()).then(|| headers.to_attachment()),
+		|current| {
+			Some(
+				headers
+					.clone()
+					.with(TRACEPARENT, current.to_string())
+					.to_attachment(),
+			)
+		},
+	)
+}
+// endregion:	--- TraceContext
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::executor::block_on;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<TraceContext>();
+	}
+
+	#[test]
+	fn traceparent() {
+		let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+		let context: TraceContext = traceparent.parse().expect("snh");
+		assert_eq!(
+			context.trace_id(),
+			0x4bf9_2f35_77b3_4da6_a3ce_929d_0e0e_4736
+		);
+		assert_eq!(context.span_id(), 0x00f0_67aa_0ba9_02b7);
+		assert!(context.is_sampled());
+		assert_eq!(context.to_string(), traceparent);
+
+		assert!(
+			"00-00000000000000000000000000000000-00f067aa0ba902b7-01"
+				.parse::<TraceContext>()
+				.is_err()
+		);
+		assert!(
+			"ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+				.parse::<TraceContext>()
+				.is_err()
+		);
+		assert!(
+			"00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7"
+				.parse::<TraceContext>()
+				.is_err()
+		);
+		assert!(
+			"00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01"
+				.parse::<TraceContext>()
+				.is_err()
+		);
+
+		let child = TraceContext::continue_from(&context);
+		assert_eq!(child.trace_id(), context.trace_id());
+		assert_eq!(child.parent_id(), Some(context.span_id()));
+		assert_ne!(child.span_id(), context.span_id());
+	}
+
+	#[test]
+	fn propagation() {
+		let headers = Headers::new().with("schema", "v2");
+		assert_eq!(TraceContext::current(), None);
+		assert_eq!(attachment(&Headers::new()), None);
+
+		let root = TraceContext::new_root();
+		let sent = block_on(root.scope("test", async { attachment(&headers) })).expect("snh");
+		let sent = Headers::from_attachment(&sent).expect("snh");
+		assert_eq!(sent.get("schema"), Some("v2"));
+
+		let received = TraceContext::continue_or_new(&sent);
+		assert_eq!(received.trace_id(), root.trace_id());
+		assert_eq!(received.parent_id(), Some(root.span_id()));
+
+		let unrelated = TraceContext::continue_or_new(&headers);
+		assert_ne!(unrelated.trace_id(), root.trace_id());
+		assert_eq!(unrelated.parent_id(), None);
+	}
+}
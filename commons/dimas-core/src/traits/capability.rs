@@ -12,17 +12,23 @@ extern crate std;
 // region:		--- modules
 use crate::{
 	enums::{OperationState, TaskSignal},
-	error::Result,
+	error::{Error, Result},
 	message_types::{Message, QueryableMsg},
 	utils::selector_from,
 };
-use alloc::{string::String, sync::Arc};
-use core::fmt::Debug;
+use alloc::{boxed::Box, string::String, sync::Arc};
+use core::{fmt::Debug, future::Future, pin::Pin, time::Duration};
 #[cfg(feature = "std")]
 use tokio::sync::mpsc::Sender;
 use zenoh::Session;
 // endregion:	--- modules
 
+// region:		--- types
+/// Type definition for the future returned by [`Capability::drain`].
+/// It resolves to `true` if the capability had to be aborted forcefully.
+pub type DrainFuture = Pin<Box<dyn Future<Output = bool> + Send + 'static>>;
+// endregion:	--- types
+
 // region:		--- Capability
 /// Commonalities for capability components
 pub trait Capability: Debug {
@@ -30,5 +36,57 @@ pub trait Capability: Debug {
 	/// If not, implementation has to adjusts components state to needs.
 	/// # Errors
 	fn manage_operation_state(&self, state: &OperationState) -> Result<()>;
+
+	/// Gracefully adjusts the capability component for a shutdown to the given [`OperationState`].
+	///
+	/// Other than [`Capability::manage_operation_state`] this never (re)starts a component.
+	/// A running task is stopped after its in-flight callbacks have finished,
+	/// but latest after `timeout`.
+	///
+	/// The default implementation falls back to [`Capability::manage_operation_state`].
+	fn drain(&self, state: &OperationState, timeout: Duration) -> DrainFuture {
+		let _ = timeout;
+		let _ = self.manage_operation_state(state);
+		Box::pin(core::future::ready(false))
+	}
+
+	/// Pause or resume the capability component.
+	///
+	/// A paused component is stopped by [`Capability::manage_operation_state`] independent
+	/// of the given [`OperationState`] and only started again after it has been resumed.
+	/// The new setting takes effect with the next call to [`Capability::manage_operation_state`].
+	/// # Errors
+	/// if the component can not be paused, which is the default
+	fn set_paused(&self, paused: bool) -> Result<()> {
+		let _ = paused;
+		Err(Error::NotImplemented.into())
+	}
+
+	/// Whether the capability component is paused
+	#[must_use]
+	fn is_paused(&self) -> bool {
+		false
+	}
+}
+
+impl<T> Capability for Box<T>
+where
+	T: Capability + ?Sized,
+{
+	fn manage_operation_state(&self, state: &OperationState) -> Result<()> {
+		(**self).manage_operation_state(state)
+	}
+
+	fn drain(&self, state: &OperationState, timeout: Duration) -> DrainFuture {
+		(**self).drain(state, timeout)
+	}
+
+	fn set_paused(&self, paused: bool) -> Result<()> {
+		(**self).set_paused(paused)
+	}
+
+	fn is_paused(&self) -> bool {
+		(**self).is_paused()
+	}
 }
 // endregion:	--- Capability
@@ -0,0 +1,57 @@
+// Copyright © 2024 Stephan Kunz
+
+//! Clock trait of `DiMAS`
+//!
+
+#[doc(hidden)]
+extern crate alloc;
+
+// region:		--- modules
+use alloc::boxed::Box;
+use core::{fmt::Debug, future::Future, pin::Pin, time::Duration};
+// endregion:	--- modules
+
+// region:		--- types
+/// Type definition for the future returned by [`Clock::sleep_until`] and [`Clock::sleep`].
+pub type SleepFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+// endregion:	--- types
+
+// region:		--- Clock
+/// The source of time for timers, pings and timestamps of an agent.
+/// Wall-clock times are durations since the unix epoch,
+/// monotonic times are durations since a fixed start and never go backwards.
+pub trait Clock: Debug + Send + Sync {
+	/// Get the current wall-clock time
+	#[must_use]
+	fn now(&self) -> Duration;
+
+	/// A future, which is ready when the wall-clock has reached `deadline`
+	#[must_use]
+	fn sleep_until(&self, deadline: Duration) -> SleepFuture;
+
+	/// Get the current monotonic time, which is used to schedule periodic timers.
+	/// Defaults to the wall-clock time.
+	#[must_use]
+	fn monotonic(&self) -> Duration {
+		self.now()
+	}
+
+	/// A future, which is ready when the monotonic time has reached `deadline`
+	#[must_use]
+	fn sleep_until_monotonic(&self, deadline: Duration) -> SleepFuture {
+		self.sleep_until(deadline)
+	}
+
+	/// A future, which is ready when `duration` has passed on the clock
+	#[must_use]
+	fn sleep(&self, duration: Duration) -> SleepFuture {
+		self.sleep_until_monotonic(self.monotonic().saturating_add(duration))
+	}
+
+	/// Get the current time as timestamp in nanoseconds
+	#[must_use]
+	fn timestamp(&self) -> i64 {
+		i64::try_from(self.now().as_nanos()).unwrap_or(i64::MAX)
+	}
+}
+// endregion:	--- Clock
@@ -11,15 +11,21 @@ extern crate std;
 
 // region:		--- modules
 use crate::{
-	enums::{OperationState, TaskSignal},
+	enums::{CapabilityKind, OperationState, TaskSignal, TimerCommand},
 	error::Result,
-	message_types::{Message, QueryableMsg},
+	handle::TimerRef,
+	message_types::{CapabilityDescription, Message, QueryableMsg, Replies, TimerStats},
+	traits::Clock,
 	utils::selector_from,
 };
-use alloc::{string::String, sync::Arc};
-use core::fmt::Debug;
+use alloc::{string::String, sync::Arc, vec::Vec};
+use core::{
+	fmt::Debug,
+	ops::{Deref, DerefMut},
+	time::Duration,
+};
 #[cfg(feature = "std")]
-use tokio::sync::mpsc::Sender;
+use tokio::sync::{mpsc::Sender, watch};
 use zenoh::Session;
 // endregion:	--- modules
 
@@ -52,6 +58,29 @@ pub trait ContextAbstraction: Debug + Send + Sync {
 	/// # Errors
 	fn set_state(&self, state: OperationState) -> Result<()>;
 
+	/// Switch into [`OperationState::Error`] and record the `cause`.
+	///
+	/// The capabilities are left as they are, use [`ContextAbstraction::recover`]
+	/// to leave the state `Error`.
+	/// # Errors
+	fn set_error(&self, cause: String) -> Result<()>;
+
+	/// Get the cause of the last switch into [`OperationState::Error`]
+	#[must_use]
+	fn last_error(&self) -> Option<String>;
+
+	/// Recover from [`OperationState::Error`].
+	///
+	/// The capabilities are stepped down from the state the agent failed in to the given
+	/// safe `state`, which will be the new state. The transition guards are asked first
+	/// and the lifecycle hooks of each step are called.
+	/// A successful recovery clears [`ContextAbstraction::last_error`], a failing step
+	/// leaves the agent in state `Error` with the failure as new cause.
+	/// # Errors
+	/// if not in state `Error`, the given `state` is above `Configured` or above the state
+	/// the agent failed in, a guard rejects the recovery or a step fails
+	fn recover(&self, state: OperationState) -> Result<()>;
+
 	/// Get the uuid
 	#[must_use]
 	fn uuid(&self) -> String;
@@ -72,6 +101,10 @@ pub trait ContextAbstraction: Debug + Send + Sync {
 	#[must_use]
 	fn session(&self, session_id: &str) -> Option<Arc<Session>>;
 
+	/// Get the [`Clock`] used for timers, pings and timestamps
+	#[must_use]
+	fn clock(&self) -> Arc<dyn Clock>;
+
 	/// Get sender reference
 	#[must_use]
 	fn sender(&self) -> &Sender<TaskSignal>;
@@ -81,10 +114,16 @@ pub trait ContextAbstraction: Debug + Send + Sync {
 	/// # Errors
 	fn read(&self) -> Result<std::sync::RwLockReadGuard<'_, Self::Props>>;
 
-	/// Gives write access to the properties
+	/// Gives write access to the properties.
 	///
+	/// The watchers of the properties are notified after the returned guard has been dropped.
 	/// # Errors
-	fn write(&self) -> Result<std::sync::RwLockWriteGuard<'_, Self::Props>>;
+	fn write(&self) -> Result<PropsWriteGuard<'_, Self::Props>>;
+
+	/// Get a receiver, which is notified after the properties have been written.
+	/// The received value counts the writes since the start of the agent.
+	#[must_use]
+	fn watch_props(&self) -> watch::Receiver<u64>;
 
 	/// Method to do a publishing for a `topic`
 	/// The `topic` will be enhanced with the prefix.
@@ -155,6 +194,42 @@ pub trait ContextAbstraction: Debug + Send + Sync {
 		callback: Option<&mut dyn FnMut(QueryableMsg) -> Result<()>>,
 	) -> Result<()>;
 
+	/// Send a query for a `topic` with an optional [`Message`] without blocking.
+	/// The `topic` will be enhanced with the prefix.
+	/// If there is a query stored, it will be used
+	/// otherwise an ad-hoc query will be done
+	///
+	/// The answers are received via the returned [`Replies`], which can also be
+	/// decoded and collected into a `Vec` with [`Replies::collect_typed`].
+	/// A given `timeout` overrides the timeout of the query.
+	///
+	/// # Errors
+	/// if the query could not be sent
+	fn get_async(
+		&self,
+		topic: &str,
+		message: Option<Message>,
+		timeout: Option<Duration>,
+	) -> Result<Replies> {
+		let selector = selector_from(topic, self.prefix());
+		self.get_async_with(&selector, message, timeout)
+	}
+
+	/// Send a query for a `selector` with an optional [`Message`] without blocking.
+	/// If there is a query stored, it will be used
+	/// otherwise an ad-hoc query will be done
+	///
+	/// A given `timeout` overrides the timeout of the query.
+	///
+	/// # Errors
+	/// if the query could not be sent
+	fn get_async_with(
+		&self,
+		selector: &str,
+		message: Option<Message>,
+		timeout: Option<Duration>,
+	) -> Result<Replies>;
+
 	/// Send an observation request for a `topic` with a [`Message`].
 	/// The `topic` will be enhanced with the prefix.
 	///
@@ -182,5 +257,128 @@ pub trait ContextAbstraction: Debug + Send + Sync {
 	///
 	/// # Errors
 	fn cancel_observe_with(&self, selector: &str) -> Result<()>;
+
+	/// Get the kind and selector of all registered capabilities.
+	///
+	/// # Errors
+	fn capabilities(&self) -> Result<Vec<(CapabilityKind, String)>>;
+
+	/// Get a description of all registered capabilities.
+	///
+	/// # Errors
+	fn describe_capabilities(&self) -> Result<Vec<CapabilityDescription>>;
+
+	/// Stop the capability of the given `kind` with the `selector` and remove it.
+	/// Returns `false`, if there is no such capability.
+	///
+	/// # Errors
+	fn remove_capability(&self, kind: CapabilityKind, selector: &str) -> Result<bool>;
+
+	/// Kick the watchdog timer `name`, so that it does not fire within its current period.
+	/// The `name` is looked up as given and prefixed with the agents prefix.
+	///
+	/// # Errors
+	/// if there is no such timer or the timer is not a watchdog
+	fn kick(&self, name: &str) -> Result<()>;
+
+	/// Get a [`TimerRef`] to control the timer `name` at runtime.
+	/// The `name` is looked up as given and prefixed with the agents prefix.
+	#[must_use]
+	fn timer(&self, name: &str) -> TimerRef<'_, Self::Props>;
+
+	/// Apply the [`TimerCommand`] to the timer `name`.
+	/// The `name` is looked up as given and prefixed with the agents prefix.
+	///
+	/// # Errors
+	/// if there is no such timer or the timer does not support the command
+	fn control_timer(&self, name: &str, command: TimerCommand) -> Result<()>;
+
+	/// Get the execution statistics of the timer `name`.
+	/// The `name` is looked up as given and prefixed with the agents prefix.
+	///
+	/// # Errors
+	/// if there is no such timer
+	fn timer_stats(&self, name: &str) -> Result<TimerStats>;
 }
 // endregion:	--- Context
+
+// region:		--- PropsWriteGuard
+/// Write access to the properties of a [`Context`].
+///
+/// Dropping the guard releases the lock first and then notifies the watchers of the properties.
+pub struct PropsWriteGuard<'a, P> {
+	guard: Option<std::sync::RwLockWriteGuard<'a, P>>,
+	changed: &'a watch::Sender<u64>,
+}
+
+impl<P> Debug for PropsWriteGuard<'_, P>
+where
+	P: Debug,
+{
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("PropsWriteGuard")
+			.field("props", &self.guard)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<P> Deref for PropsWriteGuard<'_, P> {
+	type Target = P;
+
+	fn deref(&self) -> &Self::Target {
+		self.guard
+			.as_ref()
+			.expect("guard is only taken on drop")
+	}
+}
+
+impl<P> DerefMut for PropsWriteGuard<'_, P> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		self.guard
+			.as_mut()
+			.expect("guard is only taken on drop")
+	}
+}
+
+impl<P> Drop for PropsWriteGuard<'_, P> {
+	fn drop(&mut self) {
+		drop(self.guard.take());
+		self.changed
+			.send_modify(|writes| *writes = writes.wrapping_add(1));
+	}
+}
+
+impl<'a, P> PropsWriteGuard<'a, P> {
+	/// Constructor, `changed` is used to notify the watchers on drop
+	#[must_use]
+	pub const fn new(
+		guard: std::sync::RwLockWriteGuard<'a, P>,
+		changed: &'a watch::Sender<u64>,
+	) -> Self {
+		Self {
+			guard: Some(guard),
+			changed,
+		}
+	}
+}
+// endregion:	--- PropsWriteGuard
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::RwLock;
+
+	#[test]
+	fn notify_after_drop() {
+		let props = RwLock::new(0);
+		let (changed, mut watcher) = watch::channel(0);
+		let mut guard = PropsWriteGuard::new(props.write().expect("snh"), &changed);
+		*guard += 1;
+		assert!(!watcher.has_changed().expect("snh"));
+		drop(guard);
+		// the lock is released before the watchers are notified
+		assert!(watcher.has_changed().expect("snh"));
+		assert_eq!(*watcher.borrow_and_update(), 1);
+		assert_eq!(*props.read().expect("snh"), 1);
+	}
+}
@@ -4,8 +4,10 @@
 //!
 
 mod capability;
+mod clock;
 mod context;
 
 // flatten
 pub use capability::*;
+pub use clock::*;
 pub use context::*;
@@ -6,8 +6,30 @@
 #[doc(hidden)]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 // region:		--- modules
+#[cfg(feature = "std")]
+use crate::enums::TaskSignal;
 use alloc::string::{String, ToString};
+#[cfg(feature = "std")]
+use core::{
+	any::Any,
+	future::Future,
+	hash::{BuildHasher, Hasher},
+	panic::AssertUnwindSafe,
+	sync::atomic::{AtomicU64, Ordering},
+	time::Duration,
+};
+#[cfg(feature = "std")]
+use futures::FutureExt;
+#[cfg(feature = "std")]
+use std::hash::RandomState;
+#[cfg(feature = "std")]
+use tokio::{sync::mpsc::Sender, task::JoinHandle};
+#[cfg(feature = "std")]
+use tracing::error;
 // endregion:	--- modules
 
 // region:    --- tracing
@@ -25,7 +47,74 @@ pub fn init_tracing() {
 }
 // endregion: --- tracing
 
+// region:    --- drain
+/// Wait at most `timeout` for `idle` to resolve, then abort the task behind `handle`.
+///
+/// The `idle` future should resolve, when the task can be aborted without interrupting
+/// an in-flight callback, e.g. by acquiring the callbacks lock.
+/// Returns `true` if the task had to be aborted forcefully.
+#[cfg(feature = "std")]
+pub async fn drain_task<F>(handle: JoinHandle<()>, idle: F, timeout: Duration) -> bool
+where
+	F: Future + Send,
+{
+	let drained = tokio::time::timeout(timeout, async {
+		let guards = idle.await;
+		handle.abort();
+		drop(guards);
+	})
+	.await
+	.is_ok();
+	handle.abort();
+	!drained
+}
+// endregion: --- drain
+
+// region:    --- supervise
+/// Run the `task` of a capability and send `signal` via `sender`, if the task panics.
+///
+/// Other than a panic hook this only catches the panics of the supervised task,
+/// so the `signal` always identifies the capability that failed.
+///
+/// Panics can only be caught when unwinding, with `panic = 'abort'`
+/// as in the release profile of the workspace the process is aborted instead.
+#[cfg(feature = "std")]
+pub async fn supervise<F>(task: F, sender: Sender<TaskSignal>, signal: TaskSignal)
+where
+	F: Future<Output = ()> + Send,
+{
+	if let Err(reason) = AssertUnwindSafe(task).catch_unwind().await {
+		error!("{signal:?} due to panic: {}", panic_message(&*reason));
+		if let Err(reason) = sender.send(signal).await {
+			error!("could not send restart request: {reason}");
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+fn panic_message(reason: &(dyn Any + Send)) -> &str {
+	reason
+		.downcast_ref::<&str>()
+		.copied()
+		.or_else(|| {
+			reason
+				.downcast_ref::<String>()
+				.map(String::as_str)
+		})
+		.unwrap_or("unknown reason")
+}
+// endregion: --- supervise
+
 // region:    --- helper
+/// A random non zero number, not suitable for cryptographic purposes
+#[cfg(feature = "std")]
+pub(crate) fn random() -> u64 {
+	static COUNTER: AtomicU64 = AtomicU64::new(0);
+	let mut hasher = RandomState::new().build_hasher();
+	hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+	hasher.finish().max(1)
+}
+
 /// create selector
 #[must_use]
 pub fn selector_from(topic: &str, mut prefix: Option<&String>) -> String {
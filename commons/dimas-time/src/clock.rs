@@ -0,0 +1,158 @@
+// Copyright © 2024 Stephan Kunz
+
+//! Module `clock` provides the [`Clock`] implementations.
+//!
+//! The [`SystemClock`] follows the wall-clock time and is used by default,
+//! its monotonic time is not affected by steps of the wall-clock.
+//! The [`SimulatedClock`] only advances when told so, which allows deterministic tests
+//! of timer driven logic and running agents in lockstep with a simulator.
+
+#[doc(hidden)]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+// region:		--- modules
+use alloc::{boxed::Box, sync::Arc};
+use core::time::Duration;
+use dimas_core::traits::{Clock, SleepFuture};
+#[cfg(feature = "std")]
+use std::{
+	sync::OnceLock,
+	time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::{sync::watch, time};
+// endregion:	--- modules
+
+/// The fixed start of the monotonic time of the [`SystemClock`]
+#[cfg(feature = "std")]
+static START: OnceLock<time::Instant> = OnceLock::new();
+
+// region:		--- SystemClock
+/// A [`Clock`] following the systems wall-clock time.
+/// Its monotonic time is measured from the first use of any [`SystemClock`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+	fn now(&self) -> Duration {
+		SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap_or_default()
+	}
+
+	fn sleep_until(&self, deadline: Duration) -> SleepFuture {
+		Box::pin(time::sleep(deadline.saturating_sub(self.now())))
+	}
+
+	fn monotonic(&self) -> Duration {
+		START.get_or_init(time::Instant::now).elapsed()
+	}
+
+	fn sleep_until_monotonic(&self, deadline: Duration) -> SleepFuture {
+		let start = *START.get_or_init(time::Instant::now);
+		Box::pin(time::sleep_until(start + deadline))
+	}
+
+	fn sleep(&self, duration: Duration) -> SleepFuture {
+		Box::pin(time::sleep(duration))
+	}
+}
+// endregion:	--- SystemClock
+
+// region:		--- SimulatedClock
+/// A [`Clock`], which is advanced manually.
+/// Its monotonic time is the same as its wall-clock time.
+/// Clones share the same time, so a clone handed to an agent can be advanced from outside.
+#[derive(Debug, Clone)]
+pub struct SimulatedClock {
+	now: Arc<watch::Sender<Duration>>,
+}
+
+impl Default for SimulatedClock {
+	fn default() -> Self {
+		Self::new(Duration::ZERO)
+	}
+}
+
+impl Clock for SimulatedClock {
+	fn now(&self) -> Duration {
+		*self.now.borrow()
+	}
+
+	fn sleep_until(&self, deadline: Duration) -> SleepFuture {
+		let mut now = self.now.subscribe();
+		Box::pin(async move {
+			// without a clock there will be no wake up
+			if now
+				.wait_for(|now| *now >= deadline)
+				.await
+				.is_err()
+			{
+				core::future::pending::<()>().await;
+			}
+		})
+	}
+}
+
+impl SimulatedClock {
+	/// Create a [`SimulatedClock`] starting at `start`
+	#[must_use]
+	pub fn new(start: Duration) -> Self {
+		Self {
+			now: Arc::new(watch::Sender::new(start)),
+		}
+	}
+
+	/// Advance the clock by `duration`, waking up everything sleeping until then
+	pub fn advance(&self, duration: Duration) {
+		self.now
+			.send_modify(|now| *now = now.saturating_add(duration));
+	}
+
+	/// Set the clock to `time`, e.g. the time of a simulator
+	pub fn set(&self, time: Duration) {
+		self.now.send_replace(time);
+	}
+}
+// endregion:	--- SimulatedClock
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::FutureExt;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<SystemClock>();
+		is_normal::<SimulatedClock>();
+	}
+
+	#[test]
+	fn system_clock() {
+		let clock = SystemClock;
+		let first = clock.monotonic();
+		assert!(SystemClock.monotonic() >= first);
+	}
+
+	#[test]
+	fn simulated_clock() {
+		let clock = SimulatedClock::new(Duration::from_secs(10));
+		let mut sleep = clock.sleep(Duration::from_secs(5));
+		let mut until = clock.sleep_until(Duration::from_secs(20));
+		assert!((&mut sleep).now_or_never().is_none());
+
+		clock.clone().advance(Duration::from_secs(5));
+		assert_eq!(clock.now(), Duration::from_secs(15));
+		assert!(sleep.now_or_never().is_some());
+		assert!((&mut until).now_or_never().is_none());
+
+		clock.set(Duration::from_secs(20));
+		assert!(until.now_or_never().is_some());
+		assert_eq!(clock.timestamp(), 20_000_000_000);
+	}
+}
@@ -0,0 +1,279 @@
+// Copyright © 2024 Stephan Kunz
+
+//! Module `cron` provides the `CronSchedule` for timers firing according to a cron expression.
+//!
+//! An expression has 5 fields `minute hour day-of-month month day-of-week`
+//! or 6 fields with a leading `second` field.
+//! Each field is a comma separated list of `*`, a value `a`, a range `a-b`
+//! and a step `*/n`, `a/n` or `a-b/n`. Day-of-week is `0-7`, where both 0 and 7 are sunday.
+//! If day-of-month and day-of-week are both restricted, a day matching either one is scheduled.
+//! The shortcuts `@yearly`, `@annually`, `@monthly`, `@weekly`, `@daily`, `@midnight`
+//! and `@hourly` are accepted as well.
+//!
+//! Schedules are evaluated in UTC.
+
+#[doc(hidden)]
+extern crate alloc;
+
+// region:		--- modules
+use crate::error::Error;
+use alloc::{
+	format,
+	string::{String, ToString},
+	vec::Vec,
+};
+use chrono::{DateTime, Datelike, Duration, DurationRound, Timelike, Utc};
+use core::{fmt::Display, str::FromStr};
+// endregion:	--- modules
+
+// region:		--- Field
+/// The allowed values of one field of a cron expression as bit set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Field {
+	bits: u64,
+	/// Whether the field is restricted or matches every value
+	restricted: bool,
+}
+
+impl Field {
+	fn parse(expression: &str, min: u32, max: u32) -> core::result::Result<Self, String> {
+		let mut bits = 0_u64;
+		for item in expression.split(',') {
+			let (range, step) = match item.split_once('/') {
+				Some((range, step)) => {
+					let step: u32 = step
+						.parse()
+						.map_err(|_| format!("invalid step '{step}'"))?;
+					if step == 0 {
+						return Err(format!("invalid step '{step}'"));
+					}
+					(range, Some(step))
+				}
+				None => (item, None),
+			};
+			let (first, last) = if range == "*" {
+				(min, max)
+			} else if let Some((first, last)) = range.split_once('-') {
+				(value(first, min, max)?, value(last, min, max)?)
+			} else {
+				let first = value(range, min, max)?;
+				// a single value with step runs until the maximum
+				(first, if step.is_some() { max } else { first })
+			};
+			if first > last {
+				return Err(format!("invalid range '{range}'"));
+			}
+			let step = step.unwrap_or(1) as usize;
+			for value in (first..=last).step_by(step) {
+				bits |= 1 << value;
+			}
+		}
+		Ok(Self {
+			bits,
+			restricted: expression != "*",
+		})
+	}
+
+	const fn contains(&self, value: u32) -> bool {
+		self.bits & (1 << value) != 0
+	}
+}
+
+fn value(value: &str, min: u32, max: u32) -> core::result::Result<u32, String> {
+	value
+		.parse()
+		.ok()
+		.filter(|value| (min..=max).contains(value))
+		.ok_or_else(|| format!("invalid value '{value}', allowed are {min}-{max}"))
+}
+// endregion:	--- Field
+
+// region:		--- CronSchedule
+/// A schedule given by a cron expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+	expression: String,
+	seconds: Field,
+	minutes: Field,
+	hours: Field,
+	days: Field,
+	months: Field,
+	weekdays: Field,
+}
+
+impl Display for CronSchedule {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "{}", self.expression)
+	}
+}
+
+impl FromStr for CronSchedule {
+	type Err = Error;
+
+	fn from_str(expression: &str) -> core::result::Result<Self, Self::Err> {
+		let invalid = |reason: String| Error::InvalidCron {
+			expression: expression.to_string(),
+			reason,
+		};
+		let expanded = match expression.trim() {
+			"@yearly" | "@annually" => "0 0 1 1 *",
+			"@monthly" => "0 0 1 * *",
+			"@weekly" => "0 0 * * 0",
+			"@daily" | "@midnight" => "0 0 * * *",
+			"@hourly" => "0 * * * *",
+			other => other,
+		};
+		let fields: Vec<&str> = expanded.split_whitespace().collect();
+		let (seconds, fields) = match fields.len() {
+			5 => ("0", fields.as_slice()),
+			6 => (fields[0], &fields[1..]),
+			n => return Err(invalid(format!("expected 5 or 6 fields, found {n}"))),
+		};
+		let weekdays = Field::parse(fields[4], 0, 7).map_err(invalid)?;
+		// sunday may be given as 0 or 7
+		let weekdays = Field {
+			bits: (weekdays.bits | (weekdays.bits >> 7)) & 0x7f,
+			..weekdays
+		};
+		Ok(Self {
+			expression: expression.trim().to_string(),
+			seconds: Field::parse(seconds, 0, 59).map_err(invalid)?,
+			minutes: Field::parse(fields[0], 0, 59).map_err(invalid)?,
+			hours: Field::parse(fields[1], 0, 23).map_err(invalid)?,
+			days: Field::parse(fields[2], 1, 31).map_err(invalid)?,
+			months: Field::parse(fields[3], 1, 12).map_err(invalid)?,
+			weekdays,
+		})
+	}
+}
+
+impl CronSchedule {
+	/// Upper limit of steps searching the next time, covers more than 8 years
+	const SEARCH_LIMIT: usize = 100_000;
+
+	/// Get the cron expression
+	#[must_use]
+	pub fn expression(&self) -> &str {
+		&self.expression
+	}
+
+	/// The first scheduled time strictly after `time`,
+	/// `None` if there is none, e.g. for the 30th of february.
+	#[must_use]
+	pub fn next_after(&self, time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+		let mut next = time
+			.duration_trunc(Duration::seconds(1))
+			.ok()?
+			.checked_add_signed(Duration::seconds(1))?;
+		for _ in 0..Self::SEARCH_LIMIT {
+			let start_of_day = next.duration_trunc(Duration::days(1)).ok()?;
+			if !self.months.contains(next.month()) {
+				// first day of next month
+				let (year, month) = if next.month() == 12 {
+					(next.year() + 1, 1)
+				} else {
+					(next.year(), next.month() + 1)
+				};
+				next = start_of_day
+					.with_day(1)?
+					.with_year(year)?
+					.with_month(month)?;
+			} else if !self.day_matches(&next) {
+				next = start_of_day.checked_add_signed(Duration::days(1))?;
+			} else if !self.hours.contains(next.hour()) {
+				next = next
+					.duration_trunc(Duration::hours(1))
+					.ok()?
+					.checked_add_signed(Duration::hours(1))?;
+			} else if !self.minutes.contains(next.minute()) {
+				next = next
+					.duration_trunc(Duration::minutes(1))
+					.ok()?
+					.checked_add_signed(Duration::minutes(1))?;
+			} else if !self.seconds.contains(next.second()) {
+				next = next.checked_add_signed(Duration::seconds(1))?;
+			} else {
+				return Some(next);
+			}
+		}
+		None
+	}
+
+	fn day_matches(&self, time: &DateTime<Utc>) -> bool {
+		let day = self.days.contains(time.day());
+		let weekday = self
+			.weekdays
+			.contains(time.weekday().num_days_from_sunday());
+		if self.days.restricted && self.weekdays.restricted {
+			day || weekday
+		} else {
+			day && weekday
+		}
+	}
+}
+// endregion:	--- CronSchedule
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use chrono::TimeZone;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<CronSchedule>();
+	}
+
+	fn time(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> DateTime<Utc> {
+		Utc.with_ymd_and_hms(year, month, day, hour, minute, second)
+			.single()
+			.expect("snh")
+	}
+
+	#[test]
+	fn parsing() {
+		assert!("* * * * *".parse::<CronSchedule>().is_ok());
+		assert!("*/5 * * * * *".parse::<CronSchedule>().is_ok());
+		assert!("0 9-17/2 * * 1-5".parse::<CronSchedule>().is_ok());
+		assert!("@daily".parse::<CronSchedule>().is_ok());
+
+		assert!("* * * *".parse::<CronSchedule>().is_err());
+		assert!("60 * * * *".parse::<CronSchedule>().is_err());
+		assert!("*/0 * * * *".parse::<CronSchedule>().is_err());
+		assert!("5-1 * * * *".parse::<CronSchedule>().is_err());
+		assert!("a * * * *".parse::<CronSchedule>().is_err());
+	}
+
+	#[test]
+	fn next_times() {
+		let start = time(2024, 2, 28, 23, 58, 30);
+
+		let every_5_minutes: CronSchedule = "*/5 * * * *".parse().expect("snh");
+		assert_eq!(
+			every_5_minutes.next_after(start),
+			Some(time(2024, 2, 29, 0, 0, 0))
+		);
+
+		let every_second: CronSchedule = "* * * * * *".parse().expect("snh");
+		assert_eq!(
+			every_second.next_after(start),
+			Some(time(2024, 2, 28, 23, 58, 31))
+		);
+
+		// sunday given as 7, 2024-03-03 is a sunday
+		let sundays: CronSchedule = "30 12 * * 7".parse().expect("snh");
+		assert_eq!(sundays.next_after(start), Some(time(2024, 3, 3, 12, 30, 0)));
+
+		// day-of-month or day-of-week, 2024-03-01 is a friday
+		let either: CronSchedule = "0 0 1 * 0".parse().expect("snh");
+		assert_eq!(either.next_after(start), Some(time(2024, 3, 1, 0, 0, 0)));
+
+		let yearly: CronSchedule = "@yearly".parse().expect("snh");
+		assert_eq!(yearly.next_after(start), Some(time(2025, 1, 1, 0, 0, 0)));
+
+		let never: CronSchedule = "0 0 30 2 *".parse().expect("snh");
+		assert_eq!(never.next_after(start), None);
+	}
+}
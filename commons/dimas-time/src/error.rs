@@ -15,9 +15,26 @@ use thiserror::Error;
 /// `dimas-time` error type.
 #[derive(Error, Debug)]
 pub enum Error {
+	/// an invalid cron expression.
+	#[error("invalid cron expression '{expression}': {reason}")]
+	InvalidCron {
+		/// the expression
+		expression: String,
+		/// why the expression is invalid
+		reason: String,
+	},
 	/// a Mutex is poisoned.
 	#[error("a Mutex poison error happened in {0}")]
 	MutexPoison(String),
+	/// the timer has no interval.
+	#[error("the timer {0} has no interval")]
+	NoInterval(String),
+	/// the timer is not a watchdog.
+	#[error("the timer {0} is not a watchdog")]
+	NoWatchdog(String),
+	/// a zero interval was given.
+	#[error("the interval of timer {0} must not be zero")]
+	ZeroInterval(String),
 }
 // region:		--- Error
 
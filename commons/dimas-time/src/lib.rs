@@ -5,12 +5,16 @@
 //!
 
 // region:    --- modules
+mod clock;
+mod cron;
 mod error;
 mod timer;
 #[cfg(feature = "std")]
 mod timer_builder;
 
 // flatten
+pub use clock::*;
+pub use cron::*;
 pub use timer::*;
 #[cfg(feature = "std")]
 pub use timer_builder::*;
@@ -10,119 +10,265 @@ extern crate alloc;
 extern crate std;
 
 // region:		--- modules
-use alloc::{boxed::Box, string::String, sync::Arc};
-use core::{fmt::Debug, time::Duration};
+use crate::{cron::CronSchedule, error::Error};
+use alloc::{boxed::Box, format, string::String, sync::Arc};
+use chrono::{DateTime, Utc};
+use core::{
+	fmt::Debug,
+	sync::atomic::{AtomicBool, Ordering},
+	time::Duration,
+};
 use dimas_core::{
 	Result,
-	enums::{OperationState, TaskSignal},
-	traits::{Capability, Context},
+	enums::{CapabilityKind, OperationState, TaskSignal},
+	message_types::{CapabilityDescription, TimerStats},
+	policies::ErrorHandler,
+	trace_context::TraceContext,
+	traits::{Capability, Clock, Context, DrainFuture},
+	utils::{drain_task, supervise},
 };
+use futures::future::BoxFuture;
 #[cfg(feature = "std")]
-use std::sync::Mutex;
+use std::sync::{Mutex, PoisonError};
 #[cfg(feature = "std")]
-use tokio::{task::JoinHandle, time};
-use tracing::{Level, error, info, instrument, warn};
+use tokio::{
+	sync::{Notify, watch},
+	task::JoinHandle,
+};
+use tracing::{Level, instrument, warn};
 // endregion:	--- modules
 
 // region:		--- types
-/// type definition for the functions called by a timer
-pub type ArcTimerCallback<P> =
-	Arc<Mutex<dyn FnMut(Context<P>) -> Result<()> + Send + Sync + 'static>>;
+/// Type definition for the functions called by a timer
+pub type TimerCallback<P> =
+	Box<dyn FnMut(Context<P>) -> BoxFuture<'static, Result<()>> + Send + Sync>;
+/// Type definition for the atomic reference counted functions called by a timer
+pub type ArcTimerCallback<P> = Arc<tokio::sync::Mutex<TimerCallback<P>>>;
 // endregion:	--- types
 
+// region:		--- MissedTicks
+/// The behaviour of a [`Timer`] when ticks have been missed,
+/// because the callback took longer than the interval.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MissedTicks {
+	/// Fire the missed ticks as fast as possible until the timer has caught up
+	#[default]
+	Burst,
+	/// Fire immediately and continue with the interval from then on
+	Delay,
+	/// Skip the missed ticks and fire at the next tick according to the original schedule
+	Skip,
+}
+// endregion:	--- MissedTicks
+
+// region:		--- TimerMonitor
+/// Records the [`TimerStats`] of a [`Timer`] and warns about callbacks exceeding their budget
+#[derive(Debug, Clone, Default)]
+pub struct TimerMonitor {
+	budget: Option<Duration>,
+	stats: Arc<Mutex<TimerStats>>,
+}
+
+impl TimerMonitor {
+	/// Create a [`TimerMonitor`] with an optional `budget` for the duration of a callback.
+	/// Without a budget a callback overruns, if it takes longer than the timers period.
+	#[must_use]
+	pub fn new(budget: Option<Duration>) -> Self {
+		Self {
+			budget,
+			stats: Arc::new(Mutex::new(TimerStats::default())),
+		}
+	}
+
+	/// Get the budget for the duration of a callback
+	#[must_use]
+	pub const fn budget(&self) -> Option<Duration> {
+		self.budget
+	}
+
+	/// Get a copy of the recorded statistics
+	#[must_use]
+	pub fn stats(&self) -> TimerStats {
+		self.stats
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.clone()
+	}
+
+	/// Record a callback running for `duration`, which was started `jitter` behind its schedule
+	fn record(
+		&self,
+		selector: &str,
+		duration: Duration,
+		jitter: Option<Duration>,
+		period: Option<Duration>,
+	) {
+		if let Some(budget) = self.budget.filter(|budget| duration > *budget) {
+			warn!(
+				"callback of timer {selector} took {duration:?}, exceeding its budget of {budget:?}"
+			);
+		}
+		let overrun = self
+			.budget
+			.or(period)
+			.is_some_and(|limit| duration > limit);
+		self.stats
+			.lock()
+			.unwrap_or_else(PoisonError::into_inner)
+			.record(duration, jitter, overrun);
+	}
+}
+// endregion:	--- TimerMonitor
+
 // region:		--- Timer
-/// Timer
-pub enum Timer<P>
-where
-	P: Send + Sync + 'static,
-{
-	/// A Timer with an Interval
+/// The schedule of a [`Timer`], holding what is specific to each kind of timer
+enum Schedule {
+	/// Firing in an interval, optionally starting after a delay
 	Interval {
-		/// The Timers ID
-		selector: String,
-		/// Context for the Timer
-		context: Context<P>,
-		/// [`OperationState`] on which this timer is started
-		activation_state: OperationState,
-		/// Timers Callback function called, when Timer is fired
-		callback: ArcTimerCallback<P>,
 		/// The interval in which the Timer is fired
-		interval: Duration,
-		/// The handle to stop the Timer
-		handle: Mutex<Option<JoinHandle<()>>>,
-	},
-	/// A delayed Timer with an Interval
-	DelayedInterval {
-		/// The Timers ID
-		selector: String,
-		/// Context for the Timer
-		context: Context<P>,
-		/// [`OperationState`] on which this timer is started
-		activation_state: OperationState,
-		/// Timers Callback function called, when Timer is fired
-		callback: ArcTimerCallback<P>,
-		/// The interval in which the Timer is fired
-		interval: Duration,
+		interval: watch::Sender<Duration>,
 		/// The delay after which the first firing of the Timer happenes
-		delay: Duration,
-		/// The handle to stop the Timer
-		handle: Mutex<Option<JoinHandle<()>>>,
+		delay: Option<Duration>,
+		/// The behaviour on missed ticks
+		missed_ticks: MissedTicks,
+	},
+	/// Firing once after a delay, each time the Timer is started
+	OneShot {
+		/// The delay after which the Timer is fired
+		delay: watch::Sender<Duration>,
+	},
+	/// Firing only, if the Timer has not been kicked within its period
+	Watchdog {
+		/// The period within the Timer has to be kicked
+		period: watch::Sender<Duration>,
+		/// Notification about kicks
+		kicked: Arc<Notify>,
+	},
+	/// Firing at the wall-clock boundaries of its period, counted from the unix epoch
+	Aligned {
+		/// The period whose boundaries the Timer is aligned to
+		period: watch::Sender<Duration>,
+		/// The offset of the firing times behind the boundaries
+		offset: Duration,
+	},
+	/// Firing according to a cron schedule
+	Cron {
+		/// The schedule of the Timer
+		schedule: CronSchedule,
 	},
 }
 
-impl<P> Debug for Timer<P>
-where
-	P: Send + Sync + 'static,
-{
+impl Debug for Schedule {
 	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
 		match self {
-			Self::Interval { interval, .. } => f
-				.debug_struct("IntervalTimer")
-				.field("interval", interval)
-				.finish_non_exhaustive(),
-			Self::DelayedInterval {
-				delay, interval, ..
+			Self::Interval {
+				interval,
+				delay,
+				missed_ticks,
 			} => f
-				.debug_struct("DelayedIntervalTimer")
+				.debug_struct("Interval")
+				.field("interval", &*interval.borrow())
 				.field("delay", delay)
-				.field("interval", interval)
+				.field("missed_ticks", missed_ticks)
+				.finish(),
+			Self::OneShot { delay } => f
+				.debug_struct("OneShot")
+				.field("delay", &*delay.borrow())
+				.finish(),
+			Self::Watchdog { period, .. } => f
+				.debug_struct("Watchdog")
+				.field("period", &*period.borrow())
 				.finish_non_exhaustive(),
+			Self::Aligned { period, offset } => f
+				.debug_struct("Aligned")
+				.field("period", &*period.borrow())
+				.field("offset", offset)
+				.finish(),
+			Self::Cron { schedule } => f
+				.debug_struct("Cron")
+				.field("schedule", &schedule.expression())
+				.finish(),
 		}
 	}
 }
 
+/// Timer
+///
+/// A timer is not triggered by a message, so every firing of its callback starts
+/// a new root trace, see [`TraceContext`].
+pub struct Timer<P>
+where
+	P: Send + Sync + 'static,
+{
+	/// The Timers ID
+	selector: String,
+	/// Context for the Timer
+	context: Context<P>,
+	/// [`OperationState`] on which this timer is started
+	activation_state: OperationState,
+	/// A paused Timer is not started
+	paused: AtomicBool,
+	/// Timers Callback function called, when Timer is fired
+	callback: ArcTimerCallback<P>,
+	/// Handling of the errors returned by the callback
+	errors: ErrorHandler<P>,
+	/// Statistics of the callback executions
+	monitor: TimerMonitor,
+	/// When the Timer is fired
+	schedule: Schedule,
+	/// The handle to stop the Timer
+	handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl<P> Debug for Timer<P>
+where
+	P: Send + Sync + 'static,
+{
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("Timer")
+			.field("selector", &self.selector)
+			.field("schedule", &self.schedule)
+			.finish_non_exhaustive()
+	}
+}
+
 impl<P> Capability for Timer<P>
 where
 	P: Send + Sync + 'static,
 {
 	fn manage_operation_state(&self, state: &OperationState) -> Result<()> {
-		match self {
-			Self::Interval {
-				selector: _,
-				context: _,
-				activation_state,
-				interval: _,
-				callback: _,
-				handle: _,
-			}
-			| Self::DelayedInterval {
-				selector: _,
-				context: _,
-				activation_state,
-				delay: _,
-				interval: _,
-				callback: _,
-				handle: _,
-			} => {
-				if state >= activation_state {
-					self.start()
-				} else if state < activation_state {
-					self.stop()
-				} else {
-					Ok(())
-				}
-			}
+		if state >= &self.activation_state && !self.is_paused() {
+			self.start()
+		} else {
+			self.stop()
+		}
+	}
+
+	fn set_paused(&self, paused: bool) -> Result<()> {
+		self.paused.store(paused, Ordering::Release);
+		Ok(())
+	}
+
+	fn is_paused(&self) -> bool {
+		self.paused.load(Ordering::Acquire)
+	}
+
+	fn drain(&self, state: &OperationState, timeout: Duration) -> DrainFuture {
+		if state >= &self.activation_state {
+			return Box::pin(core::future::ready(false));
 		}
+		let Some(handle) = self
+			.handle
+			.lock()
+			.map_or(None, |mut handle| handle.take())
+		else {
+			return Box::pin(core::future::ready(false));
+		};
+		Box::pin(drain_task(
+			handle,
+			self.callback.clone().lock_owned(),
+			timeout,
+		))
 	}
 }
 
@@ -131,191 +277,552 @@ where
 	P: Send + Sync + 'static,
 {
 	/// Constructor for a [Timer]
+	#[allow(clippy::too_many_arguments)]
 	#[must_use]
 	pub fn new(
 		name: String,
 		context: Context<P>,
 		activation_state: OperationState,
 		callback: ArcTimerCallback<P>,
+		errors: ErrorHandler<P>,
+		monitor: TimerMonitor,
+		missed_ticks: MissedTicks,
 		interval: Duration,
 		delay: Option<Duration>,
 	) -> Self {
-		match delay {
-			Some(delay) => Self::DelayedInterval {
-				selector: name,
-				context,
-				activation_state,
-				delay,
-				interval,
-				callback,
-				handle: Mutex::new(None),
-			},
-			None => Self::Interval {
-				selector: name,
-				context,
-				activation_state,
+		let schedule = Schedule::Interval {
+			interval: watch::Sender::new(interval),
+			delay,
+			missed_ticks,
+		};
+		Self::with_schedule(
+			name,
+			context,
+			activation_state,
+			callback,
+			errors,
+			monitor,
+			schedule,
+		)
+	}
+
+	/// Constructor for a one shot [Timer], which fires once after `delay` each time it is started
+	#[must_use]
+	pub fn new_one_shot(
+		name: String,
+		context: Context<P>,
+		activation_state: OperationState,
+		callback: ArcTimerCallback<P>,
+		errors: ErrorHandler<P>,
+		monitor: TimerMonitor,
+		delay: Duration,
+	) -> Self {
+		let schedule = Schedule::OneShot {
+			delay: watch::Sender::new(delay),
+		};
+		Self::with_schedule(
+			name,
+			context,
+			activation_state,
+			callback,
+			errors,
+			monitor,
+			schedule,
+		)
+	}
+
+	/// Constructor for a watchdog [Timer], which fires if it is not kicked within `period`
+	#[must_use]
+	pub fn new_watchdog(
+		name: String,
+		context: Context<P>,
+		activation_state: OperationState,
+		callback: ArcTimerCallback<P>,
+		errors: ErrorHandler<P>,
+		monitor: TimerMonitor,
+		period: Duration,
+	) -> Self {
+		let schedule = Schedule::Watchdog {
+			period: watch::Sender::new(period),
+			kicked: Arc::new(Notify::new()),
+		};
+		Self::with_schedule(
+			name,
+			context,
+			activation_state,
+			callback,
+			errors,
+			monitor,
+			schedule,
+		)
+	}
+
+	/// Constructor for a [Timer] firing at the wall-clock boundaries of `period` plus `offset`,
+	/// e.g. a `period` of 5 minutes fires at :00, :05, :10 and so on.
+	#[allow(clippy::too_many_arguments)]
+	#[must_use]
+	pub fn new_aligned(
+		name: String,
+		context: Context<P>,
+		activation_state: OperationState,
+		callback: ArcTimerCallback<P>,
+		errors: ErrorHandler<P>,
+		monitor: TimerMonitor,
+		period: Duration,
+		offset: Duration,
+	) -> Self {
+		let schedule = Schedule::Aligned {
+			period: watch::Sender::new(period),
+			offset,
+		};
+		Self::with_schedule(
+			name,
+			context,
+			activation_state,
+			callback,
+			errors,
+			monitor,
+			schedule,
+		)
+	}
+
+	/// Constructor for a [Timer] firing according to the cron `schedule`
+	#[must_use]
+	pub fn new_cron(
+		name: String,
+		context: Context<P>,
+		activation_state: OperationState,
+		callback: ArcTimerCallback<P>,
+		errors: ErrorHandler<P>,
+		monitor: TimerMonitor,
+		schedule: CronSchedule,
+	) -> Self {
+		let schedule = Schedule::Cron { schedule };
+		Self::with_schedule(
+			name,
+			context,
+			activation_state,
+			callback,
+			errors,
+			monitor,
+			schedule,
+		)
+	}
+
+	/// Constructor for a [Timer] with the given [`Schedule`]
+	fn with_schedule(
+		name: String,
+		context: Context<P>,
+		activation_state: OperationState,
+		callback: ArcTimerCallback<P>,
+		errors: ErrorHandler<P>,
+		monitor: TimerMonitor,
+		schedule: Schedule,
+	) -> Self {
+		Self {
+			selector: name,
+			context,
+			activation_state,
+			paused: AtomicBool::new(false),
+			callback,
+			errors,
+			monitor,
+			schedule,
+			handle: Mutex::new(None),
+		}
+	}
+
+	/// Get `selector`
+	#[must_use]
+	pub fn selector(&self) -> &str {
+		&self.selector
+	}
+
+	/// The [`Trigger`] firing the timer
+	fn trigger(&self) -> Trigger<P> {
+		Trigger {
+			selector: self.selector.clone(),
+			context: self.context.clone(),
+			callback: self.callback.clone(),
+			errors: self.errors.clone(),
+			monitor: self.monitor.clone(),
+			clock: self.context.clock(),
+		}
+	}
+
+	/// Kick a watchdog timer, so that it does not fire within its next period.
+	/// # Errors
+	/// if the timer is not a watchdog
+	pub fn kick(&self) -> Result<()> {
+		match &self.schedule {
+			Schedule::Watchdog { kicked, .. } => {
+				kicked.notify_one();
+				Ok(())
+			}
+			_ => Err(Error::NoWatchdog(self.selector.clone()).into()),
+		}
+	}
+
+	/// Change the interval of the timer, which is the period of watchdog and aligned timers
+	/// and the delay of one shot timers.
+	/// A running periodic timer fires next one new interval after the change.
+	/// # Errors
+	/// if the interval is zero or the timer is a cron timer
+	pub fn set_interval(&self, interval: Duration) -> Result<()> {
+		if interval.is_zero() {
+			return Err(Error::ZeroInterval(self.selector.clone()).into());
+		}
+		match &self.schedule {
+			Schedule::Interval {
+				interval: value, ..
+			}
+			| Schedule::OneShot { delay: value }
+			| Schedule::Watchdog { period: value, .. }
+			| Schedule::Aligned { period: value, .. } => {
+				value.send_replace(interval);
+				Ok(())
+			}
+			Schedule::Cron { .. } => Err(Error::NoInterval(self.selector.clone()).into()),
+		}
+	}
+
+	/// Fire the timer immediately once, independent of its schedule and whether it is running.
+	/// A panic of the callback restarts the timer only if it is running and not paused.
+	pub fn trigger_now(&self) {
+		let trigger = self.trigger();
+		let signal = TaskSignal::RestartTimer(trigger.selector.clone());
+		let sender = trigger.context.sender().clone();
+		let fire = async move { trigger.fire(None, None).await };
+		if self.is_running() && !self.is_paused() {
+			tokio::task::spawn(supervise(fire, sender, signal));
+		} else {
+			tokio::task::spawn(fire);
+		}
+	}
+
+	/// Whether the task of the timer is running
+	fn is_running(&self) -> bool {
+		self.handle.lock().is_ok_and(|handle| {
+			handle
+				.as_ref()
+				.is_some_and(|handle| !handle.is_finished())
+		})
+	}
+
+	/// Get the execution statistics of the timer
+	#[must_use]
+	pub fn stats(&self) -> TimerStats {
+		self.monitor.stats()
+	}
+
+	/// Describe the timer for introspection
+	#[must_use]
+	pub fn describe(&self) -> CapabilityDescription {
+		let description = CapabilityDescription::new(
+			CapabilityKind::Timer,
+			self.selector.clone(),
+			None,
+			self.activation_state.clone(),
+			self.is_running(),
+			self.is_paused(),
+		)
+		.with_timer_stats(self.stats());
+		let description = match self.monitor.budget() {
+			Some(budget) => description.with_qos("budget", format!("{budget:?}")),
+			None => description,
+		};
+		match &self.schedule {
+			Schedule::Interval {
 				interval,
-				callback,
-				handle: Mutex::new(None),
-			},
+				delay,
+				missed_ticks,
+			} => {
+				let description =
+					description.with_qos("interval", format!("{:?}", *interval.borrow()));
+				let description = match delay {
+					Some(delay) => description.with_qos("delay", format!("{delay:?}")),
+					None => description,
+				};
+				description.with_qos("missed_ticks", format!("{missed_ticks:?}"))
+			}
+			Schedule::OneShot { delay } => description
+				.with_qos("one_shot", "true")
+				.with_qos("delay", format!("{:?}", *delay.borrow())),
+			Schedule::Watchdog { period, .. } => description
+				.with_qos("watchdog", "true")
+				.with_qos("period", format!("{:?}", *period.borrow())),
+			Schedule::Aligned { period, offset } => description
+				.with_qos("aligned", format!("{:?}", *period.borrow()))
+				.with_qos("offset", format!("{offset:?}")),
+			Schedule::Cron { schedule } => description.with_qos("cron", schedule.expression()),
 		}
 	}
 
 	/// Start or restart the timer
 	/// An already running timer will be stopped.
-	#[allow(clippy::cognitive_complexity)]
 	#[instrument(level = Level::TRACE, skip_all)]
 	fn start(&self) -> Result<()> {
 		self.stop()?;
 
-		match self {
-			Self::Interval {
-				selector,
-				context,
-				activation_state: _,
-				interval,
-				callback,
-				handle,
-			} => {
-				// check Mutexes
-				{
-					if callback.lock().is_err() {
-						warn!("found poisoned Mutex");
-						callback.clear_poison();
-					}
-				}
+		let trigger = self.trigger();
+		let signal = TaskSignal::RestartTimer(trigger.selector.clone());
+		let sender = trigger.context.sender().clone();
+		let task = self.task(trigger);
+		self.handle.lock().map_or_else(
+			|_| Err(Error::MutexPoison(String::from("Timer")).into()),
+			|mut handle| {
+				handle.replace(tokio::task::spawn(supervise(task, sender, signal)));
+				Ok(())
+			},
+		)
+	}
 
-				let key = selector.clone();
-				let interval = *interval;
-				let cb = callback.clone();
-				let ctx1 = context.clone();
-				let ctx2 = context.clone();
-
-				handle.lock().map_or_else(
-					|_| todo!(),
-					|mut handle| {
-						handle.replace(tokio::task::spawn(async move {
-							std::panic::set_hook(Box::new(move |reason| {
-								error!("delayed timer panic: {}", reason);
-								if let Err(reason) = ctx1
-									.sender()
-									.blocking_send(TaskSignal::RestartTimer(key.clone()))
-								{
-									error!("could not restart timer: {}", reason);
-								} else {
-									info!("restarting timer!");
-								}
-							}));
-							run_timer(interval, cb, ctx2).await;
-						}));
-						Ok(())
-					},
-				)
-			}
-			Self::DelayedInterval {
-				selector,
-				context,
-				activation_state: _,
-				delay,
+	/// The task running the timer
+	fn task(&self, trigger: Trigger<P>) -> BoxFuture<'static, ()> {
+		match &self.schedule {
+			Schedule::Interval {
+				interval,
+				delay: None,
+				missed_ticks,
+			} => Box::pin(run_timer(interval.subscribe(), *missed_ticks, trigger)),
+			Schedule::Interval {
 				interval,
-				callback,
-				handle,
+				delay: Some(delay),
+				missed_ticks,
 			} => {
-				// check Mutexes
-				{
-					if callback.lock().is_err() {
-						warn!("found poisoned Mutex");
-						callback.clear_poison();
-					}
-				}
-
-				let key = selector.clone();
 				let delay = *delay;
-				let interval = *interval;
-				let cb = callback.clone();
-				let ctx1 = context.clone();
-				let ctx2 = context.clone();
-
-				handle.lock().map_or_else(
-					|_| todo!(),
-					|mut handle| {
-						handle.replace(tokio::task::spawn(async move {
-							std::panic::set_hook(Box::new(move |reason| {
-								error!("delayed timer panic: {}", reason);
-								if let Err(reason) = ctx1
-									.sender()
-									.blocking_send(TaskSignal::RestartTimer(key.clone()))
-								{
-									error!("could not restart timer: {}", reason);
-								} else {
-									info!("restarting timer!");
-								}
-							}));
-							tokio::time::sleep(delay).await;
-							run_timer(interval, cb, ctx2).await;
-						}));
-						Ok(())
-					},
-				)
+				let interval = interval.subscribe();
+				let missed_ticks = *missed_ticks;
+				Box::pin(async move {
+					trigger.clock.sleep(delay).await;
+					run_timer(interval, missed_ticks, trigger).await;
+				})
+			}
+			Schedule::OneShot { delay } => {
+				let scheduled = trigger.clock.monotonic() + *delay.borrow();
+				Box::pin(async move {
+					trigger
+						.clock
+						.sleep_until_monotonic(scheduled)
+						.await;
+					trigger
+						.fire(Some(late(trigger.clock.monotonic(), scheduled)), None)
+						.await;
+				})
 			}
+			Schedule::Watchdog { period, kicked } => {
+				Box::pin(run_watchdog(period.subscribe(), kicked.clone(), trigger))
+			}
+			Schedule::Aligned { period, offset } => {
+				Box::pin(run_aligned(period.subscribe(), *offset, trigger))
+			}
+			Schedule::Cron { schedule } => Box::pin(run_cron(schedule.clone(), trigger)),
 		}
 	}
 
 	/// Stop a running Timer
 	#[instrument(level = Level::TRACE, skip_all)]
 	fn stop(&self) -> Result<()> {
-		match self {
-			Self::Interval {
-				selector: _,
-				context: _,
-				activation_state: _,
-				interval: _,
-				callback: _,
-				handle,
-			}
-			| Self::DelayedInterval {
-				selector: _,
-				context: _,
-				activation_state: _,
-				delay: _,
-				interval: _,
-				callback: _,
-				handle,
-			} => handle.lock().map_or_else(
-				|_| todo!(),
-				|mut handle| {
-					if let Some(handle) = handle.take() {
-						handle.abort();
-					}
-					Ok(())
-				},
-			),
-		}
+		self.handle.lock().map_or_else(
+			|_| Err(Error::MutexPoison(String::from("Timer")).into()),
+			|mut handle| {
+				if let Some(handle) = handle.take() {
+					handle.abort();
+				}
+				Ok(())
+			},
+		)
 	}
 }
 
-#[instrument(name="timer", level = Level::ERROR, skip_all)]
-async fn run_timer<P>(interval: Duration, cb: ArcTimerCallback<P>, ctx: Context<P>)
+/// Everything needed to fire a timer
+struct Trigger<P>
+where
+	P: Send + Sync + 'static,
+{
+	selector: String,
+	context: Context<P>,
+	callback: ArcTimerCallback<P>,
+	errors: ErrorHandler<P>,
+	monitor: TimerMonitor,
+	clock: Arc<dyn Clock>,
+}
+
+impl<P> Trigger<P>
 where
 	P: Send + Sync + 'static,
 {
-	let mut interval = time::interval(interval);
+	/// Call the timers callback, record its execution and handle its result.
+	/// `jitter` is the time the callback is called behind its schedule,
+	/// `period` the time it must not exceed without a budget.
+	async fn fire(&self, jitter: Option<Duration>, period: Option<Duration>) {
+		let started = self.clock.monotonic();
+		let mut lock = self.callback.lock().await;
+		// every firing starts a new trace
+		let result = TraceContext::new_root()
+			.scope(&self.selector, lock(self.context.clone()))
+			.await;
+		drop(lock);
+		let duration = self.clock.monotonic().saturating_sub(started);
+		self.monitor
+			.record(&self.selector, duration, jitter, period);
+		self.errors
+			.handle(&self.context, &self.selector, result);
+	}
+}
+
+#[instrument(name="timer", level = Level::ERROR, skip_all)]
+async fn run_timer<P>(
+	mut interval: watch::Receiver<Duration>,
+	missed_ticks: MissedTicks,
+	trigger: Trigger<P>,
+) where
+	P: Send + Sync + 'static,
+{
+	// scheduling uses the monotonic time, which is not affected by steps of the wall-clock
+	let clock = trigger.clock.clone();
+	let mut period = *interval.borrow_and_update();
+	// the first tick is immediately
+	let mut next = clock.monotonic();
 	loop {
-		let ctx = ctx.clone();
-		interval.tick().await;
+		tokio::select! {
+			() = clock.sleep_until_monotonic(next) => {
+				trigger.fire(Some(late(clock.monotonic(), next)), Some(period)).await;
+				next = next_tick(missed_ticks, next, period, clock.monotonic());
+			}
+			Ok(()) = interval.changed() => {
+				// the next tick is one new interval after the change
+				period = *interval.borrow_and_update();
+				next = clock.monotonic() + period;
+			}
+		}
+	}
+}
 
-		match cb.lock() {
-			Ok(mut cb) => {
-				if let Err(error) = cb(ctx) {
-					error!("callback failed with {error}");
-				}
+#[instrument(name="watchdog", level = Level::ERROR, skip_all)]
+async fn run_watchdog<P>(
+	mut period: watch::Receiver<Duration>,
+	kicked: Arc<Notify>,
+	trigger: Trigger<P>,
+) where
+	P: Send + Sync + 'static,
+{
+	let clock = trigger.clock.clone();
+	loop {
+		let deadline = clock.monotonic() + *period.borrow_and_update();
+		tokio::select! {
+			() = clock.sleep_until_monotonic(deadline) => {
+				trigger.fire(Some(late(clock.monotonic(), deadline)), None).await;
 			}
-			Err(err) => {
-				error!("callback lock failed with {err}");
+			() = kicked.notified() => {}
+			// a changed period starts a new one
+			Ok(()) = period.changed() => {}
+		}
+	}
+}
+
+#[instrument(name="aligned", level = Level::ERROR, skip_all)]
+async fn run_aligned<P>(
+	mut period: watch::Receiver<Duration>,
+	offset: Duration,
+	trigger: Trigger<P>,
+) where
+	P: Send + Sync + 'static,
+{
+	let clock = trigger.clock.clone();
+	let mut next = Duration::ZERO;
+	loop {
+		let current = *period.borrow_and_update();
+		// never fire twice for the same boundary, even if woken up early
+		next = next_boundary(clock.now().max(next), current, offset);
+		tokio::select! {
+			() = clock.sleep_until(next) => {
+				trigger.fire(Some(late(clock.now(), next)), Some(current)).await;
 			}
+			// recalculate the next boundary for the changed period
+			Ok(()) = period.changed() => next = Duration::ZERO,
+		}
+	}
+}
+
+#[instrument(name="cron", level = Level::ERROR, skip_all)]
+async fn run_cron<P>(schedule: CronSchedule, trigger: Trigger<P>)
+where
+	P: Send + Sync + 'static,
+{
+	let clock = trigger.clock.clone();
+	let mut last = clock.now();
+	loop {
+		let Some(next) = date_time(clock.now().max(last))
+			.and_then(|time| schedule.next_after(time))
+			.map(since_epoch)
+		else {
+			warn!(
+				"schedule '{schedule}' of timer {} has no further times",
+				trigger.selector
+			);
+			return;
+		};
+		clock.sleep_until(next).await;
+		last = next;
+		trigger
+			.fire(Some(late(clock.now(), next)), None)
+			.await;
+	}
+}
+
+/// How late `now` is behind `scheduled`
+const fn late(now: Duration, scheduled: Duration) -> Duration {
+	now.saturating_sub(scheduled)
+}
+
+/// The tick following the tick `scheduled` at `now`, which depends on the behaviour on missed ticks
+fn next_tick(
+	missed_ticks: MissedTicks,
+	scheduled: Duration,
+	period: Duration,
+	now: Duration,
+) -> Duration {
+	let next = scheduled + period;
+	if next > now {
+		return next;
+	}
+	match missed_ticks {
+		MissedTicks::Burst => next,
+		MissedTicks::Delay => now + period,
+		MissedTicks::Skip => {
+			let period = period.as_nanos().max(1);
+			let ticks = (now - scheduled).as_nanos() / period + 1;
+			scheduled + Duration::from_nanos(u64::try_from(ticks * period).unwrap_or(u64::MAX))
 		}
 	}
 }
+
+/// The time given as duration since the unix epoch as date and time
+fn date_time(time: Duration) -> Option<DateTime<Utc>> {
+	DateTime::from_timestamp(i64::try_from(time.as_secs()).ok()?, time.subsec_nanos())
+}
+
+/// The date and time as duration since the unix epoch
+fn since_epoch(time: DateTime<Utc>) -> Duration {
+	(time - DateTime::UNIX_EPOCH)
+		.to_std()
+		.unwrap_or_default()
+}
+
+/// The first boundary of `period` shifted by `offset` strictly after `after`,
+/// all values are durations since the unix epoch
+fn next_boundary(after: Duration, period: Duration, offset: Duration) -> Duration {
+	let period = period.as_nanos().max(1);
+	let offset = offset.as_nanos() % period;
+	let after = after.as_nanos();
+	let next = if after < offset {
+		offset
+	} else {
+		((after - offset) / period + 1) * period + offset
+	};
+	Duration::from_nanos(u64::try_from(next).unwrap_or(u64::MAX))
+}
 // endregion:	--- Timer
 
 #[cfg(test)]
@@ -331,5 +838,66 @@ mod tests {
 	#[test]
 	const fn normal_types() {
 		is_normal::<Timer<Props>>();
+		is_normal::<MissedTicks>();
+		is_normal::<TimerMonitor>();
+	}
+
+	#[test]
+	fn ticks() {
+		let period = Duration::from_secs(1);
+		let scheduled = Duration::from_secs(10);
+		// in time
+		let now = Duration::from_millis(10_500);
+		for missed_ticks in [
+			MissedTicks::Burst,
+			MissedTicks::Delay,
+			MissedTicks::Skip,
+		] {
+			assert_eq!(
+				next_tick(missed_ticks, scheduled, period, now),
+				Duration::from_secs(11)
+			);
+		}
+		// too late
+		let now = Duration::from_millis(12_500);
+		assert_eq!(
+			next_tick(MissedTicks::Burst, scheduled, period, now),
+			Duration::from_secs(11)
+		);
+		assert_eq!(
+			next_tick(MissedTicks::Delay, scheduled, period, now),
+			Duration::from_millis(13_500)
+		);
+		assert_eq!(
+			next_tick(MissedTicks::Skip, scheduled, period, now),
+			Duration::from_secs(13)
+		);
+	}
+
+	#[test]
+	fn boundaries() {
+		let second = Duration::from_secs(1);
+		let minute = Duration::from_secs(60);
+		assert_eq!(
+			next_boundary(Duration::from_millis(1500), second, Duration::ZERO),
+			Duration::from_secs(2)
+		);
+		assert_eq!(
+			next_boundary(Duration::from_secs(2), second, Duration::ZERO),
+			Duration::from_secs(3)
+		);
+		assert_eq!(
+			next_boundary(Duration::from_secs(130), minute, Duration::from_secs(15)),
+			Duration::from_secs(135)
+		);
+		assert_eq!(
+			next_boundary(Duration::from_secs(10), minute, Duration::from_secs(15)),
+			Duration::from_secs(15)
+		);
+		// offsets larger than the period wrap around
+		assert_eq!(
+			next_boundary(Duration::from_secs(10), minute, Duration::from_secs(75)),
+			Duration::from_secs(15)
+		);
 	}
 }
@@ -10,20 +10,26 @@ extern crate alloc;
 extern crate std;
 
 // region:		--- modules
-use crate::error::Error;
 use dimas_core::builder_states::{
 	Callback, Interval, NoCallback, NoInterval, NoSelector, NoStorage, Selector, Storage,
 };
+use dimas_core::handle::CapabilityHandle;
 
-use super::{ArcTimerCallback, Timer};
+use super::{ArcTimerCallback, CronSchedule, MissedTicks, Timer, TimerCallback, TimerMonitor};
 
 use alloc::{
+	boxed::Box,
 	format,
 	string::{String, ToString},
 	sync::Arc,
 };
-use core::time::Duration;
-use dimas_core::{Result, enums::OperationState, traits::Context};
+use core::{error::Error, future::Future, time::Duration};
+use dimas_core::{
+	Result,
+	enums::OperationState,
+	policies::{ArcErrorCallback, ErrorCallback, ErrorHandler, ErrorPolicy},
+	traits::Context,
+};
 use std::{
 	collections::HashMap,
 	sync::{Mutex, RwLock},
@@ -31,6 +37,16 @@ use std::{
 // endregion:	--- modules
 
 // region:		--- TimerBuilder
+/// The kinds of [`Timer`]s the [`TimerBuilder`] can build
+#[derive(Clone, Debug)]
+enum Kind {
+	Periodic,
+	OneShot,
+	Watchdog,
+	Aligned,
+	Cron(CronSchedule),
+}
+
 /// A builder for a timer
 #[derive(Clone)]
 pub struct TimerBuilder<P, K, I, C, S>
@@ -44,6 +60,11 @@ where
 	callback: C,
 	storage: S,
 	delay: Option<Duration>,
+	missed_ticks: MissedTicks,
+	budget: Option<Duration>,
+	kind: Kind,
+	error_policy: ErrorPolicy,
+	error_callback: Option<ArcErrorCallback<P>>,
 }
 
 impl<P> TimerBuilder<P, NoSelector, NoInterval, NoCallback, NoStorage>
@@ -61,6 +82,11 @@ where
 			callback: NoCallback,
 			storage: NoStorage,
 			delay: None,
+			missed_ticks: MissedTicks::Burst,
+			budget: None,
+			kind: Kind::Periodic,
+			error_policy: ErrorPolicy::Log,
+			error_callback: None,
 		}
 	}
 }
@@ -82,6 +108,40 @@ where
 		self.delay.replace(delay);
 		self
 	}
+
+	/// Set the behaviour on missed ticks, default is [`MissedTicks::Burst`].
+	#[must_use]
+	pub const fn missed_ticks(mut self, missed_ticks: MissedTicks) -> Self {
+		self.missed_ticks = missed_ticks;
+		self
+	}
+
+	/// Set a budget for the duration of the callback.
+	/// Exceeding it is warned about and counted as overrun in the timers statistics.
+	#[must_use]
+	pub const fn budget(mut self, budget: Duration) -> Self {
+		self.budget.replace(budget);
+		self
+	}
+
+	/// Set the [`ErrorPolicy`] for errors returned by the callback, default is [`ErrorPolicy::Log`].
+	#[must_use]
+	pub const fn error_policy(mut self, policy: ErrorPolicy) -> Self {
+		self.error_policy = policy;
+		self
+	}
+
+	/// Set a callback, which is informed with the timers selector about every error returned by the callback.
+	#[must_use]
+	pub fn on_error<F>(mut self, callback: F) -> Self
+	where
+		F: FnMut(Context<P>, &str, &(dyn Error + Send + Sync)) + Send + Sync + 'static,
+	{
+		let callback: ErrorCallback<P> = Box::new(callback);
+		self.error_callback
+			.replace(Arc::new(Mutex::new(callback)));
+		self
+	}
 }
 
 impl<P, I, C, S> TimerBuilder<P, NoSelector, I, C, S>
@@ -98,6 +158,11 @@ where
 			callback,
 			storage,
 			delay,
+			missed_ticks,
+			budget,
+			kind,
+			error_policy,
+			error_callback,
 			..
 		} = self;
 		TimerBuilder {
@@ -110,6 +175,11 @@ where
 			callback,
 			storage,
 			delay,
+			missed_ticks,
+			budget,
+			kind,
+			error_policy,
+			error_callback,
 		}
 	}
 
@@ -128,6 +198,11 @@ where
 			callback,
 			storage,
 			delay,
+			missed_ticks,
+			budget,
+			kind,
+			error_policy,
+			error_callback,
 			..
 		} = self;
 		TimerBuilder {
@@ -138,6 +213,11 @@ where
 			callback,
 			storage,
 			delay,
+			missed_ticks,
+			budget,
+			kind,
+			error_policy,
+			error_callback,
 		}
 	}
 }
@@ -156,6 +236,11 @@ where
 			callback,
 			storage,
 			delay,
+			missed_ticks,
+			budget,
+			kind,
+			error_policy,
+			error_callback,
 			..
 		} = self;
 		TimerBuilder {
@@ -166,19 +251,83 @@ where
 			callback,
 			storage,
 			delay,
+			missed_ticks,
+			budget,
+			kind,
+			error_policy,
+			error_callback,
 		}
 	}
 }
 
+impl<P, K, C, S> TimerBuilder<P, K, NoInterval, C, S>
+where
+	P: Send + Sync + 'static,
+{
+	/// Build a one shot timer, which fires once after `delay` each time it is started.
+	/// The settings for `delay` and missed ticks are ignored.
+	#[must_use]
+	pub fn one_shot(self, delay: Duration) -> TimerBuilder<P, K, Interval, C, S> {
+		let mut builder = self.interval(delay);
+		builder.kind = Kind::OneShot;
+		builder
+	}
+
+	/// Build a watchdog timer, which fires if it is not kicked within `period`.
+	/// A watchdog is kicked via its [`Timer::kick()`] method or the contexts `kick()` method.
+	/// The settings for `delay` and missed ticks are ignored.
+	#[must_use]
+	pub fn watchdog(self, period: Duration) -> TimerBuilder<P, K, Interval, C, S> {
+		let mut builder = self.interval(period);
+		builder.kind = Kind::Watchdog;
+		builder
+	}
+
+	/// Build a timer, which fires at the wall-clock boundaries of `period` counted from the unix epoch,
+	/// e.g. `Duration::from_secs(300)` fires every 5 minutes at :00, :05, :10 and so on.
+	/// A `delay` shifts the firing times behind the boundaries, the setting for missed ticks is ignored.
+	#[must_use]
+	pub fn aligned(self, period: Duration) -> TimerBuilder<P, K, Interval, C, S> {
+		let mut builder = self.interval(period);
+		builder.kind = Kind::Aligned;
+		builder
+	}
+
+	/// Build a timer, which fires according to a cron `schedule`, which is evaluated in UTC.
+	/// The settings for `delay` and missed ticks are ignored.
+	#[must_use]
+	pub fn cron(self, schedule: CronSchedule) -> TimerBuilder<P, K, Interval, C, S> {
+		let mut builder = self.interval(Duration::ZERO);
+		builder.kind = Kind::Cron(schedule);
+		builder
+	}
+}
+
 impl<P, K, I, S> TimerBuilder<P, K, I, NoCallback, S>
 where
 	P: Send + Sync + 'static,
 {
 	/// Set interval callback for timer
 	#[must_use]
-	pub fn callback<F>(self, callback: F) -> TimerBuilder<P, K, I, Callback<ArcTimerCallback<P>>, S>
+	pub fn callback<F>(
+		self,
+		mut callback: F,
+	) -> TimerBuilder<P, K, I, Callback<ArcTimerCallback<P>>, S>
 	where
 		F: FnMut(Context<P>) -> Result<()> + Send + Sync + 'static,
+	{
+		self.async_callback(move |ctx| core::future::ready(callback(ctx)))
+	}
+
+	/// Set asynchronous interval callback for timer
+	#[must_use]
+	pub fn async_callback<CB, F>(
+		self,
+		mut callback: CB,
+	) -> TimerBuilder<P, K, I, Callback<ArcTimerCallback<P>>, S>
+	where
+		CB: FnMut(Context<P>) -> F + Send + Sync + 'static,
+		F: Future<Output = Result<()>> + Send + Sync + 'static,
 	{
 		let Self {
 			context,
@@ -187,9 +336,15 @@ where
 			interval,
 			storage,
 			delay,
+			missed_ticks,
+			budget,
+			kind,
+			error_policy,
+			error_callback,
 			..
 		} = self;
-		let callback: ArcTimerCallback<P> = Arc::new(Mutex::new(callback));
+		let callback: TimerCallback<P> = Box::new(move |ctx| Box::pin(callback(ctx)));
+		let callback: ArcTimerCallback<P> = Arc::new(tokio::sync::Mutex::new(callback));
 		TimerBuilder {
 			context,
 			activation_state,
@@ -198,6 +353,11 @@ where
 			callback: Callback { callback },
 			storage,
 			delay,
+			missed_ticks,
+			budget,
+			kind,
+			error_policy,
+			error_callback,
 		}
 	}
 }
@@ -219,6 +379,11 @@ where
 			interval,
 			callback,
 			delay,
+			missed_ticks,
+			budget,
+			kind,
+			error_policy,
+			error_callback,
 			..
 		} = self;
 		TimerBuilder {
@@ -229,6 +394,11 @@ where
 			callback,
 			storage: Storage { storage },
 			delay,
+			missed_ticks,
+			budget,
+			kind,
+			error_policy,
+			error_callback,
 		}
 	}
 }
@@ -239,7 +409,7 @@ where
 {
 	/// Build the [Timer]
 	/// # Errors
-	///
+	/// if the interval of a periodic, watchdog or aligned timer is zero
 	pub fn build(self) -> Result<Timer<P>> {
 		let Self {
 			context,
@@ -248,17 +418,73 @@ where
 			interval,
 			callback,
 			delay,
+			missed_ticks,
+			budget,
+			kind,
+			error_policy,
+			error_callback,
 			..
 		} = self;
 
-		Ok(Timer::new(
-			name.selector,
-			context,
-			activation_state,
-			callback.callback,
-			interval.interval,
-			delay,
-		))
+		if interval.interval.is_zero()
+			&& matches!(kind, Kind::Periodic | Kind::Watchdog | Kind::Aligned)
+		{
+			return Err(crate::error::Error::ZeroInterval(name.selector).into());
+		}
+
+		let errors = ErrorHandler::new(error_policy, error_callback);
+		let monitor = TimerMonitor::new(budget);
+		let timer = match kind {
+			Kind::Periodic => Timer::new(
+				name.selector,
+				context,
+				activation_state,
+				callback.callback,
+				errors,
+				monitor,
+				missed_ticks,
+				interval.interval,
+				delay,
+			),
+			Kind::OneShot => Timer::new_one_shot(
+				name.selector,
+				context,
+				activation_state,
+				callback.callback,
+				errors,
+				monitor,
+				interval.interval,
+			),
+			Kind::Watchdog => Timer::new_watchdog(
+				name.selector,
+				context,
+				activation_state,
+				callback.callback,
+				errors,
+				monitor,
+				interval.interval,
+			),
+			Kind::Aligned => Timer::new_aligned(
+				name.selector,
+				context,
+				activation_state,
+				callback.callback,
+				errors,
+				monitor,
+				interval.interval,
+				delay.unwrap_or_default(),
+			),
+			Kind::Cron(schedule) => Timer::new_cron(
+				name.selector,
+				context,
+				activation_state,
+				callback.callback,
+				errors,
+				monitor,
+				schedule,
+			),
+		};
+		Ok(timer)
 	}
 }
 
@@ -267,18 +493,28 @@ where
 	P: Send + Sync + 'static,
 {
 	/// Build and add the timer to the agents context
+	/// Returns a [`CapabilityHandle`] to remove, pause or resume it later.
 	/// # Errors
-	///
-	pub fn add(self) -> Result<Option<Timer<P>>> {
+	/// if a capability with the same selector is already registered
+	pub fn add(self) -> Result<CapabilityHandle<P, Timer<P>>> {
 		let name = self.selector.selector.clone();
 		let collection = self.storage.storage.clone();
+		let context = self.context.clone();
 		let t = self.build()?;
+		CapabilityHandle::register(name, t, context, collection)
+	}
 
-		let r = collection
-			.write()
-			.map_err(|_| Error::MutexPoison(String::from("TimerBuilder")))?
-			.insert(name, t);
-		Ok(r)
+	/// Build and add the timer to the agents context
+	/// An already registered one with the same selector is stopped and replaced.
+	/// Returns a [`CapabilityHandle`] to remove, pause or resume it later.
+	/// # Errors
+	/// if the storage is not accessible
+	pub fn replace(self) -> Result<CapabilityHandle<P, Timer<P>>> {
+		let name = self.selector.selector.clone();
+		let collection = self.storage.storage.clone();
+		let context = self.context.clone();
+		let t = self.build()?;
+		CapabilityHandle::replace(name, t, context, collection)
 	}
 }
 // endregion:	--- TimerBuilder
@@ -41,13 +41,17 @@
 //! }
 //! ```
 //!
-//! A running agent can be properly stopped with `ctrl-c`
+//! A running agent can be properly stopped with `ctrl-c` or `SIGTERM`.
+//! On unix platforms `SIGHUP` calls the `on_reload` hook
+//! and `SIGUSR1` logs the registered capabilities.
 //!
 
 // region:		--- modules
 use crate::context::ContextImpl;
 use crate::error::Error;
-use chrono::Local;
+use crate::lifecycle::{ArcLifecycleCallback, ArcTransitionGuard, LifecycleHooks};
+use crate::parameters::ParameterServerBuilder;
+use crate::signals::{OsSignal, OsSignals};
 use core::{fmt::Debug, time::Duration};
 use dimas_com::builder::LivelinessSubscriberBuilder;
 use dimas_com::builder::{
@@ -56,35 +60,49 @@ use dimas_com::builder::{
 };
 use dimas_com::traits::LivelinessSubscriber;
 use dimas_com::traits::{Observer, Publisher, Querier, Responder};
-use dimas_commands::messages::{AboutEntity, PingEntity};
+use dimas_commands::messages::{AboutEntity, CapabilitiesEntity, PingEntity, StateEntity};
 use dimas_config::Config;
 use dimas_core::{
 	Result,
 	builder_states::{NoCallback, NoInterval, NoSelector, Storage},
-	enums::{OperationState, Signal, TaskSignal},
-	message_types::{Message, QueryMsg},
-	traits::{Capability, Context, ContextAbstraction},
+	enums::{CapabilityKind, OperationState, Signal, TaskSignal},
+	message_types::{CapabilityDescription, Message, QueryMsg},
+	policies::RestartPolicy,
+	traits::{Clock, Context, ContextAbstraction},
 };
-use dimas_time::{Timer, TimerBuilder};
+use dimas_time::{SystemClock, Timer, TimerBuilder};
+use serde::{Serialize, de::DeserializeOwned};
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::sync::RwLock;
-use tokio::{select, signal, sync::mpsc};
+use std::sync::{Mutex, RwLock};
+use tokio::{select, sync::mpsc, time::Instant};
 use tracing::{error, info, warn};
 use zenoh::liveliness::LivelinessToken;
 // endregion:	--- modules
 
+// region:	   --- types
+/// Default maximum time for the tasks to finish their work on shutdown
+const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+// endregion:   --- types
+
 // region:	   --- callbacks
 async fn callback_dispatcher<P>(ctx: Context<P>, request: QueryMsg) -> Result<()>
 where
 	P: Send + Sync + 'static,
 {
+	// the queryable for the fully qualified name also receives requests for its siblings
+	if !request.key_expr().as_str().ends_with("/signal") {
+		return Ok(());
+	}
 	if let Some(value) = request.payload() {
 		let content: Vec<u8> = value.to_bytes().into_owned();
 		let msg = Message::new(content);
 		let signal: Signal = Message::decode(msg)?;
 		match signal {
 			Signal::About => about_handler(ctx, request)?,
+			Signal::Capabilities => capabilities_handler(ctx, request)?,
 			Signal::Ping { sent } => ping_handler(ctx, request, sent)?,
+			Signal::Recover { state } => recover_handler(ctx, request, state)?,
 			Signal::Shutdown => shutdown_handler(ctx, request)?,
 			Signal::State { state } => state_handler(ctx, request, state)?,
 		}
@@ -92,7 +110,7 @@ where
 	Ok(())
 }
 
-fn about_handler<P>(ctx: Context<P>, request: QueryMsg) -> Result<()>
+fn about_entity<P>(ctx: &Context<P>) -> AboutEntity
 where
 	P: Send + Sync + 'static,
 {
@@ -102,7 +120,30 @@ where
 	let mode = ctx.mode().clone();
 	let zid = ctx.uuid();
 	let state = ctx.state();
-	let value = AboutEntity::new(name, mode, zid, state);
+	let error = ctx.last_error();
+	AboutEntity::new(name, mode, zid, state, error)
+}
+
+fn about_handler<P>(ctx: Context<P>, request: QueryMsg) -> Result<()>
+where
+	P: Send + Sync + 'static,
+{
+	let value = about_entity(&ctx);
+	drop(ctx);
+	request.reply(value)?;
+	Ok(())
+}
+
+fn capabilities_handler<P>(ctx: Context<P>, request: QueryMsg) -> Result<()>
+where
+	P: Send + Sync + 'static,
+{
+	let name = ctx
+		.fq_name()
+		.unwrap_or_else(|| String::from("--"));
+	let zid = ctx.uuid();
+	let capabilities = ctx.describe_capabilities()?;
+	let value = CapabilitiesEntity::new(name, zid, capabilities);
 	drop(ctx);
 	request.reply(value)?;
 	Ok(())
@@ -112,11 +153,7 @@ fn ping_handler<P>(ctx: Context<P>, request: QueryMsg, sent: i64) -> Result<()>
 where
 	P: Send + Sync + 'static,
 {
-	let now = Local::now()
-		.naive_utc()
-		.and_utc()
-		.timestamp_nanos_opt()
-		.unwrap_or(0);
+	let now = ctx.clock().timestamp();
 
 	let name = ctx
 		.fq_name()
@@ -128,27 +165,33 @@ where
 	Ok(())
 }
 
+fn recover_handler<P>(ctx: Context<P>, request: QueryMsg, state: OperationState) -> Result<()>
+where
+	P: Send + Sync + 'static,
+{
+	// a failing recovery is reported back to the requester
+	let rejection = ctx.recover(state).err().map(|error| {
+		warn!("recovery failed: {error}");
+		error.to_string()
+	});
+
+	// send back result
+	let value = state_entity(&ctx, rejection);
+	drop(ctx);
+	request.reply(value)?;
+	Ok(())
+}
+
 fn shutdown_handler<P>(ctx: Context<P>, request: QueryMsg) -> Result<()>
 where
 	P: Send + Sync + 'static,
 {
 	// send back current infos
-	let name = ctx
-		.fq_name()
-		.unwrap_or_else(|| String::from("--"));
-	let mode = ctx.mode().clone();
-	let zid = ctx.uuid();
-	let state = ctx.state();
-	let value = AboutEntity::new(name, mode, zid, state);
+	let value = about_entity(&ctx);
 	request.reply(value)?;
 
-	// shutdown agent after a short wait time to be able to send response
+	// the agent gracefully ends its tasks, the signal queryables stay available until the end
 	tokio::task::spawn(async move {
-		tokio::time::sleep(Duration::from_millis(10)).await;
-		// gracefully end agent
-		let _ = ctx.set_state(OperationState::Standby);
-		tokio::time::sleep(Duration::from_millis(100)).await;
-		let _ = ctx.set_state(OperationState::Created);
 		let _ = ctx.sender().send(TaskSignal::Shutdown).await;
 	});
 	Ok(())
@@ -159,21 +202,32 @@ where
 	P: Send + Sync + 'static,
 {
 	// is a state value given?
-	if let Some(value) = state {
-		let _ = ctx.set_state(value);
-	}
+	// a failing change is reported back to the requester
+	let rejection = state.and_then(|value| {
+		ctx.set_state(value).err().map(|error| {
+			warn!("{error}");
+			error.to_string()
+		})
+	});
 
 	// send back result
+	let value = state_entity(&ctx, rejection);
+	drop(ctx);
+	request.reply(value)?;
+	Ok(())
+}
+
+fn state_entity<P>(ctx: &Context<P>, rejection: Option<String>) -> StateEntity
+where
+	P: Send + Sync + 'static,
+{
 	let name = ctx
 		.fq_name()
 		.unwrap_or_else(|| String::from("--"));
 	let mode = ctx.mode().clone();
 	let zid = ctx.uuid();
 	let state = ctx.state();
-	let value = AboutEntity::new(name, mode, zid, state);
-	drop(ctx);
-	request.reply(value)?;
-	Ok(())
+	StateEntity::new(name, mode, zid, state, rejection)
 }
 // endregion:	--- callbacks
 
@@ -188,6 +242,8 @@ where
 	name: Option<String>,
 	prefix: Option<String>,
 	props: P,
+	hooks: LifecycleHooks<P>,
+	clock: Option<Arc<dyn Clock>>,
 }
 
 impl<P> UnconfiguredAgent<P>
@@ -200,6 +256,8 @@ where
 			name: None,
 			prefix: None,
 			props: properties,
+			hooks: LifecycleHooks::new(),
+			clock: None,
 		}
 	}
 
@@ -217,6 +275,106 @@ where
 		self
 	}
 
+	/// Set the [`Clock`] used for timers, pings and timestamps, default is the [`SystemClock`].
+	/// A [`SimulatedClock`](dimas_time::SimulatedClock) allows deterministic tests and running in lockstep with a simulator.
+	#[must_use]
+	pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+		self.clock = Some(clock);
+		self
+	}
+
+	/// Set a hook called on transition from `Created` to `Configured`.
+	/// An error returned by the hook stops the transition.
+	#[must_use]
+	pub fn on_configure<F>(mut self, callback: F) -> Self
+	where
+		F: FnMut(Context<P>) -> Result<()> + Send + Sync + 'static,
+	{
+		self.hooks.configure = Some(Arc::new(Mutex::new(callback)));
+		self
+	}
+
+	/// Set a hook called on transition from `Standby` to `Active`.
+	/// An error returned by the hook stops the transition.
+	#[must_use]
+	pub fn on_activate<F>(mut self, callback: F) -> Self
+	where
+		F: FnMut(Context<P>) -> Result<()> + Send + Sync + 'static,
+	{
+		self.hooks.activate = Some(Arc::new(Mutex::new(callback)));
+		self
+	}
+
+	/// Set a hook called on transition from `Active` to `Standby`.
+	/// An error returned by the hook stops the transition.
+	#[must_use]
+	pub fn on_deactivate<F>(mut self, callback: F) -> Self
+	where
+		F: FnMut(Context<P>) -> Result<()> + Send + Sync + 'static,
+	{
+		self.hooks.deactivate = Some(Arc::new(Mutex::new(callback)));
+		self
+	}
+
+	/// Set a hook called on transition from `Configured` to `Created`.
+	/// An error returned by the hook stops the transition.
+	#[must_use]
+	pub fn on_cleanup<F>(mut self, callback: F) -> Self
+	where
+		F: FnMut(Context<P>) -> Result<()> + Send + Sync + 'static,
+	{
+		self.hooks.cleanup = Some(Arc::new(Mutex::new(callback)));
+		self
+	}
+
+	/// Set a hook called when entering the state `Error`.
+	#[must_use]
+	pub fn on_error<F>(mut self, callback: F) -> Self
+	where
+		F: FnMut(Context<P>) -> Result<()> + Send + Sync + 'static,
+	{
+		self.hooks.error = Some(Arc::new(Mutex::new(callback)));
+		self
+	}
+
+	/// Set a hook called when the running agent receives `SIGHUP`,
+	/// e.g. for re-reading the configuration.
+	#[must_use]
+	pub fn on_reload<F>(mut self, callback: F) -> Self
+	where
+		F: FnMut(Context<P>) -> Result<()> + Send + Sync + 'static,
+	{
+		self.hooks.reload = Some(Arc::new(Mutex::new(callback)));
+		self
+	}
+
+	/// Set a hook called by the running agent after its properties have been written,
+	/// e.g. for adjusting timers and subscribers to a changed configuration.
+	/// Several writes in short succession may result in a single call,
+	/// writes done by the hook itself do not call it again.
+	/// The hook gets neither the old nor the new values, for comparing them
+	/// keep a copy and watch the writes via `Context::watch_props()` instead.
+	#[must_use]
+	pub fn on_props_changed<F>(mut self, callback: F) -> Self
+	where
+		F: FnMut(Context<P>) -> Result<()> + Send + Sync + 'static,
+	{
+		self.hooks.props_changed = Some(Arc::new(Mutex::new(callback)));
+		self
+	}
+
+	/// Add a guard, which is asked before a requested state change.
+	/// The guard gets the current and the requested [`OperationState`],
+	/// an error returned by the guard vetoes the change and is reported as reason.
+	#[must_use]
+	pub fn transition_guard<F>(mut self, guard: F) -> Self
+	where
+		F: Fn(Context<P>, &OperationState, &OperationState) -> Result<()> + Send + Sync + 'static,
+	{
+		self.hooks.guards.push(Arc::new(guard));
+		self
+	}
+
 	/// Set the [`Config`]uration.
 	/// An agent with [`OperationState`] `Configured` can be started
 	/// and will respond to commands from dimasctl/dimasmon
@@ -232,6 +390,9 @@ where
 			self.name,
 			tx,
 			self.prefix,
+			self.hooks,
+			self.clock
+				.unwrap_or_else(|| Arc::new(SystemClock)),
 		)?);
 
 		let agent = Agent {
@@ -239,6 +400,8 @@ where
 			context,
 			liveliness: false,
 			liveliness_token: RwLock::new(None),
+			drain_timeout: DEFAULT_DRAIN_TIMEOUT,
+			restart_policy: RestartPolicy::default(),
 		};
 
 		// add signal queryables
@@ -285,6 +448,10 @@ where
 	/// The liveliness token - typically the uuid sent to other participants.
 	/// Is available in the [`LivelinessSubscriber`] callback
 	liveliness_token: RwLock<Option<LivelinessToken>>,
+	/// Maximum time for the tasks to finish their work on shutdown
+	drain_timeout: Duration,
+	/// The policy for restarting panicked tasks
+	restart_policy: RestartPolicy,
 }
 
 impl<P> Debug for Agent<P>
@@ -321,6 +488,138 @@ where
 		self.liveliness = activate;
 	}
 
+	/// Set the maximum time the tasks get to finish their current work on shutdown.
+	/// Tasks still running after that time are aborted.
+	/// Default is 1 second.
+	pub const fn drain_timeout(&mut self, timeout: Duration) {
+		self.drain_timeout = timeout;
+	}
+
+	/// Set the policy for restarting a capability whose task panicked.
+	/// Default is [`RestartPolicy::default()`].
+	pub const fn restart_policy(&mut self, policy: RestartPolicy) {
+		self.restart_policy = policy;
+	}
+
+	/// Set a hook called on transition from `Created` to `Configured`.
+	/// An error returned by the hook stops the transition.
+	/// # Errors
+	/// if the hooks can not be modified
+	pub fn on_configure<F>(&self, callback: F) -> Result<()>
+	where
+		F: FnMut(Context<P>) -> Result<()> + Send + Sync + 'static,
+	{
+		self.set_hook(|hooks| &mut hooks.configure, Arc::new(Mutex::new(callback)))
+	}
+
+	/// Set a hook called on transition from `Standby` to `Active`.
+	/// An error returned by the hook stops the transition.
+	/// # Errors
+	/// if the hooks can not be modified
+	pub fn on_activate<F>(&self, callback: F) -> Result<()>
+	where
+		F: FnMut(Context<P>) -> Result<()> + Send + Sync + 'static,
+	{
+		self.set_hook(|hooks| &mut hooks.activate, Arc::new(Mutex::new(callback)))
+	}
+
+	/// Set a hook called on transition from `Active` to `Standby`.
+	/// An error returned by the hook stops the transition.
+	/// # Errors
+	/// if the hooks can not be modified
+	pub fn on_deactivate<F>(&self, callback: F) -> Result<()>
+	where
+		F: FnMut(Context<P>) -> Result<()> + Send + Sync + 'static,
+	{
+		self.set_hook(
+			|hooks| &mut hooks.deactivate,
+			Arc::new(Mutex::new(callback)),
+		)
+	}
+
+	/// Set a hook called on transition from `Configured` to `Created`.
+	/// An error returned by the hook stops the transition.
+	/// # Errors
+	/// if the hooks can not be modified
+	pub fn on_cleanup<F>(&self, callback: F) -> Result<()>
+	where
+		F: FnMut(Context<P>) -> Result<()> + Send + Sync + 'static,
+	{
+		self.set_hook(|hooks| &mut hooks.cleanup, Arc::new(Mutex::new(callback)))
+	}
+
+	/// Set a hook called when entering the state `Error`.
+	/// # Errors
+	/// if the hooks can not be modified
+	pub fn on_error<F>(&self, callback: F) -> Result<()>
+	where
+		F: FnMut(Context<P>) -> Result<()> + Send + Sync + 'static,
+	{
+		self.set_hook(|hooks| &mut hooks.error, Arc::new(Mutex::new(callback)))
+	}
+
+	/// Set a hook called when the running agent receives `SIGHUP`,
+	/// e.g. for re-reading the configuration.
+	/// # Errors
+	/// if the hooks can not be modified
+	pub fn on_reload<F>(&self, callback: F) -> Result<()>
+	where
+		F: FnMut(Context<P>) -> Result<()> + Send + Sync + 'static,
+	{
+		self.set_hook(|hooks| &mut hooks.reload, Arc::new(Mutex::new(callback)))
+	}
+
+	/// Set a hook called by the running agent after its properties have been written,
+	/// e.g. for adjusting timers and subscribers to a changed configuration.
+	/// Several writes in short succession may result in a single call,
+	/// writes done by the hook itself do not call it again.
+	/// The hook gets neither the old nor the new values, for comparing them
+	/// keep a copy and watch the writes via `Context::watch_props()` instead.
+	/// # Errors
+	/// if the hooks can not be modified
+	pub fn on_props_changed<F>(&self, callback: F) -> Result<()>
+	where
+		F: FnMut(Context<P>) -> Result<()> + Send + Sync + 'static,
+	{
+		self.set_hook(
+			|hooks| &mut hooks.props_changed,
+			Arc::new(Mutex::new(callback)),
+		)
+	}
+
+	/// Add a guard, which is asked before a requested state change.
+	/// The guard gets the current and the requested [`OperationState`],
+	/// an error returned by the guard vetoes the change and is reported as reason.
+	/// # Errors
+	/// if the hooks can not be modified
+	pub fn transition_guard<F>(&self, guard: F) -> Result<()>
+	where
+		F: Fn(Context<P>, &OperationState, &OperationState) -> Result<()> + Send + Sync + 'static,
+	{
+		let guard: ArcTransitionGuard<P> = Arc::new(guard);
+		self.context
+			.hooks()
+			.write()
+			.map_err(|_| Error::ModifyStruct("hooks".into()))?
+			.guards
+			.push(guard);
+		Ok(())
+	}
+
+	/// Store a hook into the selected slot of the contexts [`LifecycleHooks`]
+	fn set_hook(
+		&self,
+		slot: impl FnOnce(&mut LifecycleHooks<P>) -> &mut Option<ArcLifecycleCallback<P>>,
+		callback: ArcLifecycleCallback<P>,
+	) -> Result<()> {
+		let hooks = self.context.hooks();
+		let mut hooks = hooks
+			.write()
+			.map_err(|_| Error::ModifyStruct("hooks".into()))?;
+		slot(&mut hooks).replace(callback);
+		Ok(())
+	}
+
 	/// Get a [`LivelinessSubscriberBuilder`], the builder for a `LivelinessSubscriber`.
 	#[must_use]
 	pub fn liveliness_subscriber(
@@ -458,9 +757,31 @@ where
 		TimerBuilder::new(self.context.clone()).storage(self.context.timers())
 	}
 
+	/// Get the kind and selector of all registered capabilities.
+	/// # Errors
+	/// if a storage is not accessible
+	pub fn capabilities(&self) -> Result<Vec<(CapabilityKind, String)>> {
+		self.context.capabilities()
+	}
+
+	/// Get a description of all registered capabilities.
+	/// # Errors
+	/// if a storage is not accessible
+	pub fn describe_capabilities(&self) -> Result<Vec<CapabilityDescription>> {
+		self.context.describe_capabilities()
+	}
+
+	/// Stop the capability of the given `kind` with the `selector` and remove it.
+	/// Returns `false`, if there is no such capability.
+	/// # Errors
+	/// if a storage is not accessible
+	pub fn remove_capability(&self, kind: CapabilityKind, selector: &str) -> Result<bool> {
+		self.context.remove_capability(kind, selector)
+	}
+
 	/// Start the agent.
 	///
-	/// The agent can be stopped properly using `ctrl-c`
+	/// The agent can be stopped properly using `ctrl-c` or `SIGTERM`
 	///
 	/// # Errors
 	///
@@ -495,11 +816,26 @@ where
 			context: self.context,
 			liveliness: self.liveliness,
 			liveliness_token: self.liveliness_token,
+			drain_timeout: self.drain_timeout,
+			restart_policy: self.restart_policy,
+			restarts: HashMap::new(),
 		}
 		.run()
 		.await
 	}
 }
+
+impl<P> Agent<P>
+where
+	P: Debug + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	/// Get a [`ParameterServerBuilder`], the builder for the parameter server,
+	/// which gives remote access to the fields of the agents properties.
+	#[must_use]
+	pub fn parameters(&self) -> ParameterServerBuilder<P> {
+		ParameterServerBuilder::new(self.context.clone(), self.context.responders())
+	}
+}
 // endregion:   --- Agent
 
 // region:	   --- RunningAgent
@@ -518,6 +854,12 @@ where
 	/// The liveliness token - typically the uuid sent to other participants.
 	/// Is available in the [`LivelinessSubscriber`] callback
 	liveliness_token: RwLock<Option<LivelinessToken>>,
+	/// Maximum time for the tasks to finish their work on shutdown
+	drain_timeout: Duration,
+	/// The policy for restarting panicked tasks
+	restart_policy: RestartPolicy,
+	/// The number of restarts per capability and the begin of their counting
+	restarts: HashMap<(CapabilityKind, String), (u32, Instant)>,
 }
 
 impl<P> RunningAgent<P>
@@ -526,6 +868,8 @@ where
 {
 	/// run
 	async fn run(mut self) -> Result<Agent<P>> {
+		let mut os_signals = OsSignals::new();
+		let mut props_changed = self.context.watch_props();
 		loop {
 			// different possibilities that can happen
 			select! {
@@ -533,88 +877,138 @@ where
 				Some(signal) = self.rx.recv() => {
 					match signal {
 						TaskSignal::RestartLiveliness(selector) => {
-							self.context.liveliness_subscribers()
-								.write()
-								.map_err(|_| Error::WriteAccess)?
-								.get_mut(&selector)
-								.ok_or_else(|| Error::GetMut("liveliness".into()))?
-								.manage_operation_state(&self.context.state())?;
+							self.restart(CapabilityKind::LivelinessSubscriber, selector);
 						},
 						TaskSignal::RestartQueryable(selector) => {
-							self.context.responders()
-								.write()
-								.map_err(|_| Error::WriteAccess)?
-								.get_mut(&selector)
-								.ok_or_else(|| Error::GetMut("queryables".into()))?
-								.manage_operation_state(&self.context.state())?;
+							self.restart(CapabilityKind::Queryable, selector);
 						},
 						TaskSignal::RestartObservable(selector) => {
-							self.context.responders()
-								.write()
-								.map_err(|_| Error::WriteAccess)?
-								.get_mut(&selector)
-								.ok_or_else(|| Error::GetMut("observables".into()))?
-								.manage_operation_state(&self.context.state())?;
+							self.restart(CapabilityKind::Observable, selector);
 						},
 						TaskSignal::RestartSubscriber(selector) => {
-							self.context.responders()
-								.write()
-								.map_err(|_| Error::WriteAccess)?
-								.get_mut(&selector)
-								.ok_or_else(|| Error::GetMut("subscribers".into()))?
-								.manage_operation_state(&self.context.state())?;
+							self.restart(CapabilityKind::Subscriber, selector);
 						},
 						TaskSignal::RestartTimer(selector) => {
-							self.context.timers()
-								.write()
-								.map_err(|_| Error::WriteAccess)?
-								.get_mut(&selector)
-								.ok_or_else(|| Error::GetMut("timers".into()))?
-								.manage_operation_state(&self.context.state())?;
+							self.restart(CapabilityKind::Timer, selector);
+						},
+						TaskSignal::Unregistered(selector) => {
+							self.forget_restarts(&selector);
 						},
 						TaskSignal::Shutdown => {
-							return self.stop();
+							return self.stop().await;
 						}
 					}
 				}
 
-				// shutdown signal "ctrl-c"
-				signal = signal::ctrl_c() => {
-					match signal {
-						Ok(()) => {
-							info!("shutdown due to 'ctrl-c'");
-							return self.stop();
-						}
-						Err(err) => {
-							error!("Unable to listen for 'Ctrl-C': {err}");
-							// we also try to shut down the agent properly
-							return self.stop();
-						}
+				// operating system signals
+				signal = os_signals.recv() => {
+					if signal.is_shutdown() {
+						info!("shutdown due to '{signal}'");
+						return self.stop().await;
+					}
+					self.handle_os_signal(signal);
+				}
+
+				// written properties
+				Ok(()) = props_changed.changed() => {
+					if let Err(error) = self.context.props_changed() {
+						warn!("{error}");
 					}
 				}
 			}
 		}
 	}
 
-	/// Stop the agent
+	/// Restart a capability whose task panicked according to the [`RestartPolicy`]
+	fn restart(&mut self, kind: CapabilityKind, selector: String) {
+		let now = Instant::now();
+		let period = self.restart_policy.period;
+		// the counting begins again after the period
+		self.restarts
+			.retain(|_, (_, since)| now.duration_since(*since) <= period);
+		let (restarts, _) = self
+			.restarts
+			.entry((kind, selector.clone()))
+			.or_insert((0, now));
+		let Some(delay) = self.restart_policy.delay(*restarts) else {
+			let cause = format!("{kind} '{selector}' failed after {restarts} restarts");
+			error!("{cause}");
+			if self.restart_policy.escalate {
+				if let Err(err) = self.context.set_error(cause) {
+					error!("{err}");
+				}
+			}
+			return;
+		};
+		*restarts += 1;
+		info!("restarting {kind} '{selector}' in {delay:?}");
+		let context = self.context.clone();
+		tokio::spawn(async move {
+			tokio::time::sleep(delay).await;
+			if let Err(err) = context.restart_capability(kind, &selector) {
+				error!("could not restart {kind} '{selector}': {err}");
+			}
+		});
+	}
+
+	/// Forget the restarts of a removed or replaced capability,
+	/// a capability added later with the same selector starts counting anew
+	fn forget_restarts(&mut self, selector: &str) {
+		self.restarts
+			.retain(|(_, key), _| key != selector);
+	}
+
+	/// Handle the [`OsSignal`]s not stopping the agent
+	fn handle_os_signal(&self, signal: OsSignal) {
+		let result = match signal {
+			OsSignal::Reload => {
+				info!("reload due to 'SIGHUP'");
+				self.context.reload()
+			}
+			OsSignal::Diagnostics => self.context.log_diagnostics(),
+			OsSignal::Interrupt | OsSignal::Terminate => Ok(()),
+		};
+		if let Err(err) = result {
+			error!("{err}");
+		}
+	}
+
+	/// Stop the agent gracefully
+	///
+	/// First the liveliness token is withdrawn, so other agents stop sending requests.
+	/// Then the tasks are stopped in reverse order of their start, each getting the chance
+	/// to finish its current work within the agents drain timeout.
+	/// Stopping is not vetoed by transition guards.
 	///
 	/// # Errors
 	#[tracing::instrument(skip_all)]
-	pub fn stop(self) -> Result<Agent<P>> {
-		self.context.set_state(OperationState::Created)?;
-
+	pub async fn stop(self) -> Result<Agent<P>> {
 		// stop liveliness
 		if self.liveliness {
-			self.liveliness_token
+			let token = self
+				.liveliness_token
 				.write()
 				.map_err(|_| Error::ModifyStruct("liveliness".into()))?
 				.take();
+			if let Some(token) = token {
+				if let Err(error) = token.undeclare().await {
+					warn!("could not withdraw liveliness token: {error}");
+				}
+			}
+		}
+
+		let aborted = self.context.shutdown(self.drain_timeout).await?;
+		if !aborted.is_empty() {
+			warn!("tasks aborted on shutdown: {}", aborted.join(", "));
 		}
+
 		let r = Agent {
 			rx: self.rx,
 			context: self.context,
 			liveliness: self.liveliness,
 			liveliness_token: self.liveliness_token,
+			drain_timeout: self.drain_timeout,
+			restart_policy: self.restart_policy,
 		};
 		Ok(r)
 	}
@@ -624,6 +1018,7 @@ where
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use dimas_com::zenoh::Communicator;
 
 	// check, that the auto traits are available
 	const fn is_normal<T: Sized + Send + Sync>() {}
@@ -638,4 +1033,125 @@ mod tests {
 		is_normal::<RunningAgent<Props>>();
 		is_normal::<TaskSignal>();
 	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn rejected_state_reply() {
+		let config = Config::default();
+		let agent = Agent::new(Props {})
+			.transition_guard(|_, _, requested| {
+				if requested == &OperationState::Active {
+					Err("not ready".into())
+				} else {
+					Ok(())
+				}
+			})
+			.config(&config)
+			.expect("snh");
+		// as on start of the agent, which starts the signal queryables
+		agent
+			.context
+			.change_state(OperationState::Configured)
+			.expect("snh");
+		let zid = agent.context.uuid();
+		// the controller has its own session
+		let com = Communicator::new(config.zenoh_config()).expect("snh");
+
+		// the sessions may need some time to discover each other
+		let mut entities = Vec::new();
+		for _ in 0..10 {
+			entities = dimas_commands::set_state(&com, &zid, Some(OperationState::Active))
+				.unwrap_or_default();
+			if !entities.is_empty() {
+				break;
+			}
+		}
+		assert_eq!(entities.len(), 1);
+		assert_eq!(entities[0].state(), &OperationState::Configured);
+		let rejection = entities[0].rejection().expect("snh");
+		assert!(rejection.contains("not ready"));
+		assert_eq!(agent.context.state(), OperationState::Configured);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn rejected_recovery_reply() {
+		let config = Config::default();
+		let agent = Agent::new(Props {}).config(&config).expect("snh");
+		// as on start of the agent, which starts the signal queryables
+		agent
+			.context
+			.change_state(OperationState::Configured)
+			.expect("snh");
+		let zid = agent.context.uuid();
+		// the controller has its own session
+		let com = Communicator::new(config.zenoh_config()).expect("snh");
+
+		// the sessions may need some time to discover each other
+		let mut entities = Vec::new();
+		for _ in 0..10 {
+			entities =
+				dimas_commands::recover(&com, &zid, OperationState::Created).unwrap_or_default();
+			if !entities.is_empty() {
+				break;
+			}
+		}
+		assert_eq!(entities.len(), 1);
+		assert_eq!(entities[0].state(), &OperationState::Configured);
+		assert!(entities[0].rejection().is_some());
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn restarts_pruned() {
+		let mut agent = Agent::new(Props {})
+			.config(&Config::default())
+			.expect("snh");
+		agent.restart_policy(RestartPolicy {
+			period: Duration::from_secs(3600),
+			backoff: Duration::from_secs(3600),
+			..RestartPolicy::new()
+		});
+		let mut running = RunningAgent {
+			rx: agent.rx,
+			context: agent.context,
+			liveliness: agent.liveliness,
+			liveliness_token: agent.liveliness_token,
+			drain_timeout: agent.drain_timeout,
+			restart_policy: agent.restart_policy,
+			restarts: HashMap::new(),
+		};
+		let restarts = |running: &RunningAgent<Props>, selector: &str| {
+			running
+				.restarts
+				.get(&(CapabilityKind::Timer, selector.into()))
+				.map(|(restarts, _)| *restarts)
+		};
+
+		running.restart(CapabilityKind::Timer, "removed".into());
+		running.restart(CapabilityKind::Timer, "removed".into());
+		running.restart(CapabilityKind::Timer, "kept".into());
+		assert_eq!(restarts(&running, "removed"), Some(2));
+
+		// a removed capability is forgotten
+		TimerBuilder::new(running.context.clone())
+			.name("removed")
+			.interval(Duration::from_secs(3600))
+			.callback(|_| Ok(()))
+			.storage(running.context.timers())
+			.add()
+			.expect("snh")
+			.remove()
+			.expect("snh");
+		let Some(TaskSignal::Unregistered(selector)) = running.rx.recv().await else {
+			panic!("no unregistered signal");
+		};
+		running.forget_restarts(&selector);
+		assert_eq!(restarts(&running, "removed"), None);
+		assert_eq!(restarts(&running, "kept"), Some(1));
+
+		// the restarts of an expired period are forgotten
+		running.restart_policy.period = Duration::ZERO;
+		std::thread::sleep(Duration::from_millis(1));
+		running.restart(CapabilityKind::Timer, "other".into());
+		assert_eq!(restarts(&running, "kept"), None);
+		assert_eq!(running.restarts.len(), 1);
+	}
 }
@@ -34,27 +34,35 @@
 #[cfg(doc)]
 use crate::agent::Agent;
 use crate::error::Error;
-use core::fmt::Debug;
+use crate::lifecycle::LifecycleHooks;
+use core::{
+	fmt::Debug,
+	sync::atomic::{AtomicU64, Ordering},
+	time::Duration,
+};
 use dimas_com::traits::LivelinessSubscriber;
 use dimas_com::traits::{
 	Communicator, CommunicatorMethods, Observer, Publisher, Querier, Responder,
 };
 use dimas_config::Config;
-#[cfg(doc)]
-use dimas_core::traits::Context;
 use dimas_core::{
 	Result,
-	enums::{OperationState, TaskSignal},
-	message_types::{Message, QueryableMsg},
-	traits::{Capability, ContextAbstraction},
+	enums::{CapabilityKind, OperationState, TaskSignal, TimerCommand},
+	handle::{TimerRef, unregister},
+	message_types::{CapabilityDescription, Message, QueryableMsg, Replies, TimerStats},
+	traits::{Capability, Clock, Context, ContextAbstraction, DrainFuture, PropsWriteGuard},
 };
 use dimas_time::Timer;
+use futures::future::join_all;
 use std::{
 	collections::HashMap,
 	sync::{Arc, RwLock},
 };
-use tokio::sync::mpsc::Sender;
-use tracing::{Level, info, instrument};
+use tokio::{
+	sync::{mpsc::Sender, watch},
+	time::Instant,
+};
+use tracing::{Level, info, instrument, warn};
 use zenoh::Session;
 // endregion:	--- modules
 
@@ -65,7 +73,7 @@ const INITIAL_SIZE: usize = 9;
 
 // region:		--- ContextImpl
 /// [`ContextImpl`] makes all relevant data of the [`Agent`] accessible via accessor methods.
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 #[allow(clippy::module_name_repetitions)]
 pub struct ContextImpl<P>
 where
@@ -88,6 +96,43 @@ where
 	communicator: Arc<dyn Communicator>,
 	/// Registered [`Timer`]
 	timers: Arc<RwLock<HashMap<String, Timer<P>>>>,
+	/// Registered lifecycle hooks
+	hooks: Arc<RwLock<LifecycleHooks<P>>>,
+	/// The cause of the last switch into [`OperationState::Error`]
+	last_error: Arc<RwLock<Option<String>>>,
+	/// The state the capabilities were left in when switching into [`OperationState::Error`]
+	failed_state: Arc<RwLock<OperationState>>,
+	/// Notifies the watchers of the properties after a write
+	props_changed: watch::Sender<u64>,
+	/// The number of writes the `on_props_changed` hook has been called for
+	props_handled: Arc<AtomicU64>,
+	/// The source of time
+	clock: Arc<dyn Clock>,
+}
+
+impl<P> Clone for ContextImpl<P>
+where
+	P: Send + Sync + 'static,
+{
+	/// A clone shares all data with the original
+	fn clone(&self) -> Self {
+		Self {
+			uuid: self.uuid.clone(),
+			name: self.name.clone(),
+			prefix: self.prefix.clone(),
+			state: self.state.clone(),
+			sender: self.sender.clone(),
+			props: self.props.clone(),
+			communicator: self.communicator.clone(),
+			timers: self.timers.clone(),
+			hooks: self.hooks.clone(),
+			last_error: self.last_error.clone(),
+			failed_state: self.failed_state.clone(),
+			props_changed: self.props_changed.clone(),
+			props_handled: self.props_handled.clone(),
+			clock: self.clock.clone(),
+		}
+	}
 }
 
 impl<P> ContextAbstraction for ContextImpl<P>
@@ -130,72 +175,85 @@ where
 		&self.sender
 	}
 
+	fn clock(&self) -> Arc<dyn Clock> {
+		self.clock.clone()
+	}
+
 	fn read(&self) -> Result<std::sync::RwLockReadGuard<'_, P>> {
 		self.props
 			.read()
 			.map_err(|_| Error::ReadAccess.into())
 	}
 
-	fn write(&self) -> Result<std::sync::RwLockWriteGuard<'_, P>> {
-		self.props
+	fn write(&self) -> Result<PropsWriteGuard<'_, P>> {
+		let guard = self
+			.props
 			.write()
-			.map_err(|_| Error::WriteAccess.into())
+			.map_err(|_| Error::WriteAccess)?;
+		Ok(PropsWriteGuard::new(guard, &self.props_changed))
+	}
+
+	fn watch_props(&self) -> watch::Receiver<u64> {
+		self.props_changed.subscribe()
 	}
 
 	fn set_state(&self, state: OperationState) -> Result<()> {
-		info!("changing state to {}", &state);
-		let final_state = state;
-		let mut next_state;
-		// step up?
-		while self.state() < final_state {
-			match self.state() {
-				OperationState::Error => {
-					return Err(Error::ManageState.into());
-				}
-				OperationState::Created => {
-					next_state = OperationState::Configured;
-				}
-				OperationState::Configured => {
-					next_state = OperationState::Inactive;
-				}
-				OperationState::Inactive => {
-					next_state = OperationState::Standby;
-				}
-				OperationState::Standby => {
-					next_state = OperationState::Active;
-				}
-				OperationState::Active => {
-					return self.modify_state_property(OperationState::Error);
-				}
-			}
-			self.upgrade_registered_tasks(next_state)?;
+		self.check_guards(&state)?;
+		self.change_state(state)
+	}
+
+	fn set_error(&self, cause: String) -> Result<()> {
+		self.enter_error_state(cause)
+	}
+
+	fn last_error(&self) -> Option<String> {
+		self.last_error.read().expect("snh").clone()
+	}
+
+	fn recover(&self, state: OperationState) -> Result<()> {
+		if self.state() != OperationState::Error {
+			return Err(Error::NotInErrorState.into());
+		}
+		let failed_state = self
+			.failed_state
+			.read()
+			.map_err(|_| Error::ReadContext("failed state".into()))?
+			.clone();
+		if state == OperationState::Error
+			|| state > OperationState::Configured
+			|| state > failed_state
+		{
+			return Err(Error::InvalidRecoveryState(state).into());
 		}
+		self.check_guards(&state)?;
+		info!(
+			"recovering from state {} to state {}",
+			&failed_state, &state
+		);
 
-		// step down?
-		while self.state() > final_state {
-			match self.state() {
-				OperationState::Active => {
-					next_state = OperationState::Standby;
-				}
-				OperationState::Standby => {
-					next_state = OperationState::Inactive;
-				}
-				OperationState::Inactive => {
-					next_state = OperationState::Configured;
-				}
-				OperationState::Configured => {
-					next_state = OperationState::Created;
-				}
-				OperationState::Created => {
-					return self.modify_state_property(OperationState::Error);
-				}
-				OperationState::Error => {
-					return Err(Error::ManageState.into());
+		// the capabilities are still in the state the agent failed in,
+		// so step down from there to run the lifecycle hooks
+		self.modify_state_property(failed_state)?;
+		while self.state() > state {
+			let next_state = match self.state() {
+				OperationState::Active => OperationState::Standby,
+				OperationState::Standby => OperationState::Inactive,
+				OperationState::Inactive => OperationState::Configured,
+				_ => OperationState::Created,
+			};
+			if let Err(error) = self.downgrade_registered_tasks(next_state) {
+				// the failed recovery leaves the agent in state `Error` with the new cause
+				let cause = format!("recovery failed: {error}");
+				if let Err(hook_error) = self.enter_error_state(cause) {
+					warn!("{hook_error}");
 				}
+				return Err(error);
 			}
-			self.downgrade_registered_tasks(next_state)?;
 		}
-
+		self.last_error
+			.write()
+			.map_err(|_| Error::ModifyStruct("last_error".into()))?
+			.take();
 		Ok(())
 	}
 
@@ -268,6 +326,17 @@ where
 		Ok(())
 	}
 
+	#[instrument(level = Level::ERROR, skip_all)]
+	fn get_async_with(
+		&self,
+		selector: &str,
+		message: Option<Message>,
+		timeout: Option<Duration>,
+	) -> Result<Replies> {
+		self.communicator
+			.get_async(selector, message, timeout)
+	}
+
 	#[instrument(level = Level::ERROR, skip_all)]
 	fn observe_with(&self, selector: &str, message: Option<Message>) -> Result<()> {
 		self.observers()
@@ -305,11 +374,171 @@ where
 			self.communicator.session(session_id)
 		}
 	}
+
+	fn capabilities(&self) -> Result<Vec<(CapabilityKind, String)>> {
+		let mut capabilities = Vec::new();
+		capabilities.extend(
+			self.liveliness_subscribers()
+				.read()
+				.map_err(|_| Error::ReadContext("liveliness subscribers".into()))?
+				.keys()
+				.map(|selector| (CapabilityKind::LivelinessSubscriber, selector.clone())),
+		);
+		capabilities.extend(
+			self.responders()
+				.read()
+				.map_err(|_| Error::ReadContext("responders".into()))?
+				.iter()
+				.map(|(selector, responder)| (responder.kind(), selector.clone())),
+		);
+		capabilities.extend(
+			self.observers()
+				.read()
+				.map_err(|_| Error::ReadContext("observers".into()))?
+				.keys()
+				.map(|selector| (CapabilityKind::Observer, selector.clone())),
+		);
+		capabilities.extend(
+			self.publishers()
+				.read()
+				.map_err(|_| Error::ReadContext("publishers".into()))?
+				.keys()
+				.map(|selector| (CapabilityKind::Publisher, selector.clone())),
+		);
+		capabilities.extend(
+			self.queriers()
+				.read()
+				.map_err(|_| Error::ReadContext("queries".into()))?
+				.keys()
+				.map(|selector| (CapabilityKind::Querier, selector.clone())),
+		);
+		capabilities.extend(
+			self.timers
+				.read()
+				.map_err(|_| Error::ReadContext("timers".into()))?
+				.keys()
+				.map(|selector| (CapabilityKind::Timer, selector.clone())),
+		);
+		capabilities.sort();
+		Ok(capabilities)
+	}
+
+	fn describe_capabilities(&self) -> Result<Vec<CapabilityDescription>> {
+		let mut descriptions = Vec::new();
+		descriptions.extend(
+			self.liveliness_subscribers()
+				.read()
+				.map_err(|_| Error::ReadContext("liveliness subscribers".into()))?
+				.values()
+				.map(|subscriber| subscriber.describe()),
+		);
+		descriptions.extend(
+			self.responders()
+				.read()
+				.map_err(|_| Error::ReadContext("responders".into()))?
+				.values()
+				.map(|responder| responder.describe()),
+		);
+		descriptions.extend(
+			self.observers()
+				.read()
+				.map_err(|_| Error::ReadContext("observers".into()))?
+				.values()
+				.map(|observer| observer.describe()),
+		);
+		descriptions.extend(
+			self.publishers()
+				.read()
+				.map_err(|_| Error::ReadContext("publishers".into()))?
+				.values()
+				.map(|publisher| publisher.describe()),
+		);
+		descriptions.extend(
+			self.queriers()
+				.read()
+				.map_err(|_| Error::ReadContext("queries".into()))?
+				.values()
+				.map(|querier| querier.describe()),
+		);
+		descriptions.extend(
+			self.timers
+				.read()
+				.map_err(|_| Error::ReadContext("timers".into()))?
+				.values()
+				.map(Timer::describe),
+		);
+		descriptions.sort_by(|a, b| {
+			a.kind()
+				.cmp(&b.kind())
+				.then_with(|| a.selector().cmp(b.selector()))
+		});
+		Ok(descriptions)
+	}
+
+	fn remove_capability(&self, kind: CapabilityKind, selector: &str) -> Result<bool> {
+		let removed = match kind {
+			CapabilityKind::LivelinessSubscriber => {
+				unregister(&self.liveliness_subscribers(), selector)?.is_some()
+			}
+			CapabilityKind::Observable | CapabilityKind::Queryable | CapabilityKind::Subscriber => {
+				// responders of different kind share the storage
+				let responders = self.responders();
+				let found = responders
+					.read()
+					.map_err(|_| Error::ReadContext("responders".into()))?
+					.get(selector)
+					.is_some_and(|responder| responder.kind() == kind);
+				found && unregister(&responders, selector)?.is_some()
+			}
+			CapabilityKind::Observer => unregister(&self.observers(), selector)?.is_some(),
+			CapabilityKind::Publisher => unregister(&self.publishers(), selector)?.is_some(),
+			CapabilityKind::Querier => unregister(&self.queriers(), selector)?.is_some(),
+			CapabilityKind::Timer => unregister(&self.timers, selector)?.is_some(),
+		};
+		if removed {
+			let _ = self
+				.sender
+				.try_send(TaskSignal::Unregistered(selector.into()));
+		}
+		Ok(removed)
+	}
+
+	fn kick(&self, name: &str) -> Result<()> {
+		self.with_timer(name, Timer::kick)
+	}
+
+	fn timer(&self, name: &str) -> TimerRef<'_, P> {
+		TimerRef::new(name, self)
+	}
+
+	fn control_timer(&self, name: &str, command: TimerCommand) -> Result<()> {
+		let state = self.state();
+		let set_paused = |timer: &Timer<P>, paused: bool| {
+			if timer.is_paused() == paused {
+				return Ok(());
+			}
+			timer.set_paused(paused)?;
+			timer.manage_operation_state(&state)
+		};
+		self.with_timer(name, |timer| match command {
+			TimerCommand::SetInterval(interval) => timer.set_interval(interval),
+			TimerCommand::Pause => set_paused(timer, true),
+			TimerCommand::Resume => set_paused(timer, false),
+			TimerCommand::TriggerNow => {
+				timer.trigger_now();
+				Ok(())
+			}
+		})
+	}
+
+	fn timer_stats(&self, name: &str) -> Result<TimerStats> {
+		self.with_timer(name, |timer| Ok(timer.stats()))
+	}
 }
 
 impl<P> ContextImpl<P>
 where
-	P: Send + Sync + 'static,
+	P: Debug + Send + Sync + 'static,
 {
 	/// Constructor for the [`ContextImpl`]
 	/// # Errors
@@ -319,9 +548,12 @@ where
 		name: Option<String>,
 		sender: Sender<TaskSignal>,
 		prefix: Option<String>,
+		hooks: LifecycleHooks<P>,
+		clock: Arc<dyn Clock>,
 	) -> Result<Self> {
 		let communicator = dimas_com::communicator::from(config)?;
 		let uuid = communicator.uuid();
+		let (props_changed, _) = watch::channel(0);
 		Ok(Self {
 			uuid,
 			name,
@@ -331,9 +563,347 @@ where
 			communicator,
 			props: Arc::new(RwLock::new(props)),
 			timers: Arc::new(RwLock::new(HashMap::with_capacity(INITIAL_SIZE))),
+			hooks: Arc::new(RwLock::new(hooks)),
+			last_error: Arc::new(RwLock::new(None)),
+			failed_state: Arc::new(RwLock::new(OperationState::Created)),
+			props_changed,
+			props_handled: Arc::new(AtomicU64::new(0)),
+			clock,
 		})
 	}
 
+	/// Get the lifecycle hooks
+	#[must_use]
+	pub fn hooks(&self) -> Arc<RwLock<LifecycleHooks<P>>> {
+		self.hooks.clone()
+	}
+
+	/// Change the [`OperationState`] without asking the transition guards.
+	///
+	/// Setting new state is done step by step
+	/// # Errors
+	pub fn change_state(&self, state: OperationState) -> Result<()> {
+		info!("changing state to {}", &state);
+		let final_state = state;
+		// state `Error` is entered without changing the capabilities
+		if final_state == OperationState::Error {
+			return self.enter_error_state(String::from("state 'Error' requested"));
+		}
+		let mut next_state;
+		// step up?
+		while self.state() < final_state {
+			match self.state() {
+				OperationState::Error => {
+					return Err(Error::InErrorState.into());
+				}
+				OperationState::Created => {
+					next_state = OperationState::Configured;
+				}
+				OperationState::Configured => {
+					next_state = OperationState::Inactive;
+				}
+				OperationState::Inactive => {
+					next_state = OperationState::Standby;
+				}
+				OperationState::Standby => {
+					next_state = OperationState::Active;
+				}
+				OperationState::Active => {
+					return self.enter_error_state(String::from("stepped up beyond 'Active'"));
+				}
+			}
+			self.upgrade_registered_tasks(next_state)?;
+		}
+
+		// step down?
+		while self.state() > final_state {
+			match self.state() {
+				OperationState::Active => {
+					next_state = OperationState::Standby;
+				}
+				OperationState::Standby => {
+					next_state = OperationState::Inactive;
+				}
+				OperationState::Inactive => {
+					next_state = OperationState::Configured;
+				}
+				OperationState::Configured => {
+					next_state = OperationState::Created;
+				}
+				OperationState::Created => {
+					return self.enter_error_state(String::from("stepped down below 'Created'"));
+				}
+				OperationState::Error => {
+					return Err(Error::InErrorState.into());
+				}
+			}
+			self.downgrade_registered_tasks(next_state)?;
+		}
+
+		Ok(())
+	}
+
+	/// Gracefully step down to [`OperationState::Created`] for a shutdown.
+	///
+	/// On each step down the registered tasks get the chance to finish their current work.
+	/// The whole shutdown is bounded by `timeout`, tasks still running afterwards are aborted.
+	/// Failing lifecycle hooks are logged but do not stop the shutdown.
+	/// An agent in state [`OperationState::Error`] steps down from the state it failed in.
+	///
+	/// Returns the selectors of all tasks, which had to be aborted.
+	/// # Errors
+	pub async fn shutdown(&self, timeout: Duration) -> Result<Vec<String>> {
+		let deadline = Instant::now() + timeout;
+		let mut aborted = Vec::new();
+		// in state `Error` the capabilities are still in the state the agent failed in,
+		// so step down from there
+		if self.state() == OperationState::Error {
+			let failed_state = self
+				.failed_state
+				.read()
+				.map_err(|_| Error::ReadContext("failed state".into()))?
+				.clone();
+			self.modify_state_property(failed_state)?;
+		}
+		while self.state() > OperationState::Created {
+			let next_state = match self.state() {
+				OperationState::Active => OperationState::Standby,
+				OperationState::Standby => OperationState::Inactive,
+				OperationState::Inactive => OperationState::Configured,
+				_ => OperationState::Created,
+			};
+			if let Err(error) = self.run_lifecycle_hook(&next_state) {
+				warn!("{error}");
+			}
+
+			let remaining = deadline.saturating_duration_since(Instant::now());
+			// reverse order of start, timers first
+			let mut drains: Vec<(String, DrainFuture)> = self
+				.timers
+				.read()
+				.map_err(|_| Error::ReadContext("timers".into()))?
+				.iter()
+				.map(|(selector, timer)| (selector.clone(), timer.drain(&next_state, remaining)))
+				.collect();
+			drains.extend(
+				self.communicator
+					.drain_capabilities(&next_state, remaining)?,
+			);
+
+			let (selectors, futures): (Vec<_>, Vec<_>) = drains.into_iter().unzip();
+			join_all(futures)
+				.await
+				.into_iter()
+				.zip(selectors)
+				.filter(|(forced, _)| *forced)
+				.for_each(|(_, selector)| aborted.push(selector));
+
+			self.modify_state_property(next_state)?;
+		}
+		Ok(aborted)
+	}
+
+	/// Call the `on_reload` hook, if there is one registered.
+	/// # Errors
+	/// if the hook fails, the error is wrapped into [`Error::ReloadHook`]
+	pub fn reload(&self) -> Result<()> {
+		let hook = self
+			.hooks
+			.read()
+			.map_err(|_| Error::ReadContext("hooks".into()))?
+			.reload
+			.clone();
+		if let Some(hook) = hook {
+			let ctx: Context<P> = Arc::new(self.clone());
+			let mut callback = hook
+				.lock()
+				.map_err(|_| Error::MutexPoison(String::from("reload hook")))?;
+			callback(ctx).map_err(|source| Error::ReloadHook { source })?;
+		}
+		Ok(())
+	}
+
+	/// Call the `on_props_changed` hook, if there is one registered.
+	///
+	/// The writes done while the hook runs, including those of the hook itself,
+	/// are regarded as handled, so a hook writing the properties is not called again.
+	/// # Errors
+	/// if the hook fails, the error is wrapped into [`Error::PropsChangedHook`]
+	pub fn props_changed(&self) -> Result<()> {
+		let writes = *self.props_changed.borrow();
+		if self.props_handled.load(Ordering::Acquire) == writes {
+			return Ok(());
+		}
+		let hook = self
+			.hooks
+			.read()
+			.map_err(|_| Error::ReadContext("hooks".into()))?
+			.props_changed
+			.clone();
+		if let Some(hook) = hook {
+			let ctx: Context<P> = Arc::new(self.clone());
+			let mut callback = hook
+				.lock()
+				.map_err(|_| Error::MutexPoison(String::from("props changed hook")))?;
+			let result = callback(ctx);
+			drop(callback);
+			self.props_handled
+				.store(*self.props_changed.borrow(), Ordering::Release);
+			result.map_err(|source| Error::PropsChangedHook { source })?;
+		}
+		Ok(())
+	}
+
+	/// Restart the capability of `kind` registered under `selector`
+	/// by adjusting it again to the current [`OperationState`].
+	///
+	/// Returns `false` if there is no such capability, e.g. because it has been removed meanwhile.
+	/// # Errors
+	/// if the storage is not accessible or the capability can not be started
+	pub fn restart_capability(&self, kind: CapabilityKind, selector: &str) -> Result<bool> {
+		let state = self.state();
+		match kind {
+			CapabilityKind::LivelinessSubscriber => {
+				restart(&self.liveliness_subscribers(), selector, &state)
+			}
+			CapabilityKind::Observable | CapabilityKind::Queryable | CapabilityKind::Subscriber => {
+				restart(&self.responders(), selector, &state)
+			}
+			CapabilityKind::Observer => restart(&self.observers(), selector, &state),
+			CapabilityKind::Publisher => restart(&self.publishers(), selector, &state),
+			CapabilityKind::Querier => restart(&self.queriers(), selector, &state),
+			CapabilityKind::Timer => restart(&self.timers, selector, &state),
+		}
+	}
+
+	/// Log a snapshot of the [`Agent`]s state and its registered capabilities.
+	/// # Errors
+	/// if a storage is not accessible
+	pub fn log_diagnostics(&self) -> Result<()> {
+		let mut lines = Vec::new();
+		if let Some(error) = self.last_error() {
+			lines.push(format!("last error: {error}"));
+		}
+		lines.extend(
+			self.timers
+				.read()
+				.map_err(|_| Error::ReadContext("timers".into()))?
+				.keys()
+				.map(|selector| format!("timer: {selector}")),
+		);
+		lines.extend(
+			self.liveliness_subscribers()
+				.read()
+				.map_err(|_| Error::ReadContext("liveliness subscribers".into()))?
+				.keys()
+				.map(|selector| format!("liveliness subscriber: {selector}")),
+		);
+		lines.extend(
+			self.responders()
+				.read()
+				.map_err(|_| Error::ReadContext("responders".into()))?
+				.keys()
+				.map(|selector| format!("responder: {selector}")),
+		);
+		lines.extend(
+			self.publishers()
+				.read()
+				.map_err(|_| Error::ReadContext("publishers".into()))?
+				.keys()
+				.map(|selector| format!("publisher: {selector}")),
+		);
+		lines.extend(
+			self.queriers()
+				.read()
+				.map_err(|_| Error::ReadContext("queries".into()))?
+				.keys()
+				.map(|selector| format!("querier: {selector}")),
+		);
+		lines.extend(
+			self.observers()
+				.read()
+				.map_err(|_| Error::ReadContext("observers".into()))?
+				.keys()
+				.map(|selector| format!("observer: {selector}")),
+		);
+		info!(
+			"diagnostics for agent {} ({}) in state {}:\n  {}",
+			self.fq_name().unwrap_or_default(),
+			self.uuid,
+			self.state(),
+			lines.join("\n  ")
+		);
+		Ok(())
+	}
+
+	/// Ask all registered transition guards, whether the change to `state` is allowed.
+	/// # Errors
+	/// [`Error::TransitionRejected`] with the reason of the first rejecting guard
+	fn check_guards(&self, state: &OperationState) -> Result<()> {
+		let current = self.state();
+		// entering `Error` can not be vetoed
+		if &current == state || state == &OperationState::Error {
+			return Ok(());
+		}
+		let guards = self
+			.hooks
+			.read()
+			.map_err(|_| Error::ReadContext("hooks".into()))?
+			.guards
+			.clone();
+		for guard in guards {
+			let ctx: Context<P> = Arc::new(self.clone());
+			guard(ctx, &current, state).map_err(|reason| Error::TransitionRejected {
+				state: state.clone(),
+				reason: reason.to_string(),
+			})?;
+		}
+		Ok(())
+	}
+
+	/// Run the lifecycle hook registered for the step from current state to `new_state`.
+	/// # Errors
+	/// if the hook fails, the error is wrapped into [`Error::LifecycleHook`]
+	fn run_lifecycle_hook(&self, new_state: &OperationState) -> Result<()> {
+		let hook = self
+			.hooks
+			.read()
+			.map_err(|_| Error::ReadContext("hooks".into()))?
+			.for_transition(&self.state(), new_state);
+		if let Some(hook) = hook {
+			// the hook gets its own handle to the shared context data
+			let ctx: Context<P> = Arc::new(self.clone());
+			let mut callback = hook
+				.lock()
+				.map_err(|_| Error::MutexPoison(String::from("lifecycle hook")))?;
+			callback(ctx).map_err(|source| Error::LifecycleHook {
+				state: new_state.clone(),
+				source,
+			})?;
+		}
+		Ok(())
+	}
+
+	/// Switch into [`OperationState::Error`], record the cause and call the `on_error` hook
+	/// # Errors
+	fn enter_error_state(&self, cause: String) -> Result<()> {
+		warn!("entering state 'Error': {cause}");
+		let state = self.state();
+		if state != OperationState::Error {
+			*self
+				.failed_state
+				.write()
+				.map_err(|_| Error::ModifyStruct("failed state".into()))? = state;
+		}
+		self.last_error
+			.write()
+			.map_err(|_| Error::ModifyStruct("last_error".into()))?
+			.replace(cause);
+		let hook_result = self.run_lifecycle_hook(&OperationState::Error);
+		self.modify_state_property(OperationState::Error)?;
+		hook_result
+	}
+
 	/// Set the [`Context`]s state
 	/// # Errors
 	fn modify_state_property(&self, state: OperationState) -> Result<()> {
@@ -382,6 +952,27 @@ where
 		self.timers.clone()
 	}
 
+	/// Apply `f` to the timer `name`, which is looked up as given and prefixed with the prefix
+	fn with_timer<F, R>(&self, name: &str, f: F) -> Result<R>
+	where
+		F: FnOnce(&Timer<P>) -> Result<R>,
+	{
+		let timers = self
+			.timers
+			.read()
+			.map_err(|_| Error::ReadContext("timers".into()))?;
+		let result = timers
+			.get(name)
+			.or_else(|| {
+				self.prefix()
+					.and_then(|prefix| timers.get(&format!("{prefix}/{name}")))
+			})
+			.ok_or_else(|| Error::UnknownTimer(name.into()).into())
+			.and_then(f);
+		drop(timers);
+		result
+	}
+
 	/// Internal function for starting all registere)d tasks.
 	///
 	/// The tasks are started in the order
@@ -396,9 +987,14 @@ where
 	/// - [`Observer`]s and the
 	/// - [`Query`]s
 	///
+	/// Before any task is started, the lifecycle hook for the transition is called.
+	///
 	/// # Errors
-	/// Currently none
+	/// if the lifecycle hook for the transition fails
 	fn upgrade_registered_tasks(&self, new_state: OperationState) -> Result<()> {
+		// a failing hook stops the transition
+		self.run_lifecycle_hook(&new_state)?;
+
 		// start communication
 		self.communicator
 			.manage_operation_state(&new_state)?;
@@ -420,20 +1016,27 @@ where
 	///
 	/// The tasks are stopped in reverse order of their start in [`Context::start_registered_tasks()`]
 	///
+	/// Before any task is stopped, the lifecycle hook for the transition is called.
+	///
 	/// # Errors
-	/// Currently none
+	/// if the lifecycle hook for the transition fails or a capability can not be stopped,
+	/// all timers are stopped nevertheless
 	fn downgrade_registered_tasks(&self, new_state: OperationState) -> Result<()> {
+		// a failing hook stops the transition
+		self.run_lifecycle_hook(&new_state)?;
+
 		// reverse order of start!
 		// stop all registered timers
-		self.timers
+		let timer_result = self
+			.timers
 			.write()
 			.map_err(|_| Error::ModifyStruct("timers".into()))?
-			.iter_mut()
-			.for_each(|timer| {
-				let _ = timer.1.manage_operation_state(&new_state);
-			});
+			.values()
+			.map(|timer| timer.manage_operation_state(&new_state))
+			.fold(Ok(()), Result::and);
+		timer_result?;
 
-		// start communication
+		// stop communication
 		self.communicator
 			.manage_operation_state(&new_state)?;
 
@@ -443,9 +1046,45 @@ where
 }
 // endregion:	--- ContextImpl
 
+/// Adjust the capability registered under `selector` in `storage` to `state`
+fn restart<C>(
+	storage: &RwLock<HashMap<String, C>>,
+	selector: &str,
+	state: &OperationState,
+) -> Result<bool>
+where
+	C: Capability,
+{
+	let storage = storage
+		.read()
+		.map_err(|_| Error::ReadContext(selector.into()))?;
+	let Some(capability) = storage.get(selector) else {
+		return Ok(false);
+	};
+	capability.manage_operation_state(state)?;
+	drop(storage);
+	Ok(true)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::lifecycle::{ArcLifecycleCallback, ArcTransitionGuard};
+	use core::sync::atomic::{AtomicU32, Ordering};
+	use dimas_com::builder::{
+		PublisherBuilder, QuerierBuilder, QueryableBuilder, SubscriberBuilder,
+	};
+	use dimas_core::{
+		builder_states::{Interval, NoCallback, NoInterval, NoSelector, NoStorage},
+		codec::{Bitcode, Codec, Json},
+		message_types::{Message, SampleInfo},
+		policies::{ArcErrorCallback, Backoff, ErrorHandler, ErrorPolicy, RetryPolicy},
+		trace_context::{TRACEPARENT, TraceContext},
+		traits::Clock,
+	};
+	use dimas_time::{SimulatedClock, SystemClock, TimerBuilder};
+	use futures::StreamExt;
+	use std::sync::Mutex;
 
 	// check, that the auto traits are available
 	const fn is_normal<T: Sized + Send + Sync>() {}
@@ -457,4 +1096,946 @@ mod tests {
 	const fn normal_types() {
 		is_normal::<ContextImpl<Props>>();
 	}
+
+	fn context(hooks: LifecycleHooks<Props>) -> ContextImpl<Props> {
+		context_with(hooks, Arc::new(SystemClock))
+	}
+
+	fn context_with(hooks: LifecycleHooks<Props>, clock: Arc<dyn Clock>) -> ContextImpl<Props> {
+		let (sender, _) = tokio::sync::mpsc::channel(8);
+		ContextImpl::new(
+			&Config::default(),
+			Props {},
+			None,
+			sender,
+			None,
+			hooks,
+			clock,
+		)
+		.expect("snh")
+	}
+
+	/// A context driven by a [`SimulatedClock`] with a timer `name` counting its firings
+	fn timed<F>(name: &str, timer: F) -> (ContextImpl<Props>, SimulatedClock, Arc<AtomicU32>)
+	where
+		F: FnOnce(
+			TimerBuilder<Props, NoSelector, NoInterval, NoCallback, NoStorage>,
+		) -> TimerBuilder<Props, NoSelector, Interval, NoCallback, NoStorage>,
+	{
+		let clock = SimulatedClock::default();
+		let ctx = context_with(LifecycleHooks::new(), Arc::new(clock.clone()));
+		let fired = Arc::new(AtomicU32::new(0));
+		let counter = fired.clone();
+		timer(TimerBuilder::new(Arc::new(ctx.clone())))
+			.name(name)
+			.callback(move |_| {
+				counter.fetch_add(1, Ordering::Relaxed);
+				Ok(())
+			})
+			.storage(ctx.timers())
+			.add()
+			.expect("snh");
+		(ctx, clock, fired)
+	}
+
+	/// A context with a timer `name` firing at once, whose callback is busy for `duration`
+	fn busy(name: &str, duration: Duration) -> (ContextImpl<Props>, Arc<AtomicU32>) {
+		let ctx = context(LifecycleHooks::new());
+		let finished = Arc::new(AtomicU32::new(0));
+		let counter = finished.clone();
+		TimerBuilder::new(Arc::new(ctx.clone()))
+			.name(name)
+			.interval(Duration::from_secs(3600))
+			.async_callback(move |_| {
+				let counter = counter.clone();
+				async move {
+					tokio::time::sleep(duration).await;
+					counter.fetch_add(1, Ordering::Relaxed);
+					Ok(())
+				}
+			})
+			.storage(ctx.timers())
+			.add()
+			.expect("snh");
+		(ctx, finished)
+	}
+
+	/// Add a querier for `selector` with a `timeout` for each attempt, retrying according to `policy`
+	fn querier(ctx: &ContextImpl<Props>, selector: &str, timeout: Duration, policy: RetryPolicy) {
+		QuerierBuilder::new("default", Arc::new(ctx.clone()))
+			.selector(selector)
+			.timeout(timeout)
+			.retry(policy)
+			.callback(|_, _| async { Ok(()) })
+			.storage(ctx.queriers())
+			.add()
+			.expect("snh");
+	}
+
+	/// The number of attempts of a query, which failed with `error` after exhausting its retries
+	fn exhausted(error: &(dyn core::error::Error + Send + Sync + 'static)) -> u32 {
+		match error.downcast_ref::<dimas_com::error::Error>() {
+			Some(dimas_com::error::Error::RetriesExhausted { attempts, .. }) => *attempts,
+			_ => panic!("unexpected error: {error}"),
+		}
+	}
+
+	/// Give the tasks woken up by the simulated clock the time to run
+	async fn settle() {
+		tokio::time::sleep(Duration::from_millis(50)).await;
+	}
+
+	/// An [`ErrorHandler`] applying `policy` with an error callback counting the errors
+	fn error_handler(policy: ErrorPolicy) -> (ErrorHandler<Props>, Arc<AtomicU32>) {
+		let informed = Arc::new(AtomicU32::new(0));
+		let counter = informed.clone();
+		let callback: ArcErrorCallback<Props> = Arc::new(Mutex::new(Box::new(move |_, _, _| {
+			counter.fetch_add(1, Ordering::Relaxed);
+		})));
+		(ErrorHandler::new(policy, Some(callback)), informed)
+	}
+
+	/// A failed callback
+	fn failed() -> Result<()> {
+		Err("failed".into())
+	}
+
+	fn counting_hook(counter: &Arc<AtomicU32>) -> ArcLifecycleCallback<Props> {
+		let counter = counter.clone();
+		Arc::new(Mutex::new(move |_| {
+			counter.fetch_add(1, Ordering::Relaxed);
+			Ok(())
+		}))
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn recover_rejected() {
+		let ctx = context(LifecycleHooks::new());
+		assert!(ctx.recover(OperationState::Created).is_err());
+
+		ctx.change_state(OperationState::Inactive)
+			.expect("snh");
+		ctx.set_error("test".into()).expect("snh");
+		// above `Configured`
+		assert!(ctx.recover(OperationState::Active).is_err());
+		assert!(ctx.recover(OperationState::Inactive).is_err());
+		assert!(ctx.recover(OperationState::Error).is_err());
+		assert_eq!(ctx.state(), OperationState::Error);
+
+		// above the state the agent failed in
+		let ctx = context(LifecycleHooks::new());
+		ctx.set_error("test".into()).expect("snh");
+		assert!(ctx.recover(OperationState::Configured).is_err());
+		assert_eq!(ctx.state(), OperationState::Error);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn recover_accepted() {
+		let deactivated = Arc::new(AtomicU32::new(0));
+		let cleaned_up = Arc::new(AtomicU32::new(0));
+		let mut hooks = LifecycleHooks::new();
+		hooks.deactivate = Some(counting_hook(&deactivated));
+		hooks.cleanup = Some(counting_hook(&cleaned_up));
+		let ctx = context(hooks);
+
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		ctx.set_error("test".into()).expect("snh");
+		ctx.recover(OperationState::Configured)
+			.expect("snh");
+		assert_eq!(ctx.state(), OperationState::Configured);
+		assert_eq!(deactivated.load(Ordering::Relaxed), 1);
+		assert_eq!(cleaned_up.load(Ordering::Relaxed), 0);
+		assert_eq!(ctx.last_error(), None);
+
+		ctx.set_error("again".into()).expect("snh");
+		ctx.recover(OperationState::Created).expect("snh");
+		assert_eq!(ctx.state(), OperationState::Created);
+		assert_eq!(deactivated.load(Ordering::Relaxed), 1);
+		assert_eq!(cleaned_up.load(Ordering::Relaxed), 1);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn recover_failed() {
+		let errored = Arc::new(AtomicU32::new(0));
+		let mut hooks = LifecycleHooks::new();
+		hooks.deactivate = Some(Arc::new(Mutex::new(|_| Err("still busy".into()))));
+		hooks.error = Some(counting_hook(&errored));
+		let ctx = context(hooks);
+
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		ctx.set_error("test".into()).expect("snh");
+		assert_eq!(errored.load(Ordering::Relaxed), 1);
+		assert!(ctx.recover(OperationState::Configured).is_err());
+		assert_eq!(ctx.state(), OperationState::Error);
+		assert_eq!(errored.load(Ordering::Relaxed), 2);
+		let cause = ctx.last_error().expect("snh");
+		assert!(cause.contains("still busy"));
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn recover_guarded() {
+		let mut hooks = LifecycleHooks::new();
+		let guard: ArcTransitionGuard<Props> = Arc::new(|_, current, _| {
+			if current == &OperationState::Error {
+				Err("not yet".into())
+			} else {
+				Ok(())
+			}
+		});
+		hooks.guards.push(guard);
+		let ctx = context(hooks);
+
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		ctx.set_error("test".into()).expect("snh");
+		let error = ctx
+			.recover(OperationState::Configured)
+			.expect_err("snh");
+		assert!(error.to_string().contains("not yet"));
+		assert_eq!(ctx.state(), OperationState::Error);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn guarded_transition() {
+		let asked = Arc::new(AtomicU32::new(0));
+		let counter = asked.clone();
+		let mut hooks = LifecycleHooks::new();
+		let guard: ArcTransitionGuard<Props> = Arc::new(move |_, _, requested| {
+			counter.fetch_add(1, Ordering::Relaxed);
+			if requested == &OperationState::Active {
+				Err("not ready".into())
+			} else {
+				Ok(())
+			}
+		});
+		hooks.guards.push(guard);
+		let ctx = context(hooks);
+
+		ctx.set_state(OperationState::Standby)
+			.expect("snh");
+		assert_eq!(ctx.state(), OperationState::Standby);
+		let error = ctx
+			.set_state(OperationState::Active)
+			.expect_err("snh");
+		assert!(error.to_string().contains("not ready"));
+		assert_eq!(ctx.state(), OperationState::Standby);
+		assert_eq!(asked.load(Ordering::Relaxed), 2);
+
+		// changing the state directly skips the guards
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		assert_eq!(ctx.state(), OperationState::Active);
+		assert_eq!(asked.load(Ordering::Relaxed), 2);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn interval_timer() {
+		let (ctx, clock, fired) = timed("interval", |builder| {
+			builder.interval(Duration::from_secs(1))
+		});
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		// the first tick is immediately
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 1);
+
+		clock.advance(Duration::from_millis(500));
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 1);
+		clock.advance(Duration::from_millis(500));
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 2);
+
+		ctx.change_state(OperationState::Standby)
+			.expect("snh");
+		clock.advance(Duration::from_secs(5));
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 2);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn delayed_interval_timer() {
+		let (ctx, clock, fired) = timed("delayed", |builder| {
+			builder
+				.interval(Duration::from_secs(1))
+				.delay(Duration::from_secs(3))
+		});
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 0);
+
+		clock.advance(Duration::from_secs(3));
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 1);
+		clock.advance(Duration::from_secs(1));
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 2);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn one_shot_timer() {
+		let (ctx, clock, fired) = timed("one_shot", |builder| {
+			builder.one_shot(Duration::from_secs(1))
+		});
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 0);
+
+		clock.advance(Duration::from_secs(1));
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 1);
+		for _ in 0..5 {
+			clock.advance(Duration::from_secs(1));
+			settle().await;
+		}
+		assert_eq!(fired.load(Ordering::Relaxed), 1);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn watchdog_timer() {
+		let (ctx, clock, fired) = timed("watchdog", |builder| {
+			builder.watchdog(Duration::from_secs(1))
+		});
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		settle().await;
+
+		// a kicked watchdog does not fire
+		for _ in 0..5 {
+			clock.advance(Duration::from_millis(500));
+			settle().await;
+			ctx.kick("watchdog").expect("snh");
+			settle().await;
+		}
+		assert_eq!(fired.load(Ordering::Relaxed), 0);
+
+		clock.advance(Duration::from_millis(500));
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 0);
+		clock.advance(Duration::from_millis(500));
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 1);
+		// and fires again after the next timeout
+		clock.advance(Duration::from_secs(1));
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 2);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn zero_interval_rejected() {
+		let ctx = context(LifecycleHooks::new());
+		let builder = || {
+			TimerBuilder::new(Arc::new(ctx.clone()))
+				.name("zero")
+				.callback(|_| Ok(()))
+		};
+		for timer in [
+			builder().interval(Duration::ZERO).build(),
+			builder().watchdog(Duration::ZERO).build(),
+			builder().aligned(Duration::ZERO).build(),
+		] {
+			let error = timer.expect_err("snh");
+			assert!(error.to_string().contains("must not be zero"));
+		}
+		// a one shot timer may fire at once
+		assert!(builder().one_shot(Duration::ZERO).build().is_ok());
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn timer_set_interval() {
+		let (ctx, clock, fired) = timed("interval", |builder| {
+			builder.interval(Duration::from_secs(1))
+		});
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 1);
+
+		ctx.timer("interval")
+			.set_interval(Duration::from_secs(3))
+			.expect("snh");
+		settle().await;
+		clock.advance(Duration::from_secs(2));
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 1);
+		clock.advance(Duration::from_secs(1));
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 2);
+
+		assert!(
+			ctx.timer("interval")
+				.set_interval(Duration::ZERO)
+				.is_err()
+		);
+		assert!(
+			ctx.timer("unknown")
+				.set_interval(Duration::from_secs(1))
+				.is_err()
+		);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn timer_pause_resume() {
+		let (ctx, clock, fired) = timed("interval", |builder| {
+			builder.interval(Duration::from_secs(1))
+		});
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 1);
+
+		let timer = ctx.timer("interval");
+		timer.pause().expect("snh");
+		for _ in 0..3 {
+			clock.advance(Duration::from_secs(1));
+			settle().await;
+		}
+		assert_eq!(fired.load(Ordering::Relaxed), 1);
+
+		// a paused timer stays paused across state changes
+		ctx.change_state(OperationState::Standby)
+			.expect("snh");
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 1);
+
+		// resuming restarts it with an immediate first tick
+		timer.resume().expect("snh");
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 2);
+		clock.advance(Duration::from_secs(1));
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 3);
+
+		assert!(ctx.timer("unknown").pause().is_err());
+		assert!(ctx.timer("unknown").resume().is_err());
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn timer_trigger_now() {
+		let (ctx, clock, fired) = timed("delayed", |builder| {
+			builder
+				.interval(Duration::from_secs(1))
+				.delay(Duration::from_secs(3))
+		});
+		// fires even if the timer is not running
+		let timer = ctx.timer("delayed");
+		timer.trigger_now().expect("snh");
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 1);
+
+		// does not change the schedule of a running timer
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		settle().await;
+		timer.trigger_now().expect("snh");
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 2);
+		clock.advance(Duration::from_secs(3));
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 3);
+
+		// and fires while paused
+		timer.pause().expect("snh");
+		timer.trigger_now().expect("snh");
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 4);
+		clock.advance(Duration::from_secs(3));
+		settle().await;
+		assert_eq!(fired.load(Ordering::Relaxed), 4);
+
+		assert!(ctx.timer("unknown").trigger_now().is_err());
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn panicking_trigger_now_keeps_paused_timer() {
+		let (sender, mut receiver) = tokio::sync::mpsc::channel(8);
+		let ctx = ContextImpl::new(
+			&Config::default(),
+			Props {},
+			None,
+			sender,
+			None,
+			LifecycleHooks::new(),
+			Arc::new(SimulatedClock::default()),
+		)
+		.expect("snh");
+		TimerBuilder::new(Arc::new(ctx.clone()))
+			.name("panicking")
+			.interval(Duration::from_secs(3600))
+			.callback(|_| panic!("by intention"))
+			.storage(ctx.timers())
+			.add()
+			.expect("snh");
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		settle().await;
+		// the first tick panics and asks for a restart of the running timer
+		assert!(matches!(
+			receiver.try_recv(),
+			Ok(TaskSignal::RestartTimer(_))
+		));
+
+		let timer = ctx.timer("panicking");
+		timer.pause().expect("snh");
+		timer.trigger_now().expect("snh");
+		settle().await;
+		assert!(receiver.try_recv().is_err());
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn shutdown_drains() {
+		let (ctx, finished) = busy("busy", Duration::from_millis(200));
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		settle().await;
+		let aborted = ctx
+			.shutdown(Duration::from_secs(2))
+			.await
+			.expect("snh");
+		assert!(aborted.is_empty());
+		assert_eq!(finished.load(Ordering::Relaxed), 1);
+		assert_eq!(ctx.state(), OperationState::Created);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn shutdown_aborts() {
+		let (ctx, finished) = busy("busy", Duration::from_secs(10));
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		settle().await;
+		let aborted = ctx
+			.shutdown(Duration::from_millis(100))
+			.await
+			.expect("snh");
+		assert_eq!(aborted, vec![String::from("busy")]);
+		assert_eq!(finished.load(Ordering::Relaxed), 0);
+		assert_eq!(ctx.state(), OperationState::Created);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn shutdown_from_error() {
+		let (ctx, finished) = busy("busy", Duration::from_millis(200));
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		settle().await;
+		ctx.set_error("test".into()).expect("snh");
+		let aborted = ctx
+			.shutdown(Duration::from_secs(2))
+			.await
+			.expect("snh");
+		assert!(aborted.is_empty());
+		assert_eq!(finished.load(Ordering::Relaxed), 1);
+		assert_eq!(ctx.state(), OperationState::Created);
+		let running = ctx.timers().read().expect("snh")["busy"]
+			.describe()
+			.is_running();
+		assert!(!running);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn panicked_task_not_running() {
+		let ctx = context(LifecycleHooks::new());
+		SubscriberBuilder::new("default", Arc::new(ctx.clone()))
+			.selector("test/panic")
+			.put_callback(|_, msg| async move {
+				assert!(msg.value().is_empty(), "unexpected message");
+				Ok(())
+			})
+			.storage(ctx.responders())
+			.add()
+			.expect("snh");
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		settle().await;
+		let running = || {
+			ctx.describe_capabilities()
+				.expect("snh")
+				.iter()
+				.any(|description| {
+					description.selector() == "test/panic" && description.is_running()
+				})
+		};
+		assert!(running());
+
+		ctx.put_with("test/panic", Message::new(vec![1]))
+			.expect("snh");
+		settle().await;
+		assert!(!running());
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn query_retries() {
+		let ctx = context(LifecycleHooks::new());
+		let policy = RetryPolicy {
+			max_attempts: 3,
+			backoff: Backoff::Fixed(Duration::from_millis(10)),
+			..RetryPolicy::new()
+		};
+		querier(&ctx, "test/nobody", Duration::from_millis(100), policy);
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+
+		let error = ctx
+			.get_with("test/nobody", None, None)
+			.expect_err("snh");
+		assert_eq!(exhausted(error.as_ref()), 3);
+
+		let replies: Vec<_> = ctx
+			.get_async_with("test/nobody", None, None)
+			.expect("snh")
+			.collect()
+			.await;
+		assert_eq!(replies.len(), 1);
+		let error = replies
+			.into_iter()
+			.next()
+			.expect("snh")
+			.expect_err("snh");
+		assert_eq!(exhausted(error.as_ref()), 3);
+
+		// ad-hoc queries use the default policy
+		let error = ctx
+			.get_with("test/adhoc", None, None)
+			.expect_err("snh");
+		assert_eq!(exhausted(error.as_ref()), RetryPolicy::new().max_attempts);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn query_deadline() {
+		let ctx = context(LifecycleHooks::new());
+		// a queryable never answering
+		let _silent = ctx
+			.default_session()
+			.declare_queryable("test/silent")
+			.await
+			.expect("snh");
+		let policy = RetryPolicy {
+			deadline: Some(Duration::from_millis(300)),
+			..RetryPolicy::new()
+		};
+		querier(&ctx, "test/silent", Duration::from_secs(10), policy);
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+
+		// the attempt is cut at the deadline, which is reported as error reply
+		let started = std::time::Instant::now();
+		let replies: Vec<_> = ctx
+			.get_async_with("test/silent", None, None)
+			.expect("snh")
+			.collect()
+			.await;
+		assert!(started.elapsed() < Duration::from_secs(2));
+		assert_eq!(replies.len(), 1);
+		assert!(replies[0].is_err());
+
+		// no further attempt is started after the deadline
+		let policy = RetryPolicy {
+			max_attempts: 100,
+			deadline: Some(Duration::from_millis(300)),
+			..RetryPolicy::new()
+		};
+		querier(&ctx, "test/nobody", Duration::from_secs(10), policy);
+		let started = std::time::Instant::now();
+		let error = ctx
+			.get_with("test/nobody", None, None)
+			.expect_err("snh");
+		assert!(started.elapsed() < Duration::from_secs(2));
+		let attempts = exhausted(error.as_ref());
+		assert!(attempts > 1 && attempts < 100);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn error_policy_ignore_log() {
+		let ctx: Context<Props> = Arc::new(context(LifecycleHooks::new()));
+		ctx.set_state(OperationState::Active)
+			.expect("snh");
+		for policy in [ErrorPolicy::Ignore, ErrorPolicy::Log] {
+			let (errors, informed) = error_handler(policy);
+			for _ in 0..10 {
+				errors.handle(&ctx, "test", failed());
+			}
+			// the error callback is informed independent of the policy
+			assert_eq!(informed.load(Ordering::Relaxed), 10);
+			assert_eq!(ctx.state(), OperationState::Active);
+		}
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn error_policy_escalate() {
+		let ctx: Context<Props> = Arc::new(context(LifecycleHooks::new()));
+		ctx.set_state(OperationState::Active)
+			.expect("snh");
+		let (errors, informed) = error_handler(ErrorPolicy::Escalate(3));
+
+		// a success resets the count of consecutive errors
+		errors.handle(&ctx, "test", failed());
+		errors.handle(&ctx, "test", failed());
+		errors.handle(&ctx, "test", Ok(()));
+		errors.handle(&ctx, "test", failed());
+		errors.handle(&ctx, "test", failed());
+		assert_eq!(ctx.state(), OperationState::Active);
+
+		errors.handle(&ctx, "test", failed());
+		assert_eq!(ctx.state(), OperationState::Error);
+		assert_eq!(informed.load(Ordering::Relaxed), 5);
+		let cause = ctx.last_error().expect("snh");
+		assert!(cause.contains("'test' failed 3 times"));
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn error_policy_transition() {
+		let ctx: Context<Props> = Arc::new(context(LifecycleHooks::new()));
+		ctx.set_state(OperationState::Active)
+			.expect("snh");
+		let (errors, informed) = error_handler(ErrorPolicy::Transition(OperationState::Standby));
+
+		errors.handle(&ctx, "test", Ok(()));
+		assert_eq!(ctx.state(), OperationState::Active);
+		errors.handle(&ctx, "test", failed());
+		assert_eq!(ctx.state(), OperationState::Standby);
+		assert_eq!(informed.load(Ordering::Relaxed), 1);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn publisher_codec() {
+		let ctx = context(LifecycleHooks::new());
+		PublisherBuilder::new("default", Arc::new(ctx.clone()))
+			.selector("test/json")
+			.codec::<Json>()
+			.storage(ctx.publishers())
+			.add()
+			.expect("snh");
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+
+		// a message encoded with another codec is rejected
+		let error = ctx
+			.put_with("test/json", Message::encode(&42_u32))
+			.expect_err("snh");
+		assert!(matches!(
+			error.downcast_ref::<dimas_core::error::Error>(),
+			Some(dimas_core::error::Error::UnexpectedEncoding { .. })
+		));
+		let message = Message::encode_with::<Json, _>(&42_u32).expect("snh");
+		ctx.put_with("test/json", message).expect("snh");
+		// raw data is published unchecked
+		ctx.put_with("test/json", Message::new(b"42".to_vec()))
+			.expect("snh");
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn trace_across_sessions() {
+		let server = context(LifecycleHooks::new());
+		QueryableBuilder::new("default", Arc::new(server.clone()))
+			.selector("test/trace")
+			.callback(|_, request| async move { request.reply(42_u32) })
+			.storage(server.responders())
+			.add()
+			.expect("snh");
+		server
+			.change_state(OperationState::Active)
+			.expect("snh");
+		// the client has its own session
+		let client = context(LifecycleHooks::new());
+
+		let root = TraceContext::new_root();
+		let reply = root
+			.scope("test", async {
+				// the sessions may need some time to discover each other
+				for _ in 0..50 {
+					let replies = client
+						.get_async_with("test/trace", None, Some(Duration::from_millis(100)))
+						.expect("snh");
+					if let Some(reply) = replies.collect::<Vec<_>>().await.pop() {
+						return reply.expect("snh");
+					}
+				}
+				panic!("no reply");
+			})
+			.await;
+
+		// the reply is sent from within the trace of the query
+		let traceparent = reply
+			.headers()
+			.get(TRACEPARENT)
+			.expect("snh")
+			.to_string();
+		let sent: TraceContext = traceparent.parse().expect("snh");
+		assert_eq!(sent.trace_id(), root.trace_id());
+		assert_ne!(sent.span_id(), root.span_id());
+		assert_eq!(reply.decode::<u32>().ok(), Some(42));
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn props_changed_once() {
+		let called = Arc::new(AtomicU32::new(0));
+		let counter = called.clone();
+		let mut hooks = LifecycleHooks::new();
+		hooks.props_changed = Some(Arc::new(Mutex::new(move |ctx: Context<Props>| {
+			counter.fetch_add(1, Ordering::Relaxed);
+			// a hook writing the properties
+			drop(ctx.write()?);
+			Ok(())
+		})));
+		let ctx = context(hooks);
+		let mut watcher = ctx.watch_props();
+
+		drop(ctx.write().expect("snh"));
+		// as the running agent does on every notification
+		for _ in 0..3 {
+			if watcher.has_changed().expect("snh") {
+				watcher.borrow_and_update();
+				ctx.props_changed().expect("snh");
+			}
+		}
+		assert_eq!(called.load(Ordering::Relaxed), 1);
+
+		drop(ctx.write().expect("snh"));
+		ctx.props_changed().expect("snh");
+		assert_eq!(called.load(Ordering::Relaxed), 2);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn sample_info() {
+		type Received = Mutex<Option<(u32, Option<SampleInfo>)>>;
+		let ctx = context(LifecycleHooks::new());
+		let received: Arc<Received> = Arc::new(Mutex::new(None));
+		let store = received.clone();
+		SubscriberBuilder::new("default", Arc::new(ctx.clone()))
+			.selector("test/info/*")
+			.typed::<u32>()
+			.put_callback_with_info(move |_, value, info| {
+				store.lock().expect("snh").replace((value, info));
+				async { Ok(()) }
+			})
+			.storage(ctx.responders())
+			.add()
+			.expect("snh");
+		QueryableBuilder::new("default", Arc::new(ctx.clone()))
+			.selector("test/info/query")
+			.callback(|_, request| async move { request.reply(42_u32) })
+			.storage(ctx.responders())
+			.add()
+			.expect("snh");
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		settle().await;
+
+		// the info of a sample received via a wildcard selector
+		let message = Message::encode(&7_u32).with_header("schema", "v2");
+		ctx.put_with("test/info/put", message)
+			.expect("snh");
+		settle().await;
+		let (value, info) = received.lock().expect("snh").take().expect("snh");
+		assert_eq!(value, 7);
+		let info = info.expect("snh");
+		assert_eq!(info.key_expr(), "test/info/put");
+		assert_eq!(info.encoding(), Bitcode::ENCODING);
+		assert_eq!(info.headers().get("schema"), Some("v2"));
+
+		// the info of a reply
+		let reply = ctx
+			.get_async_with("test/info/query", None, None)
+			.expect("snh")
+			.collect::<Vec<_>>()
+			.await
+			.pop()
+			.expect("snh")
+			.expect("snh");
+		let info = reply.info().expect("snh");
+		assert_eq!(info.key_expr(), "test/info/query");
+		// the reply carries the trace of the queryable callback
+		assert!(info.headers().get(TRACEPARENT).is_some());
+		assert_eq!(reply.decode::<u32>().ok(), Some(42));
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn typed_publish_subscribe() {
+		let ctx = context(LifecycleHooks::new());
+		let publisher = PublisherBuilder::new("default", Arc::new(ctx.clone()))
+			.selector("test/typed/value")
+			.storage(ctx.publishers())
+			.add_typed::<u32>()
+			.expect("snh");
+		let received = Arc::new(Mutex::new(Vec::new()));
+		let store = received.clone();
+		let undecodable = Arc::new(AtomicU32::new(0));
+		let counter = undecodable.clone();
+		let errors = Arc::new(AtomicU32::new(0));
+		let error_counter = errors.clone();
+		SubscriberBuilder::new("default", Arc::new(ctx.clone()))
+			.selector("test/typed/*")
+			.on_error(move |_, _, _| {
+				error_counter.fetch_add(1, Ordering::Relaxed);
+			})
+			.typed::<u32>()
+			.on_decode_error(move |_, msg, _| {
+				assert_eq!(msg.encoding(), Some(Json::ENCODING));
+				counter.fetch_add(1, Ordering::Relaxed);
+			})
+			.put_callback(move |_, value| {
+				store.lock().expect("snh").push(value);
+				async { Ok(()) }
+			})
+			.storage(ctx.responders())
+			.add()
+			.expect("snh");
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		settle().await;
+
+		// the published value arrives decoded
+		publisher.put(&42).expect("snh");
+		settle().await;
+		assert_eq!(*received.lock().expect("snh"), vec![42]);
+
+		// a message of another codec goes to the decode error callback only
+		let message = Message::encode_with::<Json, _>(&7_u32).expect("snh");
+		ctx.put_with("test/typed/json", message)
+			.expect("snh");
+		settle().await;
+		assert_eq!(*received.lock().expect("snh"), vec![42]);
+		assert_eq!(undecodable.load(Ordering::Relaxed), 1);
+		assert_eq!(errors.load(Ordering::Relaxed), 0);
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn typed_decode_error_policy() {
+		let ctx = context(LifecycleHooks::new());
+		let received = Arc::new(AtomicU32::new(0));
+		let counter = received.clone();
+		let errors = Arc::new(AtomicU32::new(0));
+		let error_counter = errors.clone();
+		SubscriberBuilder::new("default", Arc::new(ctx.clone()))
+			.selector("test/policy")
+			.error_policy(ErrorPolicy::Transition(OperationState::Standby))
+			.on_error(move |_, _, _| {
+				error_counter.fetch_add(1, Ordering::Relaxed);
+			})
+			.typed::<u32>()
+			.put_callback(move |_, _| {
+				counter.fetch_add(1, Ordering::Relaxed);
+				async { Ok(()) }
+			})
+			.storage(ctx.responders())
+			.add()
+			.expect("snh");
+		ctx.change_state(OperationState::Active)
+			.expect("snh");
+		settle().await;
+
+		// without a decode error callback an undecodable message is an error of the subscriber
+		let message = Message::encode_with::<Json, _>(&7_u32).expect("snh");
+		ctx.put_with("test/policy", message).expect("snh");
+		settle().await;
+		assert_eq!(received.load(Ordering::Relaxed), 0);
+		assert_eq!(errors.load(Ordering::Relaxed), 1);
+		assert_eq!(ctx.state(), OperationState::Standby);
+	}
 }
@@ -2,6 +2,7 @@
 
 //! `dimas` errors
 
+use dimas_core::enums::OperationState;
 use thiserror::Error;
 
 // region:		--- Error
@@ -14,9 +15,70 @@ pub enum Error {
 		/// the original zenoh error
 		source: Box<dyn core::error::Error + Send + Sync>,
 	},
+	/// a lifecycle hook failed
+	#[error("lifecycle hook for transition to {state} failed: reason {source}")]
+	LifecycleHook {
+		/// the state the transition was heading to
+		state: OperationState,
+		/// the error returned by the hook
+		source: Box<dyn core::error::Error + Send + Sync>,
+	},
+	/// the reload hook failed
+	#[error("reload hook failed: reason {source}")]
+	ReloadHook {
+		/// the error returned by the hook
+		source: Box<dyn core::error::Error + Send + Sync>,
+	},
+	/// the props changed hook failed
+	#[error("props changed hook failed: reason {source}")]
+	PropsChangedHook {
+		/// the error returned by the hook
+		source: Box<dyn core::error::Error + Send + Sync>,
+	},
 	/// manage state failed
 	#[error("managing state failed")]
 	ManageState,
+	/// the agent is in state `Error` and has to be recovered first
+	#[error("agent is in state 'Error', recover first")]
+	InErrorState,
+	/// recovery is only possible from state `Error`
+	#[error("agent is not in state 'Error'")]
+	NotInErrorState,
+	/// recovery is only possible into a state up to `Configured`
+	/// and not above the state the agent failed in
+	#[error("can not recover into state {0}")]
+	InvalidRecoveryState(OperationState),
+	/// a transition guard rejected the requested state
+	#[error("transition to {state} rejected: {reason}")]
+	TransitionRejected {
+		/// the requested state
+		state: OperationState,
+		/// the reason given by the guard
+		reason: String,
+	},
+	/// the properties can not be accessed as parameters
+	#[error("properties are not a struct with named fields")]
+	NoParameters,
+	/// there is no parameter with the given name
+	#[error("unknown parameter '{0}'")]
+	UnknownParameter(String),
+	/// the new value of a parameter is not acceptable
+	#[error("invalid value for parameter '{name}': {reason}")]
+	InvalidParameter {
+		/// the name of the parameter
+		name: String,
+		/// the reason why the value is not acceptable
+		reason: String,
+	},
+	/// encoding the properties failed
+	#[error("encoding the properties failed: reason {source}")]
+	ParameterEncoding {
+		/// the original serde error
+		source: Box<dyn core::error::Error + Send + Sync>,
+	},
+	/// there is no timer with the given name
+	#[error("unknown timer '{0}'")]
+	UnknownTimer(String),
 	/// callback is missing
 	#[error("callback is missing")]
 	MissingCallback,
@@ -16,6 +16,9 @@ doc_comment::doctest!("../README.md");
 pub mod agent;
 mod context;
 pub mod error;
+mod lifecycle;
+pub mod parameters;
+mod signals;
 // macro reexport
 pub use dimas_macros::main;
 
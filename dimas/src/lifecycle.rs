@@ -0,0 +1,178 @@
+// Copyright © 2024 Stephan Kunz
+
+//! Lifecycle hooks of an [`Agent`].
+//!
+//! The hooks are user defined functions which are called when the [`Agent`]
+//! passes the corresponding [`OperationState`] transition:
+//! - `on_configure`: `Created` -> `Configured`
+//! - `on_activate`: `Standby` -> `Active`
+//! - `on_deactivate`: `Active` -> `Standby`
+//! - `on_cleanup`: `Configured` -> `Created`
+//! - `on_error`: whenever the [`Agent`] enters the state `Error`
+//!
+//! The hook `on_reload` is not bound to a transition, it is called when the
+//! running [`Agent`] receives `SIGHUP`.
+//! Neither is `on_props_changed`, which is called by the running [`Agent`]
+//! after its properties have been written.
+//!
+//! A hook returning an error stops the transition,
+//! the [`Agent`] stays in the state it had before the failing step.
+//!
+//! Additionally transition guards can be registered, which are asked before a requested
+//! state change is started. Any guard returning an error vetoes the whole change.
+
+// region:		--- modules
+// only for doc needed
+#[cfg(doc)]
+use crate::agent::Agent;
+use core::fmt::Debug;
+use dimas_core::{Result, enums::OperationState, traits::Context};
+use std::{
+	sync::{Arc, Mutex},
+	vec::Vec,
+};
+// endregion:	--- modules
+
+// region:		--- types
+/// Type definition for the functions called on a lifecycle transition
+pub type ArcLifecycleCallback<P> =
+	Arc<Mutex<dyn FnMut(Context<P>) -> Result<()> + Send + Sync + 'static>>;
+
+/// Type definition for the guards checking a requested transition.
+/// The arguments are the current and the requested [`OperationState`].
+pub type ArcTransitionGuard<P> =
+	Arc<dyn Fn(Context<P>, &OperationState, &OperationState) -> Result<()> + Send + Sync + 'static>;
+// endregion:	--- types
+
+// region:		--- LifecycleHooks
+/// The registered lifecycle hooks of an [`Agent`]
+pub struct LifecycleHooks<P>
+where
+	P: Send + Sync + 'static,
+{
+	/// Called on transition from `Created` to `Configured`
+	pub configure: Option<ArcLifecycleCallback<P>>,
+	/// Called on transition from `Standby` to `Active`
+	pub activate: Option<ArcLifecycleCallback<P>>,
+	/// Called on transition from `Active` to `Standby`
+	pub deactivate: Option<ArcLifecycleCallback<P>>,
+	/// Called on transition from `Configured` to `Created`
+	pub cleanup: Option<ArcLifecycleCallback<P>>,
+	/// Called when entering `Error`
+	pub error: Option<ArcLifecycleCallback<P>>,
+	/// Called on a reload request
+	pub reload: Option<ArcLifecycleCallback<P>>,
+	/// Called after the properties have been written
+	pub props_changed: Option<ArcLifecycleCallback<P>>,
+	/// Guards which may veto a requested state change
+	pub guards: Vec<ArcTransitionGuard<P>>,
+}
+
+impl<P> Default for LifecycleHooks<P>
+where
+	P: Send + Sync + 'static,
+{
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<P> Debug for LifecycleHooks<P>
+where
+	P: Send + Sync + 'static,
+{
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("LifecycleHooks")
+			.field("configure", &self.configure.is_some())
+			.field("activate", &self.activate.is_some())
+			.field("deactivate", &self.deactivate.is_some())
+			.field("cleanup", &self.cleanup.is_some())
+			.field("error", &self.error.is_some())
+			.field("reload", &self.reload.is_some())
+			.field("props_changed", &self.props_changed.is_some())
+			.field("guards", &self.guards.len())
+			.finish()
+	}
+}
+
+impl<P> LifecycleHooks<P>
+where
+	P: Send + Sync + 'static,
+{
+	/// Constructor for an empty set of hooks
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			configure: None,
+			activate: None,
+			deactivate: None,
+			cleanup: None,
+			error: None,
+			reload: None,
+			props_changed: None,
+			guards: Vec::new(),
+		}
+	}
+
+	/// Get the hook for a single step transition between two [`OperationState`]s
+	#[must_use]
+	pub fn for_transition(
+		&self,
+		from: &OperationState,
+		to: &OperationState,
+	) -> Option<ArcLifecycleCallback<P>> {
+		match (from, to) {
+			(_, OperationState::Error) => self.error.clone(),
+			(OperationState::Created, OperationState::Configured) => self.configure.clone(),
+			(OperationState::Standby, OperationState::Active) => self.activate.clone(),
+			(OperationState::Active, OperationState::Standby) => self.deactivate.clone(),
+			(OperationState::Configured, OperationState::Created) => self.cleanup.clone(),
+			_ => None,
+		}
+	}
+}
+// endregion:	--- LifecycleHooks
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[derive(Debug)]
+	struct Props {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<LifecycleHooks<Props>>();
+	}
+
+	#[test]
+	fn hook_selection() {
+		let mut hooks = LifecycleHooks::<Props>::default();
+		assert!(
+			hooks
+				.for_transition(&OperationState::Created, &OperationState::Configured)
+				.is_none()
+		);
+		let callback: ArcLifecycleCallback<Props> = Arc::new(Mutex::new(|_| Ok(())));
+		hooks.configure = Some(callback.clone());
+		hooks.error = Some(callback);
+		assert!(
+			hooks
+				.for_transition(&OperationState::Created, &OperationState::Configured)
+				.is_some()
+		);
+		assert!(
+			hooks
+				.for_transition(&OperationState::Configured, &OperationState::Inactive)
+				.is_none()
+		);
+		assert!(
+			hooks
+				.for_transition(&OperationState::Active, &OperationState::Error)
+				.is_some()
+		);
+	}
+}
@@ -0,0 +1,374 @@
+// Copyright © 2024 Stephan Kunz
+
+//! Remote access to the properties of an [`Agent`].
+//!
+//! The parameter server serves the fields of the [`Agent`]s properties at `<fq_name>/params`,
+//! an [`Agent`] without a name uses its zenoh id instead.
+//! Each field can be listed, read and written by its name, values are exchanged JSON encoded.
+//!
+//! A new value is only accepted, if it fits to the type of the field and
+//! an optionally registered validator for that field agrees.
+//! After a change the registered change callback is called with the name of the changed field.
+//!
+//! Fields skipped by serde are not served, a change of another field leaves them untouched.
+
+// region:		--- modules
+// only for doc needed
+#[cfg(doc)]
+use crate::agent::Agent;
+use crate::error::Error;
+use core::fmt::Debug;
+use dimas_com::{builder::QueryableBuilder, traits::Responder};
+use dimas_commands::messages::{ParameterEntity, ParametersEntity};
+use dimas_core::{
+	Result,
+	enums::{OperationState, ParameterRequest},
+	handle::CapabilityHandle,
+	message_types::QueryMsg,
+	traits::Context,
+};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+use std::{
+	collections::HashMap,
+	string::{String, ToString},
+	sync::{Arc, Mutex, RwLock},
+	vec::Vec,
+};
+use tracing::warn;
+// endregion:	--- modules
+
+// region:		--- types
+/// Type definition for the validators checking a new parameter value
+pub type ArcParameterValidator = Arc<dyn Fn(&Value) -> Result<()> + Send + Sync + 'static>;
+
+/// Type definition for the function called after a parameter has been changed.
+/// The argument is the name of the changed parameter.
+pub type ArcParameterCallback<P> =
+	Arc<Mutex<dyn FnMut(Context<P>, &str) -> Result<()> + Send + Sync + 'static>>;
+// endregion:	--- types
+
+// region:		--- ParameterServer
+/// The registered type descriptions, validators and change callback
+struct ParameterServer<P>
+where
+	P: Send + Sync + 'static,
+{
+	descriptions: HashMap<String, String>,
+	validators: HashMap<String, ArcParameterValidator>,
+	on_change: Option<ArcParameterCallback<P>>,
+}
+
+impl<P> ParameterServer<P>
+where
+	P: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	fn new() -> Self {
+		Self {
+			descriptions: HashMap::new(),
+			validators: HashMap::new(),
+			on_change: None,
+		}
+	}
+
+	/// The properties as map of field names to values
+	fn fields(props: &P) -> Result<Map<String, Value>> {
+		match serde_json::to_value(props).map_err(|source| Error::ParameterEncoding {
+			source: source.into(),
+		})? {
+			Value::Object(fields) => Ok(fields),
+			_ => Err(Error::NoParameters.into()),
+		}
+	}
+
+	fn entity(&self, name: &str, value: &Value) -> ParameterEntity {
+		let type_description = self
+			.descriptions
+			.get(name)
+			.cloned()
+			.unwrap_or_else(|| json_type(value).to_string());
+		ParameterEntity::new(name.to_string(), type_description, value.to_string())
+	}
+
+	fn list(&self, props: &P) -> Result<Vec<ParameterEntity>> {
+		Ok(Self::fields(props)?
+			.iter()
+			.map(|(name, value)| self.entity(name, value))
+			.collect())
+	}
+
+	fn get(&self, props: &P, name: &str) -> Result<ParameterEntity> {
+		let fields = Self::fields(props)?;
+		let value = fields
+			.get(name)
+			.ok_or_else(|| Error::UnknownParameter(name.to_string()))?;
+		Ok(self.entity(name, value))
+	}
+
+	/// Set the parameter `name` of `props` to `value`.
+	/// The properties are changed in place, so fields skipped by serde keep their values.
+	fn set(&self, props: &mut P, name: &str, value: &str) -> Result<ParameterEntity> {
+		let invalid = |reason: String| Error::InvalidParameter {
+			name: name.to_string(),
+			reason,
+		};
+		let mut fields = Self::fields(props)?;
+		if !fields.contains_key(name) {
+			return Err(Error::UnknownParameter(name.to_string()).into());
+		}
+		let value: Value = serde_json::from_str(value).map_err(|err| invalid(err.to_string()))?;
+		if let Some(validator) = self.validators.get(name) {
+			validator(&value).map_err(|err| invalid(err.to_string()))?;
+		}
+		let entity = self.entity(name, &value);
+		fields.insert(name.to_string(), value);
+		let fields = Value::Object(fields);
+		// check the value before changing anything, a failing in place change is not undone
+		P::deserialize(&fields).map_err(|err| invalid(err.to_string()))?;
+		P::deserialize_in_place(fields, props).map_err(|err| invalid(err.to_string()))?;
+		Ok(entity)
+	}
+
+	fn handle(&self, ctx: &Context<P>, request: ParameterRequest) -> Result<Vec<ParameterEntity>> {
+		match request {
+			ParameterRequest::List => self.list(&*ctx.read()?),
+			ParameterRequest::Get { name } => Ok(vec![self.get(&*ctx.read()?, &name)?]),
+			ParameterRequest::Set { name, value } => {
+				let entity = self.set(&mut *ctx.write()?, &name, &value)?;
+				if let Some(callback) = &self.on_change {
+					let mut callback = callback.lock().map_err(|_| {
+						Error::MutexPoison(String::from("parameter change callback"))
+					})?;
+					if let Err(error) = callback(ctx.clone(), &name) {
+						warn!("change callback for parameter '{name}' failed: {error}");
+					}
+				}
+				Ok(vec![entity])
+			}
+		}
+	}
+
+	fn respond(&self, ctx: Context<P>, request: QueryMsg) -> Result<()> {
+		let name = ctx
+			.fq_name()
+			.unwrap_or_else(|| String::from("--"));
+		let zid = ctx.uuid();
+		let value = match request
+			.decode::<ParameterRequest>()
+			.and_then(|parameter_request| self.handle(&ctx, parameter_request))
+		{
+			Ok(parameters) => ParametersEntity::new(name, zid, parameters, None),
+			Err(reason) => ParametersEntity::new(name, zid, Vec::new(), Some(reason.to_string())),
+		};
+		drop(ctx);
+		request.reply(value)
+	}
+}
+
+/// The JSON type of a value, used if no type description is registered
+const fn json_type(value: &Value) -> &'static str {
+	match value {
+		Value::Null => "null",
+		Value::Bool(_) => "boolean",
+		Value::Number(_) => "number",
+		Value::String(_) => "string",
+		Value::Array(_) => "array",
+		Value::Object(_) => "object",
+	}
+}
+// endregion:	--- ParameterServer
+
+// region:		--- ParameterServerBuilder
+/// The builder for the parameter server of an [`Agent`]
+pub struct ParameterServerBuilder<P>
+where
+	P: Send + Sync + 'static,
+{
+	context: Context<P>,
+	storage: Arc<RwLock<HashMap<String, Box<dyn Responder>>>>,
+	server: ParameterServer<P>,
+}
+
+impl<P> Debug for ParameterServerBuilder<P>
+where
+	P: Send + Sync + 'static,
+{
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		f.debug_struct("ParameterServerBuilder")
+			.field("descriptions", &self.server.descriptions)
+			.field("on_change", &self.server.on_change.is_some())
+			.finish_non_exhaustive()
+	}
+}
+
+impl<P> ParameterServerBuilder<P>
+where
+	P: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	/// Construct a [`ParameterServerBuilder`] storing the parameter server in `storage`
+	#[must_use]
+	pub fn new(
+		context: Context<P>,
+		storage: Arc<RwLock<HashMap<String, Box<dyn Responder>>>>,
+	) -> Self {
+		Self {
+			context,
+			storage,
+			server: ParameterServer::new(),
+		}
+	}
+
+	/// Set the type description of the parameter `name`.
+	/// Without a description the JSON type of the current value is reported.
+	#[must_use]
+	pub fn describe(mut self, name: impl Into<String>, description: impl Into<String>) -> Self {
+		self.server
+			.descriptions
+			.insert(name.into(), description.into());
+		self
+	}
+
+	/// Set a validator for the parameter `name`, which is asked before a new value is set.
+	/// An error returned by the validator rejects the new value and is reported as reason.
+	#[must_use]
+	pub fn validate<T, F>(mut self, name: impl Into<String>, validator: F) -> Self
+	where
+		T: DeserializeOwned,
+		F: Fn(&T) -> Result<()> + Send + Sync + 'static,
+	{
+		let validator: ArcParameterValidator = Arc::new(move |value: &Value| {
+			let value = T::deserialize(value)?;
+			validator(&value)
+		});
+		self.server
+			.validators
+			.insert(name.into(), validator);
+		self
+	}
+
+	/// Set a callback, which is called with the name of a parameter after it has been changed.
+	#[must_use]
+	pub fn on_change<F>(mut self, callback: F) -> Self
+	where
+		F: FnMut(Context<P>, &str) -> Result<()> + Send + Sync + 'static,
+	{
+		self.server.on_change = Some(Arc::new(Mutex::new(callback)));
+		self
+	}
+
+	/// Build the parameter server and add it to the agent.
+	/// # Errors
+	/// if there is already a parameter server
+	pub fn add(self) -> Result<CapabilityHandle<P, Box<dyn Responder>>> {
+		let Self {
+			context,
+			storage,
+			server,
+		} = self;
+		let selector = format!(
+			"{}/params",
+			context
+				.fq_name()
+				.unwrap_or_else(|| context.uuid())
+		);
+		let server = Arc::new(server);
+		QueryableBuilder::new("default", context)
+			.storage(storage)
+			.selector(&selector)
+			.callback(move |ctx, request| {
+				let server = server.clone();
+				async move { server.respond(ctx, request) }
+			})
+			.activation_state(OperationState::Created)
+			.add()
+	}
+}
+// endregion:	--- ParameterServerBuilder
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde::Deserialize;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[derive(Debug, Serialize, Deserialize)]
+	struct Props {
+		gain: f64,
+		name: String,
+		#[serde(skip)]
+		cache: Vec<u32>,
+	}
+
+	fn props() -> Props {
+		Props {
+			gain: 1.5,
+			name: String::from("robot"),
+			cache: vec![1, 2, 3],
+		}
+	}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<ParameterServerBuilder<Props>>();
+	}
+
+	#[test]
+	fn get_and_list() {
+		let mut server = ParameterServer::<Props>::new();
+		server
+			.descriptions
+			.insert("gain".into(), "f64 in [0, 10]".into());
+
+		let gain = server.get(&props(), "gain").expect("snh");
+		assert_eq!(gain.type_description(), "f64 in [0, 10]");
+		assert_eq!(gain.value(), "1.5");
+		let name = server.get(&props(), "name").expect("snh");
+		assert_eq!(name.type_description(), "string");
+		assert_eq!(name.value(), "\"robot\"");
+		assert!(server.get(&props(), "unknown").is_err());
+
+		assert_eq!(server.list(&props()).expect("snh").len(), 2);
+	}
+
+	#[test]
+	fn set_with_validation() {
+		let validator: ArcParameterValidator = Arc::new(|value: &Value| {
+			if value.as_f64().is_some_and(|gain| gain <= 10.0) {
+				Ok(())
+			} else {
+				Err("gain too high".into())
+			}
+		});
+		let mut server = ParameterServer::<Props>::new();
+		server.validators.insert("gain".into(), validator);
+
+		let mut props = props();
+		let entity = server
+			.set(&mut props, "gain", "2.5")
+			.expect("snh");
+		assert!((props.gain - 2.5).abs() < f64::EPSILON);
+		assert_eq!(entity.value(), "2.5");
+
+		// rejected by validator, by type and for unknown parameters
+		assert!(server.set(&mut props, "gain", "12.0").is_err());
+		assert!(server.set(&mut props, "name", "12.0").is_err());
+		assert!(server.set(&mut props, "unknown", "1").is_err());
+		assert!((props.gain - 2.5).abs() < f64::EPSILON);
+		assert_eq!(props.name, "robot");
+	}
+
+	#[test]
+	fn set_keeps_skipped_fields() {
+		let server = ParameterServer::<Props>::new();
+		let mut props = props();
+		assert!(server.get(&props, "cache").is_err());
+
+		server
+			.set(&mut props, "name", "\"arm\"")
+			.expect("snh");
+		assert_eq!(props.name, "arm");
+		assert_eq!(props.cache, vec![1, 2, 3]);
+	}
+}
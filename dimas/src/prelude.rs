@@ -28,12 +28,19 @@ pub use zenoh::sample::Locality;
 
 // dimas stuff
 pub use crate::agent::Agent;
+pub use dimas_com::zenoh::TypedPublisher;
 pub use dimas_config::Config;
 pub use dimas_core::Result;
+pub use dimas_core::codec::{Bitcode, Cbor, Json};
+pub use dimas_core::enums::CapabilityKind;
+pub use dimas_core::handle::CapabilityHandle;
 pub use dimas_core::message_types::{
-	ControlResponse, Message, ObservableResponse, QueryMsg, QueryableMsg,
+	ControlResponse, Headers, Message, ObservableResponse, QueryMsg, QueryableMsg, Replies,
+	SampleInfo,
 };
-pub use dimas_core::traits::Context;
+pub use dimas_core::policies::{Backoff, ErrorPolicy, RestartPolicy, RetryPolicy};
+pub use dimas_core::trace_context::TraceContext;
+pub use dimas_core::traits::{Clock, Context};
 pub use dimas_core::utils::init_tracing;
 pub use dimas_macros::main;
-pub use dimas_time::Timer;
+pub use dimas_time::{CronSchedule, MissedTicks, SimulatedClock, SystemClock, Timer};
@@ -0,0 +1,158 @@
+// Copyright © 2024 Stephan Kunz
+
+//! Operating system signals handled by a running [`Agent`].
+//!
+//! - `ctrl-c` stops the [`Agent`] gracefully
+//! - `SIGTERM` stops the [`Agent`] gracefully, the same way as `ctrl-c` does
+//! - `SIGHUP` calls the `on_reload` hook
+//! - `SIGUSR1` logs a snapshot of the registered capabilities
+//!
+//! On non unix platforms only `ctrl-c` is received.
+
+// region:		--- modules
+// only for doc needed
+#[cfg(doc)]
+use crate::agent::Agent;
+use core::fmt::Display;
+#[cfg(unix)]
+use tokio::signal::unix::{Signal, SignalKind, signal};
+use tracing::error;
+// endregion:	--- modules
+
+// region:		--- OsSignal
+/// The operating system signals an [`Agent`] reacts on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OsSignal {
+	/// `ctrl-c`
+	Interrupt,
+	/// `SIGTERM`
+	Terminate,
+	/// `SIGHUP`
+	Reload,
+	/// `SIGUSR1`
+	Diagnostics,
+}
+
+impl Display for OsSignal {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			Self::Interrupt => write!(f, "ctrl-c"),
+			Self::Terminate => write!(f, "SIGTERM"),
+			Self::Reload => write!(f, "SIGHUP"),
+			Self::Diagnostics => write!(f, "SIGUSR1"),
+		}
+	}
+}
+
+impl OsSignal {
+	/// Whether the signal requests to stop the [`Agent`]
+	#[must_use]
+	pub const fn is_shutdown(self) -> bool {
+		matches!(self, Self::Interrupt | Self::Terminate)
+	}
+}
+// endregion:	--- OsSignal
+
+// region:		--- OsSignals
+/// Listener for the [`OsSignal`]s
+#[derive(Debug)]
+pub struct OsSignals {
+	#[cfg(unix)]
+	terminate: Option<Signal>,
+	#[cfg(unix)]
+	reload: Option<Signal>,
+	#[cfg(unix)]
+	diagnostics: Option<Signal>,
+}
+
+impl Default for OsSignals {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl OsSignals {
+	/// Constructor, must be called within a tokio runtime.
+	/// A signal which can not be registered is logged and ignored.
+	#[must_use]
+	pub fn new() -> Self {
+		#[cfg(unix)]
+		{
+			Self {
+				terminate: register(SignalKind::terminate(), "SIGTERM"),
+				reload: register(SignalKind::hangup(), "SIGHUP"),
+				diagnostics: register(SignalKind::user_defined1(), "SIGUSR1"),
+			}
+		}
+		#[cfg(not(unix))]
+		{
+			Self {}
+		}
+	}
+
+	/// Wait for the next [`OsSignal`]
+	#[cfg(unix)]
+	pub async fn recv(&mut self) -> OsSignal {
+		tokio::select! {
+			result = tokio::signal::ctrl_c() => interrupt(result),
+			Some(()) = recv(self.terminate.as_mut()) => OsSignal::Terminate,
+			Some(()) = recv(self.reload.as_mut()) => OsSignal::Reload,
+			Some(()) = recv(self.diagnostics.as_mut()) => OsSignal::Diagnostics,
+			else => core::future::pending().await,
+		}
+	}
+
+	/// Wait for the next [`OsSignal`], only `ctrl-c` is available on this platform
+	#[cfg(not(unix))]
+	pub async fn recv(&mut self) -> OsSignal {
+		interrupt(tokio::signal::ctrl_c().await)
+	}
+}
+
+/// If listening for `ctrl-c` fails, we also try to shut down the agent properly
+fn interrupt(result: std::io::Result<()>) -> OsSignal {
+	if let Err(err) = result {
+		error!("Unable to listen for 'Ctrl-C': {err}");
+	}
+	OsSignal::Interrupt
+}
+
+#[cfg(unix)]
+fn register(kind: SignalKind, name: &str) -> Option<Signal> {
+	signal(kind)
+		.map_err(|err| error!("Unable to listen for '{name}': {err}"))
+		.ok()
+}
+
+/// Receive from an optional [`Signal`], an unregistered one never fires
+#[cfg(unix)]
+async fn recv(signal: Option<&mut Signal>) -> Option<()> {
+	match signal {
+		Some(signal) => signal.recv().await,
+		None => core::future::pending().await,
+	}
+}
+// endregion:	--- OsSignals
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// check, that the auto traits are available
+	const fn is_normal<T: Sized + Send + Sync>() {}
+
+	#[test]
+	const fn normal_types() {
+		is_normal::<OsSignal>();
+		is_normal::<OsSignals>();
+	}
+
+	#[test]
+	fn shutdown_signals() {
+		assert!(OsSignal::Interrupt.is_shutdown());
+		assert!(OsSignal::Terminate.is_shutdown());
+		assert!(!OsSignal::Reload.is_shutdown());
+		assert!(!OsSignal::Diagnostics.is_shutdown());
+		assert_eq!(OsSignal::Terminate.to_string(), "SIGTERM");
+	}
+}
@@ -6,8 +6,10 @@
 use clap::{Parser, Subcommand};
 use core::time::Duration;
 use dimas_com::zenoh::Communicator;
+use dimas_commands::messages::{AboutEntity, ParametersEntity, StateEntity};
 use dimas_config::Config;
 use dimas_core::{Result, enums::OperationState};
+use dimas_time::SystemClock;
 // endregion:	--- modules
 
 // region:		--- Cli
@@ -29,8 +31,17 @@ fn operation_state_parser(s: &str) -> Result<OperationState> {
 // region:		--- Commands
 #[derive(Debug, Subcommand)]
 enum DimasctlCommand {
+	/// List the registered capabilities of entities
+	Capabilities,
+	/// Get a parameter of entities
+	GetParam {
+		/// The name of the parameter
+		name: String,
+	},
 	/// List running `DiMAS` entities
 	List,
+	/// List the parameters of entities
+	Params,
 	/// Ping entities
 	Ping {
 		/// Selector for the targets to ping
@@ -40,8 +51,21 @@ enum DimasctlCommand {
 		#[arg(short, long, default_value = "1")]
 		count: u8,
 	},
+	/// Recover entities from state `Error`
+	Recover {
+		/// The safe state to tear down to
+		#[arg(value_parser = operation_state_parser)]
+		state: OperationState,
+	},
 	/// Scout for `Zenoh` entities
 	Scout,
+	/// Set a parameter of entities
+	SetParam {
+		/// The name of the parameter
+		name: String,
+		/// The new value, JSON encoded
+		value: String,
+	},
 	/// Set state of entities
 	SetState {
 		/// The new state
@@ -57,13 +81,62 @@ enum DimasctlCommand {
 }
 // endregion:	--- Commands
 
+const H_ZID: &str = "ZenohId";
+const H_KIND: &str = "Kind";
+const H_STATE: &str = "State";
+const H_NAME: &str = "Prefix/Name";
+
+fn print_about_list(title: &str, list: &[AboutEntity]) {
+	println!("{title}");
+	println!("{H_ZID:32}  {H_KIND:6}  {H_STATE:10}  {H_NAME}");
+	for item in list {
+		println!(
+			"{:32}  {:6}  {:10}  {}",
+			item.zid(),
+			item.kind(),
+			item.state().to_string(),
+			item.name()
+		);
+		if let Some(cause) = item.error() {
+			println!("{:32}  last error: {cause}", "");
+		}
+	}
+}
+
+fn print_state_list(title: &str, list: &[StateEntity]) {
+	println!("{title}");
+	println!("{H_ZID:32}  {H_KIND:6}  {H_STATE:10}  {H_NAME}");
+	for item in list {
+		println!(
+			"{:32}  {:6}  {:10}  {}",
+			item.zid(),
+			item.kind(),
+			item.state().to_string(),
+			item.name()
+		);
+		if let Some(reason) = item.rejection() {
+			println!("{:32}  rejected: {reason}", "");
+		}
+	}
+}
+
+fn print_parameters_list(title: &str, list: &[ParametersEntity]) {
+	println!("{title}");
+	for item in list {
+		println!("{:32}  {}", item.zid(), item.name());
+		if let Some(reason) = item.rejection() {
+			println!("{:32}  rejected: {reason}", "");
+		}
+		for parameter in item.parameters() {
+			println!("{:32}  {parameter}", "");
+		}
+	}
+}
+
+#[allow(clippy::too_many_lines)]
 fn main() -> Result<()> {
 	let args = DimasctlArgs::parse();
 	let config = Config::default();
-	let h_zid = "ZenohId";
-	let h_kind = "Kind";
-	let h_state = "State";
-	let h_name = "Prefix/Name";
 
 	let base_selector = args
 		.selector
@@ -71,22 +144,36 @@ fn main() -> Result<()> {
 		.map_or_else(|| String::from("**"), |selector| selector);
 
 	match &args.command {
-		DimasctlCommand::List => {
+		DimasctlCommand::Capabilities => {
 			let com =
 				Communicator::new(config.zenoh_config()).expect("failed to create 'Communicator'");
-			println!("List of found DiMAS entities:");
-			println!("{h_zid:32}  {h_kind:6}  {h_state:10}  {h_name}");
-			let list = dimas_commands::about_list(&com, &base_selector)?;
+			let list = dimas_commands::capabilities_list(&com, &base_selector)?;
+			println!("List of capabilities of DiMAS entities:");
 			for item in list {
-				println!(
-					"{:32}  {:6}  {:10}  {}",
-					item.zid(),
-					item.kind(),
-					item.state().to_string(),
-					item.name()
-				);
+				println!("{:32}  {}", item.zid(), item.name());
+				for capability in item.capabilities() {
+					println!("{:32}  {capability}", "");
+				}
 			}
 		}
+		DimasctlCommand::GetParam { name } => {
+			let com =
+				Communicator::new(config.zenoh_config()).expect("failed to create 'Communicator'");
+			let list = dimas_commands::get_parameter(&com, &base_selector, name)?;
+			print_parameters_list("Parameter of DiMAS entities:", &list);
+		}
+		DimasctlCommand::List => {
+			let com =
+				Communicator::new(config.zenoh_config()).expect("failed to create 'Communicator'");
+			let list = dimas_commands::about_list(&com, &base_selector)?;
+			print_about_list("List of found DiMAS entities:", &list);
+		}
+		DimasctlCommand::Params => {
+			let com =
+				Communicator::new(config.zenoh_config()).expect("failed to create 'Communicator'");
+			let list = dimas_commands::list_parameters(&com, &base_selector)?;
+			print_parameters_list("Parameters of DiMAS entities:", &list);
+		}
 		DimasctlCommand::Ping { target, count } => {
 			let target = args
 				.selector
@@ -94,7 +181,7 @@ fn main() -> Result<()> {
 			let com =
 				Communicator::new(config.zenoh_config()).expect("failed to create 'Communicator'");
 			for _ in 0..*count {
-				let list = dimas_commands::ping_list(&com, &target)?;
+				let list = dimas_commands::ping_list(&com, &target, &SystemClock)?;
 				for item in list {
 					#[allow(clippy::cast_precision_loss)]
 					let time = item.1 as f64 / 2_000_000.0;
@@ -106,6 +193,12 @@ fn main() -> Result<()> {
 				}
 			}
 		}
+		DimasctlCommand::Recover { state } => {
+			let com =
+				Communicator::new(config.zenoh_config()).expect("failed to create 'Communicator'");
+			let list = dimas_commands::recover(&com, &base_selector, state.to_owned())?;
+			print_state_list("List of recovered DiMAS entities:", &list);
+		}
 		DimasctlCommand::Scout => {
 			println!("List of scouted Zenoh entities:");
 			println!("ZenohId                           Kind    Locators");
@@ -119,21 +212,17 @@ fn main() -> Result<()> {
 				);
 			}
 		}
+		DimasctlCommand::SetParam { name, value } => {
+			let com =
+				Communicator::new(config.zenoh_config()).expect("failed to create 'Communicator'");
+			let list = dimas_commands::set_parameter(&com, &base_selector, name, value)?;
+			print_parameters_list("Changed parameter of DiMAS entities:", &list);
+		}
 		DimasctlCommand::SetState { state } => {
 			let com =
 				Communicator::new(config.zenoh_config()).expect("failed to create 'Communicator'");
-			println!("List of current states of DiMAS entities:");
-			println!("{h_zid:32}  {h_kind:6}  {h_state:10}  {h_name}");
 			let list = dimas_commands::set_state(&com, &base_selector, state.to_owned())?;
-			for item in list {
-				println!(
-					"{:32}  {:6}  {:10}  {}",
-					item.zid(),
-					item.kind(),
-					item.state().to_string(),
-					item.name()
-				);
-			}
+			print_state_list("List of current states of DiMAS entities:", &list);
 		}
 		DimasctlCommand::Shutdown { target } => {
 			let target = args
@@ -141,18 +230,8 @@ fn main() -> Result<()> {
 				.map_or_else(|| target.to_owned(), |value| format!("{value}/{target}"));
 			let com =
 				Communicator::new(config.zenoh_config()).expect("failed to create 'Communicator'");
-			println!("List of shut down DiMAS entities:");
-			println!("{h_zid:32}  {h_kind:6}  {h_state:10}  {h_name}");
 			let list = dimas_commands::shutdown(&com, &target)?;
-			for item in list {
-				println!(
-					"{:32}  {:6}  {:10}  {}",
-					item.zid(),
-					item.kind(),
-					item.state().to_string(),
-					item.name()
-				);
-			}
+			print_about_list("List of shut down DiMAS entities:", &list);
 		}
 	}
 	Ok(())